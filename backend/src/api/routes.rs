@@ -1,16 +1,22 @@
 use axum::{
     Router,
-    extract::State,
-    http::StatusCode,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
     response::{
         IntoResponse, Json, Response,
         sse::{Event, KeepAlive, Sse},
     },
     routing::{get, post},
 };
-use futures::stream::{Stream, StreamExt};
+use futures::future;
+use futures::stream::{self, Stream, StreamExt};
+use serde::Deserialize;
 use serde_json::json;
-use shared::{GameError, GameState, MakeMoveRequest, MoveSource, TauntRequest};
+use shared::{
+    ConcedeRequest, GameError, GameState, LeaveGameRequest, MakeMoveRequest,
+    PairingRequestResponse, PairingStatusResponse, SendEmoteRequest, TauntRequest,
+};
+use std::collections::VecDeque;
 use std::convert::Infallible;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
@@ -18,15 +24,100 @@ use tokio::sync::broadcast;
 use tokio_stream::wrappers::BroadcastStream;
 use tracing::info;
 
+use crate::auth::{self, AuthError, AuthUser, Role};
+
+/// Body for POST /api/login
+#[derive(Debug, Deserialize)]
+struct LoginRequest {
+    sub: String,
+    #[serde(default)]
+    role: Role,
+    /// Required, and checked against `GAME_ADMIN_TOKEN`, for any `role` other than `Player` --
+    /// otherwise an anonymous caller could self-issue the very `Agent`/`Admin` role the rest of
+    /// the API gates `restart_game`/`reset_leaderboard`/etc. behind.
+    #[serde(default)]
+    admin_token: Option<String>,
+}
+
+/// Query params for GET /api/game
+#[derive(Debug, Deserialize)]
+struct GameQuery {
+    #[serde(rename = "gameId")]
+    game_id: Option<String>,
+    /// If set and the game's state version hasn't advanced past this, respond 304 instead of
+    /// re-sending the full state (see `get_game_state` below).
+    #[serde(rename = "sinceVersion")]
+    since_version: Option<u64>,
+}
+
+/// Query params for GET /api/pairing/status
+#[derive(Debug, Deserialize)]
+struct PairingStatusQuery {
+    #[serde(rename = "pairingId")]
+    pairing_id: String,
+}
+
+/// Query params for GET /api/leaderboard
+#[derive(Debug, Deserialize)]
+struct LeaderboardQuery {
+    limit: Option<u32>,
+}
+
 use crate::game::manager::GameManager;
-use crate::mcp::protocol::JsonRpcRequest;
 use crate::mcp::server::McpServer;
 
+/// How many past broadcast snapshots `SseHistory` retains for `Last-Event-ID` replay.
+const SSE_HISTORY_CAPACITY: usize = 100;
+
+/// A bounded ring buffer of the most recently broadcast `GameState` snapshots, keyed by a
+/// monotonically increasing sequence number. Backs `game_events`'s `Last-Event-ID` replay: a
+/// reconnecting client hands back the last sequence number it saw, and we replay whatever it
+/// missed instead of it silently losing those updates.
+#[derive(Default)]
+struct SseHistory {
+    next_seq: u64,
+    buffer: VecDeque<(u64, GameState)>,
+}
+
+impl SseHistory {
+    /// Record a newly broadcast snapshot, returning the sequence number assigned to it.
+    fn push(&mut self, game_state: GameState) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.buffer.push_back((seq, game_state));
+        if self.buffer.len() > SSE_HISTORY_CAPACITY {
+            self.buffer.pop_front();
+        }
+        seq
+    }
+}
+
+/// Messages published on `AppState::sse_tx`: either a new game-state snapshot, or the sentinel
+/// sent once as the server shuts down so connected SSE clients can close cleanly instead of
+/// just being cut off.
+#[derive(Clone)]
+pub enum SseMessage {
+    Snapshot(u64, GameState),
+    Shutdown,
+}
+
 /// Shared application state
 #[derive(Clone)]
 pub struct AppState {
     pub game_manager: Arc<Mutex<GameManager>>,
-    pub sse_tx: broadcast::Sender<GameState>,
+    pub sse_tx: broadcast::Sender<SseMessage>,
+    sse_history: Arc<Mutex<SseHistory>>,
+}
+
+impl AppState {
+    pub fn new(game_manager: Arc<Mutex<GameManager>>) -> Self {
+        let (sse_tx, _) = broadcast::channel(64);
+        Self {
+            game_manager,
+            sse_tx,
+            sse_history: Arc::new(Mutex::new(SseHistory::default())),
+        }
+    }
 }
 
 /// Wrapper for GameError to implement IntoResponse
@@ -44,6 +135,7 @@ impl IntoResponse for ApiError {
             GameError::CellOccupied { .. }
             | GameError::OutOfBounds { .. }
             | GameError::WrongTurn { .. }
+            | GameError::TurnExpired { .. }
             | GameError::GameOver { .. } => (StatusCode::BAD_REQUEST, self.0.to_string()),
             GameError::GameNotFound => (StatusCode::NOT_FOUND, self.0.to_string()),
             GameError::DatabaseError { .. } | GameError::InternalError { .. } => {
@@ -55,38 +147,100 @@ impl IntoResponse for ApiError {
     }
 }
 
-/// Helper to broadcast game state changes via SSE
+/// Helper to broadcast game state changes via SSE. Records the snapshot in `sse_history` (for
+/// reconnecting clients' `Last-Event-ID` replay) before publishing it live.
 fn broadcast_state(state: &AppState, game_state: &GameState) {
+    let seq = state.sse_history.lock().unwrap().push(game_state.clone());
     // Ignore send errors (no clients connected is fine)
-    let _ = state.sse_tx.send(game_state.clone());
+    let _ = state.sse_tx.send(SseMessage::Snapshot(seq, game_state.clone()));
 }
 
-/// GET /api/game - Get current game state
-async fn get_game_state(State(state): State<AppState>) -> Result<Json<GameState>, ApiError> {
-    info!("GET /api/game");
+/// GET /api/game - Get current game state, or a specific session's with `?gameId=`.
+/// `?sinceVersion=` makes this a conditional fetch: if the game hasn't advanced past that
+/// version, responds 304 Not Modified with no body instead of re-sending the full state.
+/// Either way, the response carries the current version in an `X-State-Version` header.
+async fn get_game_state(
+    State(state): State<AppState>,
+    Query(query): Query<GameQuery>,
+) -> Result<Response, ApiError> {
+    info!(
+        "GET /api/game - gameId: {:?}, sinceVersion: {:?}",
+        query.game_id, query.since_version
+    );
 
     let mut manager = state.game_manager.lock().unwrap();
-    let game_state = manager.get_or_create_game()?;
+    let game_state = manager.get_game_state_in(query.game_id.as_deref())?;
+    let version = manager.state_version(&game_state.id);
+
+    if let Some(since) = query.since_version
+        && version <= since
+    {
+        return Ok((
+            StatusCode::NOT_MODIFIED,
+            [("X-State-Version", version.to_string())],
+        )
+            .into_response());
+    }
 
     info!("Returning game state: {}", game_state.id);
-    Ok(Json(game_state))
+    Ok((
+        StatusCode::OK,
+        [("X-State-Version", version.to_string())],
+        Json(game_state),
+    )
+        .into_response())
 }
 
-/// POST /api/game/new - Create a new game
-async fn create_new_game(State(state): State<AppState>) -> Result<Json<GameState>, ApiError> {
+/// POST /api/login - Issue a JWT for `sub`/`role`, to present as `Authorization: Bearer <jwt>`
+/// on every state-modifying route. `role: "player"` (the default) needs nothing further; any
+/// other role requires `admin_token` to match `GAME_ADMIN_TOKEN`, the same shared-secret-env-var
+/// pattern `GAME_MCP_AUTH_TOKEN` uses for `connect` -- otherwise this endpoint would let any
+/// anonymous caller mint the very role the rest of the API gates privileged routes behind.
+async fn login(Json(request): Json<LoginRequest>) -> Result<Json<serde_json::Value>, AuthError> {
+    info!("POST /api/login - sub: {}, role: {:?}", request.sub, request.role);
+
+    if request.role != Role::Player {
+        let configured = std::env::var("GAME_ADMIN_TOKEN").ok();
+        let authorized = configured
+            .as_deref()
+            .is_some_and(|secret| request.admin_token.as_deref() == Some(secret));
+        if !authorized {
+            return Err(AuthError::forbidden(
+                "Issuing a non-player role requires a valid admin_token",
+            ));
+        }
+    }
+
+    let token = auth::sign(&request.sub, request.role)
+        .map_err(|e| AuthError::unauthorized(format!("Failed to issue token: {}", e)))?;
+
+    Ok(Json(json!({ "token": token })))
+}
+
+/// POST /api/game/new - Create a new game. Only an `Admin` token may restart the game.
+async fn create_new_game(State(state): State<AppState>, user: AuthUser) -> Response {
+    if !user.claims.can_restart() {
+        return AuthError::forbidden("Role not permitted to restart the game").into_response();
+    }
+
     info!("POST /api/game/new");
 
     let mut manager = state.game_manager.lock().unwrap();
-    let game_state = manager.restart_game()?;
+    let game_state = match manager.restart_game() {
+        Ok(game_state) => game_state,
+        Err(e) => return ApiError::from(e).into_response(),
+    };
+    drop(manager);
 
     info!("Created new game: {}", game_state.id);
     broadcast_state(&state, &game_state);
-    Ok(Json(game_state))
+    Json(game_state).into_response()
 }
 
 /// POST /api/game/move - Make a move
 async fn make_move(
     State(state): State<AppState>,
+    user: AuthUser,
     Json(request): Json<MakeMoveRequest>,
 ) -> Result<Json<GameState>, ApiError> {
     info!(
@@ -94,8 +248,17 @@ async fn make_move(
         request.row, request.col
     );
 
+    // Only a Player's own token may relabel its move as AI-sourced; an Agent token always
+    // records MCP regardless of what the request body claims.
+    let source = if request.local_ai && user.claims.role == Role::Player {
+        shared::MoveSource::AI
+    } else {
+        user.claims.move_source()
+    };
+
     let mut manager = state.game_manager.lock().unwrap();
-    let game_state = manager.make_move(request.row, request.col, MoveSource::UI)?;
+    let game_state =
+        manager.make_move_in_game(request.game_id.as_deref(), request.row, request.col, source)?;
 
     info!("Move made successfully");
     broadcast_state(&state, &game_state);
@@ -105,39 +268,188 @@ async fn make_move(
 /// POST /api/game/taunt - Add a taunt message
 async fn add_taunt(
     State(state): State<AppState>,
+    user: AuthUser,
     Json(request): Json<TauntRequest>,
 ) -> Result<StatusCode, ApiError> {
     info!("POST /api/game/taunt - message: {}", request.message);
 
     let mut manager = state.game_manager.lock().unwrap();
-    manager.add_taunt(request.message)?;
+    manager.add_taunt_in_game(
+        request.game_id.as_deref(),
+        request.message,
+        user.claims.move_source(),
+    )?;
 
     info!("Taunt added successfully");
     Ok(StatusCode::OK)
 }
 
-/// GET /api/events - Server-Sent Events stream for game state updates
+/// POST /api/pairing/request - Request a human-vs-human match. Poll the returned pairing id via
+/// GET /api/pairing/status.
+async fn request_pairing(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> Result<Json<PairingRequestResponse>, ApiError> {
+    info!("POST /api/pairing/request - player: {}", user.claims.sub);
+
+    let mut manager = state.game_manager.lock().unwrap();
+    let pairing_id = manager.request_pairing(&user.claims.sub)?;
+
+    Ok(Json(PairingRequestResponse { pairing_id }))
+}
+
+/// GET /api/pairing/status?pairingId= - Poll a pairing requested via /api/pairing/request
+async fn pairing_status(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(query): Query<PairingStatusQuery>,
+) -> Result<Json<PairingStatusResponse>, ApiError> {
+    let manager = state.game_manager.lock().unwrap();
+    manager
+        .pairing_status(&query.pairing_id, &user.claims.sub)
+        .map(Json)
+        .ok_or_else(|| ApiError::from(GameError::GameNotFound))
+}
+
+/// POST /api/game/leave - Forfeit a human-vs-human match to the other seat, e.g. on page close
+async fn leave_game(
+    State(state): State<AppState>,
+    Json(request): Json<LeaveGameRequest>,
+) -> Result<StatusCode, ApiError> {
+    info!("POST /api/game/leave - game: {}", request.game_id);
+
+    let mut manager = state.game_manager.lock().unwrap();
+    let game_state = manager.leave_game(&request.game_id, &request.token)?;
+    drop(manager);
+
+    broadcast_state(&state, &game_state);
+    Ok(StatusCode::OK)
+}
+
+/// POST /api/game/concede - Forfeit the game, handing the win to the AI
+async fn concede_game(
+    State(state): State<AppState>,
+    _user: AuthUser,
+    Json(request): Json<ConcedeRequest>,
+) -> Result<Json<GameState>, ApiError> {
+    info!("POST /api/game/concede");
+
+    let mut manager = state.game_manager.lock().unwrap();
+    let game_state = manager.concede_game_in(request.game_id.as_deref())?;
+    drop(manager);
+
+    info!("Game conceded");
+    broadcast_state(&state, &game_state);
+    Ok(Json(game_state))
+}
+
+/// POST /api/game/emote - Send a predefined quick emote
+async fn add_emote(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(request): Json<SendEmoteRequest>,
+) -> Result<StatusCode, ApiError> {
+    info!("POST /api/game/emote - emote: {}", request.emote.as_str());
+
+    let mut manager = state.game_manager.lock().unwrap();
+    manager.add_emote_in_game(
+        request.game_id.as_deref(),
+        request.emote,
+        user.claims.move_source(),
+    )?;
+
+    info!("Emote added successfully");
+    Ok(StatusCode::OK)
+}
+
+/// GET /api/leaderboard - Top players by wins, with win rate, across every finished game
+async fn get_leaderboard(
+    State(state): State<AppState>,
+    Query(query): Query<LeaderboardQuery>,
+) -> Result<Json<Vec<shared::LeaderboardEntry>>, ApiError> {
+    let limit = query.limit.unwrap_or(10);
+    info!("GET /api/leaderboard - limit: {}", limit);
+
+    let manager = state.game_manager.lock().unwrap();
+    let leaderboard = manager.get_leaderboard(limit)?;
+    Ok(Json(leaderboard))
+}
+
+/// Render one buffered/live `(seq, GameState)` snapshot as an SSE event carrying its sequence
+/// number as the event id, so a future reconnect can resume from it via `Last-Event-ID`.
+fn snapshot_event(seq: u64, game_state: &GameState) -> Option<Event> {
+    match serde_json::to_string(game_state) {
+        Ok(json) => Some(Event::default().id(seq.to_string()).data(json)),
+        Err(e) => {
+            tracing::error!("Failed to serialize game state for SSE: {}", e);
+            None
+        }
+    }
+}
+
+/// GET /api/events - Server-Sent Events stream for game state updates. A reconnecting client
+/// that sends `Last-Event-ID` is first replayed whatever it missed from `sse_history` before
+/// subscribing to the live stream; if its id is older than the buffer's oldest entry, it gets a
+/// single "resync" event carrying the full current state instead.
 async fn game_events(
     State(state): State<AppState>,
+    headers: HeaderMap,
 ) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
     info!("Client connected to SSE stream");
 
+    let last_event_id = headers
+        .get("Last-Event-ID")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.parse::<u64>().ok());
+
+    let mut replay: Vec<Event> = Vec::new();
+    if let Some(last_seq) = last_event_id {
+        let history = state.sse_history.lock().unwrap();
+        let needs_resync = history.buffer.front().is_some_and(|(oldest, _)| last_seq < *oldest);
+        if needs_resync {
+            let resync_seq = history.next_seq.saturating_sub(1);
+            drop(history);
+            if let Ok(game_state) = state.game_manager.lock().unwrap().get_game_state() {
+                replay.extend(
+                    snapshot_event(resync_seq, &game_state).map(|e| e.event("resync")),
+                );
+            }
+        } else {
+            replay.extend(
+                history
+                    .buffer
+                    .iter()
+                    .filter(|(seq, _)| *seq > last_seq)
+                    .filter_map(|(seq, game_state)| snapshot_event(*seq, game_state)),
+            );
+        }
+    }
+
     let rx = state.sse_tx.subscribe();
-    let stream = BroadcastStream::new(rx).filter_map(|result| async move {
-        match result {
-            Ok(game_state) => match serde_json::to_string(&game_state) {
-                Ok(json) => Some(Ok(Event::default().data(json))),
+    // Ends the stream itself right after the shutdown event, instead of just emitting it and
+    // continuing to poll, so a graceful shutdown's "wait for in-flight handlers" doesn't hang on
+    // an SSE connection that would otherwise never complete.
+    let live = BroadcastStream::new(rx)
+        .scan(false, |finished, result| {
+            if *finished {
+                return future::ready(None);
+            }
+            let event = match result {
+                Ok(SseMessage::Snapshot(seq, game_state)) => snapshot_event(seq, &game_state),
+                Ok(SseMessage::Shutdown) => {
+                    *finished = true;
+                    Some(Event::default().event("shutdown").data("server shutting down"))
+                }
                 Err(e) => {
-                    tracing::error!("Failed to serialize game state for SSE: {}", e);
+                    tracing::error!("SSE broadcast error: {}", e);
                     None
                 }
-            },
-            Err(e) => {
-                tracing::error!("SSE broadcast error: {}", e);
-                None
-            }
-        }
-    });
+            };
+            future::ready(Some(event.map(Ok)))
+        })
+        .filter_map(|item| future::ready(item));
+
+    let stream = stream::iter(replay.into_iter().map(Ok)).chain(live);
 
     Sse::new(stream).keep_alive(
         KeepAlive::new()
@@ -146,54 +458,82 @@ async fn game_events(
     )
 }
 
-/// POST /mcp - MCP protocol over HTTP (JSON-RPC 2.0)
+/// POST /mcp - MCP protocol over HTTP (JSON-RPC 2.0). Accepts either a single request object or
+/// a JSON-RPC batch array; `McpServer::handle_request` already knows how to dispatch both, so
+/// this handler's own job is just the HTTP-layer bits: per-method role gating, a single manager
+/// lock around the whole batch, and broadcasting once if anything in it changed game state.
 async fn mcp_handler(
     State(state): State<AppState>,
+    user: AuthUser,
     Json(request): Json<serde_json::Value>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
-    info!("MCP HTTP request received");
+) -> Result<Response, StatusCode> {
+    info!("MCP HTTP request received from {}", user.claims.sub);
+
+    let items: Vec<&serde_json::Value> = match &request {
+        serde_json::Value::Array(batch) => batch.iter().collect(),
+        single => vec![single],
+    };
+
+    for item in &items {
+        let method = item.get("method").and_then(|m| m.as_str());
+        // `run_match`'s `external` bot strategy (which spawns an arbitrary host
+        // executable/argv) is gated uniformly for every transport inside `McpServer::
+        // dispatch_inner` itself -- see `set_admin_override` below -- rather than here.
+        let requires_admin = matches!(method, Some("restart_game") | Some("reset_leaderboard"));
+        if requires_admin && !user.claims.can_restart() {
+            tracing::warn!(
+                "Rejected {} from {} (role does not permit it)",
+                method.unwrap_or("?"), user.claims.sub
+            );
+            return Err(StatusCode::FORBIDDEN);
+        }
+    }
 
-    // Parse JSON-RPC request
     let json_str = serde_json::to_string(&request).map_err(|_| StatusCode::BAD_REQUEST)?;
-    let rpc_request = JsonRpcRequest::from_json(&json_str).map_err(|e| {
-        tracing::error!("Failed to parse JSON-RPC request: {}", e.message);
-        StatusCode::BAD_REQUEST
-    })?;
-
-    // Validate request
-    if let Err(e) = rpc_request.validate() {
-        tracing::error!("Invalid JSON-RPC request: {}", e.message);
-        return Err(StatusCode::BAD_REQUEST);
-    }
 
-    // Create temporary MCP server (it's stateless except for the game manager)
+    // Create temporary MCP server (it's stateless except for the game manager). It has no
+    // session of its own to mark admin, so pass the caller's already-checked JWT `Role`
+    // through directly -- this is what lets `run_match`'s `external` strategy gate apply here
+    // too, not just over stdio/the Unix socket transport.
     let mut manager = state.game_manager.lock().unwrap();
     let mut mcp_server = McpServer::new_with_manager(&mut manager);
+    mcp_server.set_admin_override(user.claims.can_restart());
 
-    // Handle the request
+    // Handle the request (single or batch; handle_request dispatches every element in order)
     let response_str = mcp_server.handle_request(&json_str);
     drop(manager); // Release lock before broadcasting
 
+    // An all-notification batch (or a lone notification) has nothing to reply with
+    if response_str.is_empty() {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    }
+
     // Parse response
     let response: serde_json::Value =
         serde_json::from_str(&response_str).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    // If the MCP call modified the game state, broadcast it
-    // (We check if it's a successful result for state-modifying methods)
-    if let Some(result) = response.get("result")
-        && !result.is_null()
-    {
-        let method = rpc_request.method.as_str();
-        if matches!(method, "make_move" | "restart_game") {
-            // Fetch updated state and broadcast
-            let mut manager = state.game_manager.lock().unwrap();
-            if let Ok(game_state) = manager.get_game_state() {
-                broadcast_state(&state, &game_state);
-            }
+    // If any call in the batch modified the game state, broadcast it once
+    // (We check if there's a successful result for a state-modifying method anywhere)
+    let modified_state = items.iter().any(|item| {
+        item.get("method")
+            .and_then(|m| m.as_str())
+            .is_some_and(|m| matches!(m, "make_move" | "restart_game"))
+    });
+    let has_success = match &response {
+        serde_json::Value::Array(responses) => responses
+            .iter()
+            .any(|r| r.get("result").is_some_and(|v| !v.is_null())),
+        single => single.get("result").is_some_and(|v| !v.is_null()),
+    };
+
+    if modified_state && has_success {
+        let mut manager = state.game_manager.lock().unwrap();
+        if let Ok(game_state) = manager.get_game_state() {
+            broadcast_state(&state, &game_state);
         }
     }
 
-    Ok(Json(response))
+    Ok(Json(response).into_response())
 }
 
 /// Health check endpoint
@@ -205,10 +545,17 @@ async fn health_check() -> &'static str {
 pub fn create_router(state: AppState) -> Router {
     Router::new()
         .route("/health", get(health_check))
+        .route("/api/login", post(login))
         .route("/api/game", get(get_game_state))
         .route("/api/game/new", post(create_new_game))
         .route("/api/game/move", post(make_move))
         .route("/api/game/taunt", post(add_taunt))
+        .route("/api/game/emote", post(add_emote))
+        .route("/api/game/concede", post(concede_game))
+        .route("/api/pairing/request", post(request_pairing))
+        .route("/api/pairing/status", get(pairing_status))
+        .route("/api/game/leave", post(leave_game))
+        .route("/api/leaderboard", get(get_leaderboard))
         .route("/api/events", get(game_events))
         .route("/mcp", post(mcp_handler))
         .with_state(state)
@@ -0,0 +1,118 @@
+//! Server bootstrap: binds the REST/SSE/`/mcp` router and runs it with a graceful shutdown path
+//! (SIGINT/SIGTERM, or a POST to `/admin/shutdown`) instead of letting a signal tear the process
+//! down mid-move.
+
+use crate::api::routes::{AppState, SseMessage, create_router};
+use crate::auth::AuthUser;
+use crate::game::manager::GameManager;
+use axum::http::StatusCode;
+use axum::routing::post;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::signal;
+use tokio::sync::{Notify, broadcast};
+
+/// Start the server on `port`, backed by the game database at `db_path`, and run it until a
+/// shutdown signal arrives. Drains in-flight requests, closes connected SSE clients with a final
+/// event, and flushes the current game state to disk before returning.
+pub async fn start_server(db_path: &str, port: u16) -> Result<(), Box<dyn std::error::Error>> {
+    let game_manager = Arc::new(Mutex::new(GameManager::new(db_path)?));
+    let state = AppState::new(game_manager.clone());
+
+    // Periodically forfeit turn-expired games and reap long-idle ones so the shared SQLite store
+    // doesn't grow unbounded, via the same `GameManager` every request goes through -- so its
+    // in-memory `seat_tokens`/`state_versions`/`current_game_id` bookkeeping is reaped along
+    // with the rows a repository-only delete can't see. Interval/timeouts are configurable since
+    // how aggressively to reap is a deployment decision, not a code one.
+    let cleanup_interval_secs = env_u64("GAME_CLEANUP_INTERVAL_SECS", 3_600);
+    let cleanup_turn_timeout_secs = env_u64("GAME_CLEANUP_TURN_TIMEOUT_SECS", 30 * 60);
+    let cleanup_idle_secs = env_u64("GAME_CLEANUP_TIMEOUT_SECS", 24 * 3_600);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(cleanup_interval_secs));
+        ticker.tick().await; // first tick fires immediately; skip it so we wait a full interval
+        loop {
+            ticker.tick().await;
+            let result = game_manager.lock().unwrap().cleanup_stale_games(
+                Duration::from_secs(cleanup_turn_timeout_secs),
+                Duration::from_secs(cleanup_idle_secs),
+            );
+            match result {
+                Ok((forfeited, deleted)) if forfeited > 0 || deleted > 0 => tracing::info!(
+                    "Cleaned up stale games: {} forfeited, {} deleted",
+                    forfeited, deleted
+                ),
+                Ok(_) => {}
+                Err(e) => tracing::error!("Failed to clean up stale games: {}", e),
+            }
+        }
+    });
+
+    let shutdown_notify = Arc::new(Notify::new());
+    let admin_shutdown_notify = shutdown_notify.clone();
+
+    let app = create_router(state.clone()).route(
+        "/admin/shutdown",
+        post(move |user: AuthUser| {
+            let notify = admin_shutdown_notify.clone();
+            async move {
+                if !user.claims.can_restart() {
+                    return StatusCode::FORBIDDEN;
+                }
+                tracing::warn!("Shutdown requested via /admin/shutdown by {}", user.claims.sub);
+                notify.notify_one();
+                StatusCode::ACCEPTED
+            }
+        }),
+    );
+
+    let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+    tracing::info!("Listening on {}", listener.local_addr()?);
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal(shutdown_notify, state.sse_tx.clone()))
+        .await?;
+
+    tracing::info!("Drained in-flight requests; persisting final game state");
+    state.game_manager.lock().unwrap().flush()?;
+
+    Ok(())
+}
+
+fn env_u64(name: &str, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Resolves once a SIGINT, SIGTERM, or `/admin/shutdown` request arrives, having already
+/// published `SseMessage::Shutdown` so connected SSE clients wind down instead of leaving
+/// `with_graceful_shutdown` waiting on a stream that never completes.
+async fn shutdown_signal(notify: Arc<Notify>, sse_tx: broadcast::Sender<SseMessage>) {
+    let ctrl_c = async {
+        signal::ctrl_c()
+            .await
+            .expect("failed to install SIGINT handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("Received SIGINT, shutting down gracefully"),
+        _ = terminate => tracing::info!("Received SIGTERM, shutting down gracefully"),
+        _ = notify.notified() => tracing::info!("Shutdown requested via /admin/shutdown"),
+    }
+
+    let _ = sse_tx.send(SseMessage::Shutdown);
+}
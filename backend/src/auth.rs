@@ -0,0 +1,214 @@
+//! JWT bearer authentication for the HTTP API: token issuance (`sign`/`/api/login`), validation
+//! (`verify`), and the `AuthUser` extractor that routes pull out of the `Authorization` header.
+
+use axum::{
+    extract::FromRequestParts,
+    http::{header::AUTHORIZATION, request::Parts, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// How long an issued token is valid for.
+const TOKEN_TTL_SECS: i64 = 3600;
+
+/// What a token's bearer is allowed to do. `Agent` tokens are MCP callers; `Player` tokens are
+/// the human at the browser; only `Admin` may call `restart_game`/`reset_leaderboard`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Player,
+    Agent,
+    Admin,
+}
+
+impl Default for Role {
+    fn default() -> Self {
+        Role::Player
+    }
+}
+
+/// Claims embedded in every token this server issues and validates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    /// Subject: the authenticated player/agent's identifier
+    pub sub: String,
+    /// Expiry, as Unix seconds
+    pub exp: i64,
+    pub role: Role,
+}
+
+impl Claims {
+    /// The `MoveSource` a move made under this token's role should be recorded with, so MCP
+    /// agents and human players calling the same REST routes remain distinguishable.
+    pub fn move_source(&self) -> shared::MoveSource {
+        match self.role {
+            Role::Agent => shared::MoveSource::MCP,
+            Role::Player | Role::Admin => shared::MoveSource::UI,
+        }
+    }
+
+    /// Whether this token's role may call `restart_game`/`reset_leaderboard`.
+    pub fn can_restart(&self) -> bool {
+        matches!(self.role, Role::Admin)
+    }
+}
+
+/// Auth failure, rendered with the same `{"error": ...}` JSON shape as `ApiError`.
+pub struct AuthError(StatusCode, String);
+
+impl AuthError {
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self(StatusCode::UNAUTHORIZED, message.into())
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self(StatusCode::FORBIDDEN, message.into())
+    }
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+/// The HS256 signing secret, from `GAME_JWT_SECRET`. Falls back to a fixed development value so
+/// the server still runs out of the box; deployments that care about this must set the env var.
+fn jwt_secret() -> String {
+    std::env::var("GAME_JWT_SECRET").unwrap_or_else(|_| "dev-secret-change-me".to_string())
+}
+
+fn now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+/// Issue a signed token for `sub` with `role`, valid for `TOKEN_TTL_SECS`.
+pub fn sign(sub: &str, role: Role) -> Result<String, jsonwebtoken::errors::Error> {
+    let claims = Claims {
+        sub: sub.to_string(),
+        exp: now() + TOKEN_TTL_SECS,
+        role,
+    };
+
+    encode(
+        &Header::default(), // HS256
+        &claims,
+        &EncodingKey::from_secret(jwt_secret().as_bytes()),
+    )
+}
+
+/// Decode and validate `token`, checking its signature and expiry.
+pub fn verify(token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+    decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(jwt_secret().as_bytes()),
+        &Validation::default(),
+    )
+    .map(|data| data.claims)
+}
+
+/// Axum extractor that pulls `Authorization: Bearer <jwt>` out of the request, decodes it, and
+/// rejects the request with `401 Unauthorized` if it's missing, malformed, or expired.
+pub struct AuthUser {
+    pub claims: Claims,
+}
+
+impl<S> FromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let header = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| AuthError::unauthorized("Missing Authorization header"))?;
+
+        let token = header
+            .strip_prefix("Bearer ")
+            .ok_or_else(|| AuthError::unauthorized("Authorization header must be a Bearer token"))?;
+
+        let claims = verify(token)
+            .map_err(|e| AuthError::unauthorized(format!("Invalid or expired token: {}", e)))?;
+
+        Ok(AuthUser { claims })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify_round_trip() {
+        let token = sign("agent-1", Role::Agent).unwrap();
+        let claims = verify(&token).unwrap();
+
+        assert_eq!(claims.sub, "agent-1");
+        assert_eq!(claims.role, Role::Agent);
+    }
+
+    #[test]
+    fn test_verify_rejects_garbage_token() {
+        assert!(verify("not-a-jwt").is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_expired_token() {
+        let claims = Claims {
+            sub: "agent-1".to_string(),
+            exp: now() - 10,
+            role: Role::Player,
+        };
+        let token = encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(jwt_secret().as_bytes()),
+        )
+        .unwrap();
+
+        assert!(verify(&token).is_err());
+    }
+
+    #[test]
+    fn test_agent_role_maps_to_mcp_move_source() {
+        let claims = Claims {
+            sub: "agent-1".to_string(),
+            exp: now() + 60,
+            role: Role::Agent,
+        };
+
+        assert_eq!(claims.move_source(), shared::MoveSource::MCP);
+    }
+
+    #[test]
+    fn test_player_role_maps_to_ui_move_source() {
+        let claims = Claims {
+            sub: "player-1".to_string(),
+            exp: now() + 60,
+            role: Role::Player,
+        };
+
+        assert_eq!(claims.move_source(), shared::MoveSource::UI);
+    }
+
+    #[test]
+    fn test_only_admin_role_can_restart() {
+        let make_claims = |role| Claims {
+            sub: "x".to_string(),
+            exp: now() + 60,
+            role,
+        };
+
+        assert!(!make_claims(Role::Player).can_restart());
+        assert!(!make_claims(Role::Agent).can_restart());
+        assert!(make_claims(Role::Admin).can_restart());
+    }
+}
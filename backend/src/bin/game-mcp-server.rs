@@ -1,4 +1,5 @@
 use backend::mcp::server::McpServer;
+use backend::mcp::transport::UnixSocketTransport;
 use std::env;
 use tracing_subscriber::{fmt, EnvFilter};
 
@@ -21,12 +22,32 @@ fn main() {
     tracing::info!("Using database path: {}", db_path);
 
     // Create and run server
+    // A Unix domain socket path to serve over, instead of stdin/stdout. Useful for long-running
+    // agents that want to keep a single server alive across many short-lived client processes.
+    let socket_path = env::var("GAME_MCP_SOCKET").ok();
+
     match McpServer::new(&db_path) {
         Ok(mut server) => {
             tracing::info!("MCP Server initialized successfully");
-            tracing::info!("Listening for JSON-RPC 2.0 requests on stdin...");
 
-            if let Err(e) = server.run() {
+            let result = match socket_path {
+                Some(path) => {
+                    tracing::info!("Listening for JSON-RPC 2.0 requests on Unix socket {}...", path);
+                    match UnixSocketTransport::bind(&path) {
+                        Ok(mut transport) => server.run_with_transport(&mut transport),
+                        Err(e) => {
+                            tracing::error!("Failed to bind Unix socket {}: {}", path, e);
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                None => {
+                    tracing::info!("Listening for JSON-RPC 2.0 requests on stdin...");
+                    server.run()
+                }
+            };
+
+            if let Err(e) = result {
                 tracing::error!("Server error: {}", e);
                 std::process::exit(1);
             }
@@ -1,13 +1,90 @@
 use rusqlite::{Connection, params};
-use shared::{Cell, GameError, GameState, GameStatus, Move, Player};
-use std::time::{SystemTime, UNIX_EPOCH};
+use shared::{
+    AiDifficulty, Cell, GameError, GameState, GameStatus, LeaderboardEntry, Move, OpenGame,
+    Player, PlayerStats,
+};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Encode a board as a 9-character string (row-major), one of `X`/`O`/`.` per cell — the
+/// compact snapshot format stored in the `games.board` column so `load_game` can skip replaying
+/// every move.
+pub(crate) fn serialize_board(board: &[[Cell; 3]; 3]) -> String {
+    board
+        .iter()
+        .flat_map(|row| row.iter())
+        .map(|cell| match cell {
+            Cell::Empty => '.',
+            Cell::Occupied(Player::X) => 'X',
+            Cell::Occupied(Player::O) => 'O',
+        })
+        .collect()
+}
+
+/// Decode a board previously encoded by [`serialize_board`]. Errors if the string isn't exactly
+/// 9 characters or contains anything other than `X`/`O`/`.`.
+pub(crate) fn deserialize_board(s: &str) -> Result<[[Cell; 3]; 3], GameError> {
+    let chars: Vec<char> = s.chars().collect();
+    if chars.len() != 9 {
+        return Err(GameError::DatabaseError {
+            message: format!(
+                "Invalid board snapshot: expected 9 cells, got {}",
+                chars.len()
+            ),
+        });
+    }
+
+    let mut board = [[Cell::Empty; 3]; 3];
+    for (i, c) in chars.into_iter().enumerate() {
+        let cell = match c {
+            '.' => Cell::Empty,
+            'X' => Cell::Occupied(Player::X),
+            'O' => Cell::Occupied(Player::O),
+            other => {
+                return Err(GameError::DatabaseError {
+                    message: format!("Invalid board snapshot character: {:?}", other),
+                });
+            }
+        };
+        board[i / 3][i % 3] = cell;
+    }
+    Ok(board)
+}
+
+/// A taunt queued by [`GameRepository::save_taunt`] under write-behind mode, not yet flushed
+#[derive(Debug, Clone)]
+struct PendingTaunt {
+    message: String,
+    timestamp: i64,
+    source: Option<String>,
+}
+
+/// In-memory buffer for [`GameRepository::with_write_behind`], keyed by game_id
+#[derive(Default)]
+struct WriteBehindState {
+    lag: Duration,
+    first_dirty_at: Option<Instant>,
+    pending_games: HashMap<String, GameState>,
+    pending_moves: HashMap<String, Vec<Move>>,
+    pending_taunts: HashMap<String, Vec<PendingTaunt>>,
+}
 
 /// Game repository for database operations
 #[allow(dead_code)] // Will be used by API and MCP layers
 pub struct GameRepository {
     conn: Connection,
+    /// Set by [`Self::with_write_behind`]; when present, `save_game`/`save_move`/`save_taunt`
+    /// buffer here instead of writing immediately
+    write_behind: Option<RefCell<WriteBehindState>>,
 }
 
+/// Points credited to a side's cumulative `player_stats.score` for a win, loss, or draw,
+/// independent of the plain wins/losses/draws tally recorded alongside it.
+const WIN_POINTS: i64 = 3;
+const DRAW_POINTS: i64 = 1;
+const LOSS_POINTS: i64 = 0;
+
 #[allow(dead_code)] // Will be used by API and MCP layers
 impl GameRepository {
     /// Create a new repository with the given database path
@@ -19,7 +96,10 @@ impl GameRepository {
         // Initialize schema
         super::schema::init_schema(&conn)?;
 
-        Ok(Self { conn })
+        Ok(Self {
+            conn,
+            write_behind: None,
+        })
     }
 
     /// Create a new repository with an in-memory database (for testing)
@@ -31,16 +111,200 @@ impl GameRepository {
 
         super::schema::init_schema(&conn)?;
 
-        Ok(Self { conn })
+        Ok(Self {
+            conn,
+            write_behind: None,
+        })
+    }
+
+    /// Create an in-memory write-behind repository (for testing)
+    #[cfg(test)]
+    pub fn new_in_memory_with_write_behind(lag_ms: u64) -> Result<Self, GameError> {
+        let mut repo = Self::new_in_memory()?;
+        repo.write_behind = Some(RefCell::new(WriteBehindState {
+            lag: Duration::from_millis(lag_ms),
+            ..Default::default()
+        }));
+        Ok(repo)
+    }
+
+    /// Create a repository that buffers moves, taunts, and game-state transitions in memory
+    /// instead of writing them synchronously, flushing them inside a single transaction either
+    /// when `lag_ms` has elapsed since the first unflushed write or when [`Self::flush`] /
+    /// [`Self::flush_game`] is called explicitly. `load_game` transparently merges unflushed
+    /// writes so reads stay consistent, and outstanding writes are flushed on `Drop`.
+    pub fn with_write_behind(db_path: &str, lag_ms: u64) -> Result<Self, GameError> {
+        let mut repo = Self::new(db_path)?;
+        repo.write_behind = Some(RefCell::new(WriteBehindState {
+            lag: Duration::from_millis(lag_ms),
+            ..Default::default()
+        }));
+        Ok(repo)
+    }
+
+    /// Mark the write-behind buffer dirty (if enabled) and auto-flush once `lag` has elapsed
+    /// since the first unflushed write.
+    fn mark_dirty_and_maybe_flush(&self) -> Result<(), GameError> {
+        let Some(wb) = &self.write_behind else {
+            return Ok(());
+        };
+
+        let should_flush = {
+            let mut state = wb.borrow_mut();
+            state.first_dirty_at.get_or_insert_with(Instant::now);
+            state.first_dirty_at.unwrap().elapsed() >= state.lag
+        };
+
+        if should_flush {
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Flush every dirty game in the write-behind buffer. A no-op if write-behind isn't enabled.
+    pub fn flush(&self) -> Result<(), GameError> {
+        let Some(wb) = &self.write_behind else {
+            return Ok(());
+        };
+
+        let dirty_ids: HashSet<String> = {
+            let state = wb.borrow();
+            state
+                .pending_games
+                .keys()
+                .chain(state.pending_moves.keys())
+                .chain(state.pending_taunts.keys())
+                .cloned()
+                .collect()
+        };
+
+        for game_id in dirty_ids {
+            self.flush_game(&game_id)?;
+        }
+
+        wb.borrow_mut().first_dirty_at = None;
+
+        Ok(())
+    }
+
+    /// Flush one game's buffered writes (if any) inside a single transaction. A no-op if
+    /// write-behind isn't enabled or nothing is pending for `game_id`.
+    pub fn flush_game(&self, game_id: &str) -> Result<(), GameError> {
+        let Some(wb) = &self.write_behind else {
+            return Ok(());
+        };
+
+        let (pending_game, pending_moves, pending_taunts) = {
+            let mut state = wb.borrow_mut();
+            (
+                state.pending_games.remove(game_id),
+                state.pending_moves.remove(game_id).unwrap_or_default(),
+                state.pending_taunts.remove(game_id).unwrap_or_default(),
+            )
+        };
+
+        if pending_game.is_none() && pending_moves.is_empty() && pending_taunts.is_empty() {
+            return Ok(());
+        }
+
+        self.conn
+            .execute_batch("BEGIN")
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        let result: Result<(), GameError> = (|| {
+            if let Some(game) = &pending_game {
+                self.save_game_immediate(game)?;
+            }
+            for mov in &pending_moves {
+                self.save_move_immediate(game_id, mov)?;
+            }
+            for taunt in &pending_taunts {
+                self.save_taunt_immediate(
+                    game_id,
+                    &taunt.message,
+                    taunt.timestamp,
+                    taunt.source.as_deref(),
+                )?;
+            }
+            Ok(())
+        })();
+
+        match result {
+            Ok(()) => {
+                self.conn
+                    .execute_batch("COMMIT")
+                    .map_err(|e| GameError::DatabaseError {
+                        message: e.to_string(),
+                    })?;
+                Ok(())
+            }
+            Err(e) => {
+                let _ = self.conn.execute_batch("ROLLBACK");
+                Err(e)
+            }
+        }
+    }
+
+    fn pending_game(&self, game_id: &str) -> Option<GameState> {
+        self.write_behind
+            .as_ref()
+            .and_then(|wb| wb.borrow().pending_games.get(game_id).cloned())
+    }
+
+    fn pending_moves(&self, game_id: &str) -> Vec<Move> {
+        self.write_behind
+            .as_ref()
+            .and_then(|wb| wb.borrow().pending_moves.get(game_id).cloned())
+            .unwrap_or_default()
+    }
+
+    fn pending_taunts(&self, game_id: &str) -> Vec<shared::ChatMessage> {
+        self.write_behind
+            .as_ref()
+            .and_then(|wb| wb.borrow().pending_taunts.get(game_id).cloned())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|t| shared::ChatMessage {
+                // Not yet flushed, so there's no real row id; `0` never collides with a real
+                // autoincrement id (which starts at 1) and the flushed copy takes over on read
+                // once `mark_dirty_and_maybe_flush` writes it through.
+                id: 0,
+                body: t.message,
+                timestamp: t.timestamp,
+                sender: t.source.and_then(|s| match s.as_str() {
+                    "UI" => Some(shared::MoveSource::UI),
+                    "MCP" => Some(shared::MoveSource::MCP),
+                    "AI" => Some(shared::MoveSource::AI),
+                    _ => None,
+                }),
+            })
+            .collect()
     }
 
-    /// Save a new game to the database
+    /// Save a new game to the database, or buffer it under write-behind mode
     pub fn save_game(&self, game: &GameState) -> Result<(), GameError> {
+        if self.write_behind.is_some() {
+            if let Some(wb) = &self.write_behind {
+                wb.borrow_mut()
+                    .pending_games
+                    .insert(game.id.clone(), game.clone());
+            }
+            return self.mark_dirty_and_maybe_flush();
+        }
+
+        self.save_game_immediate(game)
+    }
+
+    fn save_game_immediate(&self, game: &GameState) -> Result<(), GameError> {
         let status_str = match &game.status {
             GameStatus::InProgress => "InProgress".to_string(),
             GameStatus::Won(Player::X) => "Won_X".to_string(),
             GameStatus::Won(Player::O) => "Won_O".to_string(),
             GameStatus::Draw => "Draw".to_string(),
+            GameStatus::Abandoned => "Abandoned".to_string(),
         };
 
         let human_str = match game.human_player {
@@ -63,14 +327,23 @@ impl GameRepository {
             .unwrap()
             .as_secs() as i64;
 
+        let board_str = serialize_board(&game.board);
+        let ai_difficulty_str = game.ai_difficulty.map(|d| d.as_str());
+
         self.conn
             .execute(
-                "INSERT INTO games (id, human_player, ai_player, current_turn, status, created_at, updated_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                "INSERT INTO games (id, human_player, ai_player, current_turn, status, created_at, updated_at, turn_started_at, turn_limit_secs, ai_difficulty, board, version, previous_game_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
                  ON CONFLICT(id) DO UPDATE SET
                      current_turn = ?4,
                      status = ?5,
-                     updated_at = ?7",
+                     updated_at = ?6,
+                     turn_started_at = ?7,
+                     turn_limit_secs = ?8,
+                     ai_difficulty = ?9,
+                     board = ?10,
+                     version = ?11,
+                     previous_game_id = ?12",
                 params![
                     &game.id,
                     human_str,
@@ -78,7 +351,12 @@ impl GameRepository {
                     turn_str,
                     status_str,
                     now,
-                    now
+                    game.turn_started_at,
+                    game.turn_limit_secs,
+                    ai_difficulty_str,
+                    board_str,
+                    game.version as i64,
+                    &game.previous_game_id
                 ],
             )
             .map_err(|e| GameError::DatabaseError {
@@ -88,24 +366,30 @@ impl GameRepository {
         Ok(())
     }
 
-    /// Load a game from the database
+    /// Load a game from the database, transparently merging any unflushed write-behind writes
     pub fn load_game(&self, game_id: &str) -> Result<GameState, GameError> {
         let mut stmt = self
             .conn
             .prepare(
-                "SELECT id, human_player, ai_player, current_turn, status FROM games WHERE id = ?1",
+                "SELECT id, human_player, ai_player, current_turn, status, board, turn_started_at, turn_limit_secs, ai_difficulty, version, previous_game_id FROM games WHERE id = ?1",
             )
             .map_err(|e| GameError::DatabaseError {
                 message: e.to_string(),
             })?;
 
-        let game = stmt
+        let row_result = stmt
             .query_row(params![game_id], |row| {
                 let id: String = row.get(0)?;
                 let human_str: String = row.get(1)?;
                 let ai_str: String = row.get(2)?;
                 let turn_str: String = row.get(3)?;
                 let status_str: String = row.get(4)?;
+                let board_str: Option<String> = row.get(5)?;
+                let turn_started_at: Option<i64> = row.get(6)?;
+                let turn_limit_secs: Option<u32> = row.get(7)?;
+                let ai_difficulty_str: Option<String> = row.get(8)?;
+                let version: i64 = row.get(9)?;
+                let previous_game_id: Option<String> = row.get(10)?;
 
                 let human_player = if human_str == "X" {
                     Player::X
@@ -126,35 +410,95 @@ impl GameRepository {
                     "Won_X" => GameStatus::Won(Player::X),
                     "Won_O" => GameStatus::Won(Player::O),
                     "Draw" => GameStatus::Draw,
+                    "Abandoned" => GameStatus::Abandoned,
                     _ => GameStatus::InProgress,
                 };
 
-                Ok((id, human_player, ai_player, current_turn, status))
+                Ok((
+                    id,
+                    human_player,
+                    ai_player,
+                    current_turn,
+                    status,
+                    board_str,
+                    turn_started_at.unwrap_or(0),
+                    turn_limit_secs,
+                    ai_difficulty_str.and_then(|s| AiDifficulty::parse(&s)),
+                    version as u64,
+                    previous_game_id,
+                ))
             })
-            .map_err(|_| GameError::GameNotFound)?;
-
-        // Load moves to reconstruct the board
-        let moves = self.load_moves(&game.0)?;
-        let board = Self::reconstruct_board(&moves)?;
+            .map_err(|_| GameError::GameNotFound);
+
+        // A still-buffered write-behind game takes priority over the persisted row, since it's
+        // the freshest intended state; it also covers a brand-new game that hasn't flushed yet.
+        let (id, human_player, ai_player, current_turn, status, board_str, turn_started_at, turn_limit_secs, ai_difficulty, version, previous_game_id) =
+            match self.pending_game(game_id) {
+                Some(game) => (
+                    game.id,
+                    game.human_player,
+                    game.ai_player,
+                    game.current_turn,
+                    game.status,
+                    None,
+                    game.turn_started_at,
+                    game.turn_limit_secs,
+                    game.ai_difficulty,
+                    game.version,
+                    game.previous_game_id,
+                ),
+                None => row_result?,
+            };
+
+        // Load moves for move_history, merging in unflushed ones
+        let pending_moves = self.pending_moves(&id);
+        let mut moves = self.load_moves(&id)?;
+        moves.extend(pending_moves.iter().cloned());
+
+        // Decode the stored board snapshot when there's nothing buffered ahead of it; otherwise
+        // (or for a legacy row with no snapshot) fall back to replaying every move.
+        let board = match (&board_str, pending_moves.is_empty()) {
+            (Some(s), true) => deserialize_board(s)?,
+            _ => Self::reconstruct_board(&moves)?,
+        };
 
-        // Load taunts
-        let taunts = self.load_taunts(&game.0)?;
+        // Load taunts, merging in unflushed ones
+        let mut taunts = self.load_taunts(&id)?;
+        taunts.extend(self.pending_taunts(&id));
 
         Ok(GameState {
-            id: game.0,
+            id,
             board,
-            current_turn: game.3,
-            human_player: game.1,
-            ai_player: game.2,
-            status: game.4,
+            current_turn,
+            human_player,
+            ai_player,
+            status,
             move_history: moves,
             taunts,
             winning_line: None, // Will be computed from board state
+            turn_started_at,
+            turn_limit_secs,
+            ai_difficulty,
+            version,
+            previous_game_id,
         })
     }
 
-    /// Save a move to the database
+    /// Save a move to the database, or buffer it under write-behind mode
     pub fn save_move(&self, game_id: &str, mov: &Move) -> Result<(), GameError> {
+        if let Some(wb) = &self.write_behind {
+            wb.borrow_mut()
+                .pending_moves
+                .entry(game_id.to_string())
+                .or_default()
+                .push(mov.clone());
+            return self.mark_dirty_and_maybe_flush();
+        }
+
+        self.save_move_immediate(game_id, mov)
+    }
+
+    fn save_move_immediate(&self, game_id: &str, mov: &Move) -> Result<(), GameError> {
         let player_str = match mov.player {
             Player::X => "X",
             Player::O => "O",
@@ -163,6 +507,7 @@ impl GameRepository {
         let source_str = mov.source.as_ref().map(|s| match s {
             shared::MoveSource::UI => "UI",
             shared::MoveSource::MCP => "MCP",
+            shared::MoveSource::AI => "AI",
         });
 
         self.conn
@@ -199,6 +544,7 @@ impl GameRepository {
                 let source = source_str.and_then(|s| match s.as_str() {
                     "UI" => Some(shared::MoveSource::UI),
                     "MCP" => Some(shared::MoveSource::MCP),
+                    "AI" => Some(shared::MoveSource::AI),
                     _ => None,
                 });
 
@@ -221,7 +567,7 @@ impl GameRepository {
             })
     }
 
-    /// Save a taunt to the database
+    /// Save a taunt to the database, or buffer it under write-behind mode
     pub fn save_taunt(
         &self,
         game_id: &str,
@@ -233,10 +579,33 @@ impl GameRepository {
             .unwrap()
             .as_secs() as i64;
 
+        if let Some(wb) = &self.write_behind {
+            wb.borrow_mut()
+                .pending_taunts
+                .entry(game_id.to_string())
+                .or_default()
+                .push(PendingTaunt {
+                    message: message.to_string(),
+                    timestamp: now,
+                    source: source.map(|s| s.to_string()),
+                });
+            return self.mark_dirty_and_maybe_flush();
+        }
+
+        self.save_taunt_immediate(game_id, message, now, source)
+    }
+
+    fn save_taunt_immediate(
+        &self,
+        game_id: &str,
+        message: &str,
+        timestamp: i64,
+        source: Option<&str>,
+    ) -> Result<(), GameError> {
         self.conn
             .execute(
                 "INSERT INTO taunts (game_id, message, timestamp, source) VALUES (?1, ?2, ?3, ?4)",
-                params![game_id, message, now, source],
+                params![game_id, message, timestamp, source],
             )
             .map_err(|e| GameError::DatabaseError {
                 message: e.to_string(),
@@ -246,32 +615,18 @@ impl GameRepository {
     }
 
     /// Load all taunts for a game
-    pub fn load_taunts(&self, game_id: &str) -> Result<Vec<shared::Taunt>, GameError> {
+    pub fn load_taunts(&self, game_id: &str) -> Result<Vec<shared::ChatMessage>, GameError> {
         let mut stmt = self
             .conn
             .prepare(
-                "SELECT message, timestamp, source FROM taunts WHERE game_id = ?1 ORDER BY timestamp ASC",
+                "SELECT id, message, timestamp, source FROM taunts WHERE game_id = ?1 ORDER BY timestamp ASC",
             )
             .map_err(|e| GameError::DatabaseError {
                 message: e.to_string(),
             })?;
 
         let taunts = stmt
-            .query_map(params![game_id], |row| {
-                let message: String = row.get(0)?;
-                let timestamp: i64 = row.get(1)?;
-                let source_str: Option<String> = row.get(2)?;
-                let source = source_str.and_then(|s| match s.as_str() {
-                    "UI" => Some(shared::MoveSource::UI),
-                    "MCP" => Some(shared::MoveSource::MCP),
-                    _ => None,
-                });
-                Ok(shared::Taunt {
-                    message,
-                    timestamp,
-                    source,
-                })
-            })
+            .query_map(params![game_id], Self::row_to_chat_message)
             .map_err(|e| GameError::DatabaseError {
                 message: e.to_string(),
             })?;
@@ -283,6 +638,78 @@ impl GameRepository {
             })
     }
 
+    /// A bounded, chronologically-ordered slice of a game's chat/taunt history, for
+    /// CHATHISTORY-style scroll-back instead of loading it all via `load_taunts`. `before`/`after`
+    /// page around a message `id`; omitting both returns the most recent `limit` messages.
+    pub fn get_taunts(
+        &self,
+        game_id: &str,
+        before: Option<u64>,
+        after: Option<u64>,
+        limit: u32,
+    ) -> Result<Vec<shared::ChatMessage>, GameError> {
+        let map_err = |e: rusqlite::Error| GameError::DatabaseError {
+            message: e.to_string(),
+        };
+
+        // `before`/`after` each select in the direction away from the cursor, newest-first, so
+        // LIMIT bounds the page closest to the cursor; the result is then flipped back to
+        // chronological order before returning.
+        let (sql, cursor, newest_first) = match (before, after) {
+            (Some(before), _) => (
+                "SELECT id, message, timestamp, source FROM taunts \
+                 WHERE game_id = ?1 AND id < ?2 ORDER BY id DESC LIMIT ?3",
+                before,
+                true,
+            ),
+            (None, Some(after)) => (
+                "SELECT id, message, timestamp, source FROM taunts \
+                 WHERE game_id = ?1 AND id > ?2 ORDER BY id ASC LIMIT ?3",
+                after,
+                false,
+            ),
+            (None, None) => (
+                "SELECT id, message, timestamp, source FROM taunts \
+                 WHERE game_id = ?1 ORDER BY id DESC LIMIT ?2",
+                0,
+                true,
+            ),
+        };
+
+        let mut stmt = self.conn.prepare(sql).map_err(map_err)?;
+        let rows = if before.is_some() || after.is_some() {
+            stmt.query_map(params![game_id, cursor, limit], Self::row_to_chat_message)
+        } else {
+            stmt.query_map(params![game_id, limit], Self::row_to_chat_message)
+        }
+        .map_err(map_err)?;
+
+        let mut taunts = rows.collect::<Result<Vec<_>, _>>().map_err(map_err)?;
+        if newest_first {
+            taunts.reverse();
+        }
+        Ok(taunts)
+    }
+
+    fn row_to_chat_message(row: &rusqlite::Row) -> rusqlite::Result<shared::ChatMessage> {
+        let id: i64 = row.get(0)?;
+        let body: String = row.get(1)?;
+        let timestamp: i64 = row.get(2)?;
+        let source_str: Option<String> = row.get(3)?;
+        let sender = source_str.and_then(|s| match s.as_str() {
+            "UI" => Some(shared::MoveSource::UI),
+            "MCP" => Some(shared::MoveSource::MCP),
+            "AI" => Some(shared::MoveSource::AI),
+            _ => None,
+        });
+        Ok(shared::ChatMessage {
+            id: id as u64,
+            body,
+            timestamp,
+            sender,
+        })
+    }
+
     /// Reconstruct board from moves
     fn reconstruct_board(moves: &[Move]) -> Result<[[Cell; 3]; 3], GameError> {
         let mut board = [[Cell::Empty; 3]; 3];
@@ -301,16 +728,17 @@ impl GameRepository {
         Ok(board)
     }
 
-    /// Get the current active game ID (shared across all processes)
-    pub fn get_current_game_id(&self) -> Result<Option<String>, GameError> {
-        let result: Result<String, _> =
-            self.conn
-                .query_row("SELECT game_id FROM current_game WHERE id = 1", [], |row| {
-                    row.get(0)
-                });
+    /// Read just the `updated_at` column for `game_id`, without reconstructing the board. Use as
+    /// a cheap version token for conditional polling; pair with [`Self::load_game_if_changed`].
+    pub fn get_game_version(&self, game_id: &str) -> Result<Option<i64>, GameError> {
+        let result: Result<i64, _> = self.conn.query_row(
+            "SELECT updated_at FROM games WHERE id = ?1",
+            params![game_id],
+            |row| row.get(0),
+        );
 
         match result {
-            Ok(game_id) => Ok(Some(game_id)),
+            Ok(updated_at) => Ok(Some(updated_at)),
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
             Err(e) => Err(GameError::DatabaseError {
                 message: e.to_string(),
@@ -318,181 +746,1653 @@ impl GameRepository {
         }
     }
 
-    /// Set the current active game ID (shared across all processes)
-    pub fn set_current_game_id(&self, game_id: &str) -> Result<(), GameError> {
-        // Use INSERT OR REPLACE to ensure only one current game exists
-        self.conn
-            .execute(
-                "INSERT OR REPLACE INTO current_game (id, game_id) VALUES (1, ?1)",
-                params![game_id],
+    /// Load `game_id` only if it has changed since version token `since`, skipping the
+    /// `load_moves` + `reconstruct_board` + `load_taunts` round trip otherwise. Returns
+    /// `Ok(None)` both when the game is unchanged and when it doesn't exist.
+    pub fn load_game_if_changed(
+        &self,
+        game_id: &str,
+        since: i64,
+    ) -> Result<Option<GameState>, GameError> {
+        match self.get_game_version(game_id)? {
+            Some(updated_at) if updated_at > since => Ok(Some(self.load_game(game_id)?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// IDs of in-progress games whose current turn has been idle longer than `turn_secs`, based
+    /// on the `turn_started_at` timestamp set whenever `current_turn` changes in [`Self::save_game`].
+    pub fn games_past_deadline(&self, turn_secs: i64) -> Result<Vec<String>, GameError> {
+        let cutoff = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64
+            - turn_secs;
+
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id FROM games WHERE status = 'InProgress' AND turn_started_at < ?1",
             )
             .map_err(|e| GameError::DatabaseError {
                 message: e.to_string(),
             })?;
 
-        Ok(())
+        stmt.query_map(params![cutoff], |row| row.get(0))
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?
+            .collect::<Result<_, _>>()
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use shared::MoveSource;
-    use uuid::Uuid;
+    /// Auto-forfeit `game_id` if it's still `InProgress` and past its per-turn deadline: marks it
+    /// `Won` for the opponent of `current_turn` and persists the transition. A no-op (returns
+    /// `Ok(())`) if the game isn't found, isn't `InProgress`, or hasn't actually hit the deadline.
+    pub fn resolve_timeout(&self, game_id: &str, turn_secs: i64) -> Result<(), GameError> {
+        let game = match self.load_game(game_id) {
+            Ok(game) => game,
+            Err(GameError::GameNotFound) => return Ok(()),
+            Err(e) => return Err(e),
+        };
 
-    fn create_test_game() -> GameState {
-        GameState {
-            id: Uuid::new_v4().to_string(),
-            board: [[Cell::Empty; 3]; 3],
-            current_turn: Player::X,
-            human_player: Player::X,
-            ai_player: Player::O,
-            status: GameStatus::InProgress,
-            move_history: vec![],
-            taunts: vec![],
-            winning_line: None,
+        if game.status != GameStatus::InProgress {
+            return Ok(());
         }
-    }
 
-    #[test]
-    fn test_save_and_load_game() {
-        let repo = GameRepository::new_in_memory().unwrap();
-        let game = create_test_game();
-        let game_id = game.id.clone();
+        let past_deadline = self.games_past_deadline(turn_secs)?.contains(&game.id);
+        if !past_deadline {
+            return Ok(());
+        }
 
-        // Save game
-        assert!(repo.save_game(&game).is_ok());
+        let winner = match game.current_turn {
+            Player::X => Player::O,
+            Player::O => Player::X,
+        };
+        let status_str = match winner {
+            Player::X => "Won_X",
+            Player::O => "Won_O",
+        };
 
-        // Load game
-        let loaded = repo.load_game(&game_id).unwrap();
-        assert_eq!(loaded.id, game_id);
-        assert_eq!(loaded.human_player, Player::X);
-        assert_eq!(loaded.ai_player, Player::O);
-        assert_eq!(loaded.current_turn, Player::X);
-        assert_eq!(loaded.status, GameStatus::InProgress);
-    }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
 
-    #[test]
-    fn test_load_nonexistent_game() {
-        let repo = GameRepository::new_in_memory().unwrap();
-        let result = repo.load_game("nonexistent");
-        assert!(matches!(result, Err(GameError::GameNotFound)));
+        self.conn
+            .execute(
+                "UPDATE games SET status = ?1, updated_at = ?2 WHERE id = ?3",
+                params![status_str, now, game_id],
+            )
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        Ok(())
     }
 
-    #[test]
-    fn test_save_and_load_moves() {
-        let repo = GameRepository::new_in_memory().unwrap();
-        let game = create_test_game();
-        let game_id = game.id.clone();
+    /// Fold `game_id`'s result into the cross-game `player_stats` leaderboard, identifying the
+    /// two sides by their persistent "human"/"ai" role rather than the per-game `X`/`O` seat
+    /// (which is reassigned every game by [`crate::game::player::assign_players`]), and record
+    /// its move count, duration, and AI `difficulty` into `match_history`. Idempotent per game
+    /// via the `results_recorded` flag, so calling this again for an already-recorded game
+    /// (e.g. a repeated `save_game`) is a no-op.
+    pub fn record_result(&self, game_id: &str, difficulty: &str) -> Result<(), GameError> {
+        let game = self.load_game(game_id)?;
 
-        repo.save_game(&game).unwrap();
+        let already_recorded: bool = self
+            .conn
+            .query_row(
+                "SELECT results_recorded FROM games WHERE id = ?1",
+                params![game_id],
+                |row| row.get::<_, i64>(0),
+            )
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?
+            != 0;
+        if already_recorded {
+            return Ok(());
+        }
 
-        // Save some moves
-        let move1 = Move {
-            player: Player::X,
-            row: 0,
-            col: 0,
-            timestamp: 1000,
-            source: Some(MoveSource::UI),
+        let winner_label = match &game.status {
+            GameStatus::Won(winner) => {
+                let (winner_id, loser_id) = if *winner == game.human_player {
+                    ("human", "ai")
+                } else {
+                    ("ai", "human")
+                };
+                self.bump_player_stat(winner_id, "wins")?;
+                self.bump_player_stat(loser_id, "losses")?;
+                self.bump_player_score(winner_id, WIN_POINTS)?;
+                self.bump_player_score(loser_id, LOSS_POINTS)?;
+                Some(winner_id)
+            }
+            GameStatus::Draw => {
+                self.bump_player_stat("human", "draws")?;
+                self.bump_player_stat("ai", "draws")?;
+                self.bump_player_score("human", DRAW_POINTS)?;
+                self.bump_player_score("ai", DRAW_POINTS)?;
+                None
+            }
+            // An abandoned game has no opponent still present to attribute a win/loss/draw to,
+            // so (like an in-progress one) it's simply excluded from the leaderboard.
+            GameStatus::InProgress | GameStatus::Abandoned => return Ok(()),
         };
-        let move2 = Move {
-            player: Player::O,
-            row: 1,
-            col: 1,
-            timestamp: 2000,
-            source: Some(MoveSource::UI),
+
+        let move_count = game.move_history.len() as i64;
+        let duration_secs = match (game.move_history.first(), game.move_history.last()) {
+            (Some(first), Some(last)) => last.timestamp - first.timestamp,
+            _ => 0,
         };
+        let recorded_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
 
-        repo.save_move(&game_id, &move1).unwrap();
-        repo.save_move(&game_id, &move2).unwrap();
+        self.conn
+            .execute(
+                "INSERT INTO match_history
+                     (game_id, winner, move_count, duration_secs, difficulty, recorded_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![game_id, winner_label, move_count, duration_secs, difficulty, recorded_at],
+            )
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
 
-        // Load moves
-        let moves = repo.load_moves(&game_id).unwrap();
-        assert_eq!(moves.len(), 2);
-        assert_eq!(moves[0].player, Player::X);
-        assert_eq!(moves[0].row, 0);
-        assert_eq!(moves[0].col, 0);
-        assert_eq!(moves[1].player, Player::O);
+        self.conn
+            .execute(
+                "UPDATE games SET results_recorded = 1 WHERE id = ?1",
+                params![game_id],
+            )
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        Ok(())
+    }
+
+    /// Increment one counter column (`wins`/`losses`/`draws`) for `player_id`, creating its row
+    /// if this is its first recorded game, and bump its `games_played`/`last_played` alongside
+    /// it. `column` is only ever called with a fixed internal literal, never user input, so the
+    /// formatted SQL is safe.
+    fn bump_player_stat(&self, player_id: &str, column: &str) -> Result<(), GameError> {
+        self.conn
+            .execute(
+                "INSERT INTO player_stats (player_id, wins, losses, draws, games_played, last_played)
+                 VALUES (?1, 0, 0, 0, 0, NULL)
+                 ON CONFLICT(player_id) DO NOTHING",
+                params![player_id],
+            )
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        self.conn
+            .execute(
+                &format!(
+                    "UPDATE player_stats
+                     SET {column} = {column} + 1, games_played = games_played + 1, last_played = ?2
+                     WHERE player_id = ?1",
+                    column = column
+                ),
+                params![player_id, now],
+            )
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        Ok(())
+    }
+
+    /// Add `points` (may be zero) to `player_id`'s cumulative `score`, creating its row first if
+    /// this is its first recorded result. Called alongside `bump_player_stat` from
+    /// `record_result`, never standalone, so it doesn't touch `games_played`/`last_played` itself.
+    fn bump_player_score(&self, player_id: &str, points: i64) -> Result<(), GameError> {
+        self.conn
+            .execute(
+                "INSERT INTO player_stats (player_id, wins, losses, draws, games_played, last_played, score)
+                 VALUES (?1, 0, 0, 0, 0, NULL, 0)
+                 ON CONFLICT(player_id) DO NOTHING",
+                params![player_id],
+            )
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        self.conn
+            .execute(
+                "UPDATE player_stats SET score = score + ?2 WHERE player_id = ?1",
+                params![player_id, points],
+            )
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        Ok(())
+    }
+
+    /// The top `limit` players by wins, for a simple leaderboard view
+    pub fn top_players(&self, limit: u32) -> Result<Vec<PlayerStats>, GameError> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT player_id, wins, losses, draws, games_played, last_played, score FROM player_stats
+                 ORDER BY wins DESC LIMIT ?1",
+            )
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        stmt.query_map(params![limit], |row| {
+            Ok(PlayerStats {
+                player_id: row.get(0)?,
+                wins: row.get(1)?,
+                losses: row.get(2)?,
+                draws: row.get(3)?,
+                games_played: row.get(4)?,
+                last_played: row.get(5)?,
+                score: row.get(6)?,
+            })
+        })
+        .map_err(|e| GameError::DatabaseError {
+            message: e.to_string(),
+        })?
+        .collect::<Result<_, _>>()
+        .map_err(|e| GameError::DatabaseError {
+            message: e.to_string(),
+        })
+    }
+
+    /// All recorded players' tallies, each with its win rate (`wins / total games`) folded in,
+    /// sorted descending by score, then by wins, then by win rate (the tiebreaks `top_players`'s
+    /// plain `ORDER BY wins` can't express, since it only ever compares a fixed-width prefix).
+    pub fn get_leaderboard(&self, limit: u32) -> Result<Vec<LeaderboardEntry>, GameError> {
+        let mut entries: Vec<LeaderboardEntry> = self
+            .top_players(u32::MAX)?
+            .into_iter()
+            .map(|p| {
+                let total = p.wins + p.losses + p.draws;
+                let win_rate = if total == 0 {
+                    0.0
+                } else {
+                    p.wins as f64 / total as f64
+                };
+                LeaderboardEntry {
+                    player_id: p.player_id,
+                    wins: p.wins,
+                    losses: p.losses,
+                    draws: p.draws,
+                    games_played: p.games_played,
+                    last_played: p.last_played,
+                    win_rate,
+                    score: p.score,
+                }
+            })
+            .collect();
+
+        entries.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then(b.wins.cmp(&a.wins))
+                .then(b.win_rate.partial_cmp(&a.win_rate).unwrap_or(std::cmp::Ordering::Equal))
+        });
+        entries.truncate(limit as usize);
+
+        Ok(entries)
+    }
+
+    /// Wipe every recorded leaderboard tally and match history, for starting a fresh
+    /// competition. Guarded the same way `restart_game` is (see `reset_leaderboard`'s callers).
+    pub fn reset_leaderboard(&self) -> Result<(), GameError> {
+        self.conn
+            .execute("DELETE FROM player_stats", [])
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+        self.conn
+            .execute("DELETE FROM match_history", [])
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        Ok(())
+    }
+
+    /// Record or refresh `player_id` in the `players` directory, stamping `last_seen` to now
+    fn touch_player(&self, player_id: &str, display_name: &str) -> Result<(), GameError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        self.conn
+            .execute(
+                "INSERT INTO players (id, display_name, last_seen) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(id) DO UPDATE SET display_name = ?2, last_seen = ?3",
+                params![player_id, display_name, now],
+            )
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        Ok(())
+    }
+
+    /// Open a new human-vs-human lobby entry with `creator_id` waiting in the empty seat.
+    /// Returns the new open game's id.
+    pub fn create_open_game(&self, creator_id: &str) -> Result<String, GameError> {
+        self.touch_player(creator_id, creator_id)?;
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        self.conn
+            .execute(
+                "INSERT INTO open_games (id, creator_id, opponent_id, status, created_at)
+                 VALUES (?1, ?2, NULL, 'WaitingForOpponent', ?3)",
+                params![id, creator_id, now],
+            )
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        Ok(id)
+    }
+
+    /// Atomically claim the empty seat in `game_id` for `joiner_id`. Returns `true` if this call
+    /// won the seat, `false` if it was already taken (or the game doesn't exist) — relies on the
+    /// conditional `UPDATE ... WHERE opponent_id IS NULL`'s affected-row count, not a
+    /// read-then-write, so two concurrent callers can never both succeed.
+    pub fn join_game(&self, game_id: &str, joiner_id: &str) -> Result<bool, GameError> {
+        self.touch_player(joiner_id, joiner_id)?;
+
+        let rows_affected = self
+            .conn
+            .execute(
+                "UPDATE open_games SET opponent_id = ?1, status = 'InProgress'
+                 WHERE id = ?2 AND opponent_id IS NULL",
+                params![joiner_id, game_id],
+            )
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        Ok(rows_affected == 1)
+    }
+
+    /// Open games still waiting for a second player
+    pub fn get_waiting_games(&self) -> Result<Vec<OpenGame>, GameError> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id, creator_id, opponent_id, status FROM open_games
+                 WHERE status = 'WaitingForOpponent' ORDER BY created_at ASC",
+            )
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        stmt.query_map([], |row| {
+            Ok(OpenGame {
+                id: row.get(0)?,
+                creator_id: row.get(1)?,
+                opponent_id: row.get(2)?,
+                status: row.get(3)?,
+            })
+        })
+        .map_err(|e| GameError::DatabaseError {
+            message: e.to_string(),
+        })?
+        .collect::<Result<_, _>>()
+        .map_err(|e| GameError::DatabaseError {
+            message: e.to_string(),
+        })
+    }
+
+    /// Delete a single game along with its moves and taunts, clearing `current_game` if it
+    /// pointed at the deleted game.
+    pub fn delete_game(&self, game_id: &str) -> Result<(), GameError> {
+        self.conn
+            .execute("DELETE FROM taunts WHERE game_id = ?1", params![game_id])
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+        self.conn
+            .execute("DELETE FROM moves WHERE game_id = ?1", params![game_id])
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+        self.conn
+            .execute("DELETE FROM games WHERE id = ?1", params![game_id])
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        if self.get_current_game_id()?.as_deref() == Some(game_id) {
+            self.conn
+                .execute("DELETE FROM current_game WHERE id = 1", [])
+                .map_err(|e| GameError::DatabaseError {
+                    message: e.to_string(),
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// `(game id, unix timestamp it was last touched)` for every persisted game, so a caller
+    /// like `GameManager::cleanup_stale_games` can apply its own staleness rule.
+    pub fn list_game_ids_with_last_activity(&self) -> Result<Vec<(String, i64)>, GameError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, updated_at FROM games")
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?
+            .collect::<Result<_, _>>()
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })
+    }
+
+    /// List every known game, most recently updated first
+    pub fn list_games(&self) -> Result<Vec<GameState>, GameError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id FROM games ORDER BY updated_at DESC")
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        let ids = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        ids.iter().map(|id| self.load_game(id)).collect()
+    }
+
+    /// Get the current active game ID (shared across all processes)
+    pub fn get_current_game_id(&self) -> Result<Option<String>, GameError> {
+        let result: Result<String, _> =
+            self.conn
+                .query_row("SELECT game_id FROM current_game WHERE id = 1", [], |row| {
+                    row.get(0)
+                });
+
+        match result {
+            Ok(game_id) => Ok(Some(game_id)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(GameError::DatabaseError {
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    /// Set the current active game ID (shared across all processes)
+    pub fn set_current_game_id(&self, game_id: &str) -> Result<(), GameError> {
+        // Use INSERT OR REPLACE to ensure only one current game exists
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO current_game (id, game_id) VALUES (1, ?1)",
+                params![game_id],
+            )
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+
+        Ok(())
+    }
+
+    /// Thin wrapper around the free `cleanup_stale_games` function below, for callers (like
+    /// `GameManager`) that hold a `GameRepository` rather than a raw `Connection`.
+    pub fn cleanup_stale_games(
+        &self,
+        turn_timeout_secs: i64,
+        game_timeout_secs: i64,
+    ) -> Result<(usize, usize), GameError> {
+        cleanup_stale_games(&self.conn, turn_timeout_secs, game_timeout_secs)
+    }
+}
+
+impl Drop for GameRepository {
+    /// Flush any outstanding write-behind writes so nothing is lost on shutdown
+    fn drop(&mut self) {
+        if self.write_behind.is_some() {
+            if let Err(e) = self.flush() {
+                tracing::error!("Failed to flush write-behind buffer on drop: {}", e);
+            }
+        }
+    }
+}
+
+/// Background sweep over a raw `Connection`, for an interval loop that doesn't have (or want to
+/// open) a full `GameRepository`. Two passes: first, any `InProgress` game whose `updated_at` is
+/// older than `turn_timeout_secs` is forfeited by marking it `GameStatus::Abandoned` (nobody came
+/// back to finish their turn); then any game at all -- freshly abandoned or not -- whose
+/// `updated_at` is older than the longer `game_timeout_secs` is deleted outright, along with its
+/// `moves`/`taunts` rows and the `current_game` pointer if it pointed there. Returns
+/// `(forfeited_count, deleted_count)`.
+pub fn cleanup_stale_games(
+    conn: &Connection,
+    turn_timeout_secs: i64,
+    game_timeout_secs: i64,
+) -> Result<(usize, usize), GameError> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    let turn_cutoff = now - turn_timeout_secs;
+    let game_cutoff = now - game_timeout_secs;
+
+    let forfeited = conn
+        .execute(
+            "UPDATE games SET status = 'Abandoned', updated_at = ?1
+             WHERE status = 'InProgress' AND updated_at < ?2",
+            params![now, turn_cutoff],
+        )
+        .map_err(|e| GameError::DatabaseError {
+            message: e.to_string(),
+        })?;
+
+    let mut stmt = conn
+        .prepare("SELECT id FROM games WHERE updated_at < ?1")
+        .map_err(|e| GameError::DatabaseError {
+            message: e.to_string(),
+        })?;
+    let stale_ids: Vec<String> = stmt
+        .query_map(params![game_cutoff], |row| row.get(0))
+        .map_err(|e| GameError::DatabaseError {
+            message: e.to_string(),
+        })?
+        .collect::<Result<_, _>>()
+        .map_err(|e| GameError::DatabaseError {
+            message: e.to_string(),
+        })?;
+    drop(stmt);
+
+    let current_game_id: Option<String> = conn
+        .query_row("SELECT game_id FROM current_game WHERE id = 1", [], |row| {
+            row.get(0)
+        })
+        .ok();
+
+    for game_id in &stale_ids {
+        conn.execute("DELETE FROM taunts WHERE game_id = ?1", params![game_id])
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+        conn.execute("DELETE FROM moves WHERE game_id = ?1", params![game_id])
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+        conn.execute("DELETE FROM games WHERE id = ?1", params![game_id])
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+    }
+
+    if current_game_id.is_some_and(|id| stale_ids.contains(&id)) {
+        conn.execute("DELETE FROM current_game WHERE id = 1", [])
+            .map_err(|e| GameError::DatabaseError {
+                message: e.to_string(),
+            })?;
+    }
+
+    Ok((forfeited, stale_ids.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::MoveSource;
+    use uuid::Uuid;
+
+    fn create_test_game() -> GameState {
+        GameState {
+            id: Uuid::new_v4().to_string(),
+            board: [[Cell::Empty; 3]; 3],
+            current_turn: Player::X,
+            human_player: Player::X,
+            ai_player: Player::O,
+            status: GameStatus::InProgress,
+            move_history: vec![],
+            taunts: vec![],
+            winning_line: None,
+            turn_started_at: 0,
+            turn_limit_secs: None,
+            ai_difficulty: None,
+            version: 0,
+            previous_game_id: None,
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_game() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game();
+        let game_id = game.id.clone();
+
+        // Save game
+        assert!(repo.save_game(&game).is_ok());
+
+        // Load game
+        let loaded = repo.load_game(&game_id).unwrap();
+        assert_eq!(loaded.id, game_id);
+        assert_eq!(loaded.human_player, Player::X);
+        assert_eq!(loaded.ai_player, Player::O);
+        assert_eq!(loaded.current_turn, Player::X);
+        assert_eq!(loaded.status, GameStatus::InProgress);
+    }
+
+    #[test]
+    fn test_save_and_load_game_preserves_version() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game = create_test_game();
+        game.version = 3;
+
+        repo.save_game(&game).unwrap();
+        let loaded = repo.load_game(&game.id).unwrap();
+
+        assert_eq!(loaded.version, 3);
+    }
+
+    #[test]
+    fn test_load_nonexistent_game() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let result = repo.load_game("nonexistent");
+        assert!(matches!(result, Err(GameError::GameNotFound)));
+    }
+
+    #[test]
+    fn test_save_and_load_moves() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game();
+        let game_id = game.id.clone();
+
+        repo.save_game(&game).unwrap();
+
+        // Save some moves
+        let move1 = Move {
+            player: Player::X,
+            row: 0,
+            col: 0,
+            timestamp: 1000,
+            source: Some(MoveSource::UI),
+        };
+        let move2 = Move {
+            player: Player::O,
+            row: 1,
+            col: 1,
+            timestamp: 2000,
+            source: Some(MoveSource::UI),
+        };
+
+        repo.save_move(&game_id, &move1).unwrap();
+        repo.save_move(&game_id, &move2).unwrap();
+
+        // Load moves
+        let moves = repo.load_moves(&game_id).unwrap();
+        assert_eq!(moves.len(), 2);
+        assert_eq!(moves[0].player, Player::X);
+        assert_eq!(moves[0].row, 0);
+        assert_eq!(moves[0].col, 0);
+        assert_eq!(moves[1].player, Player::O);
         assert_eq!(moves[1].row, 1);
         assert_eq!(moves[1].col, 1);
     }
 
     #[test]
-    fn test_save_and_load_taunts() {
+    fn test_save_and_load_move_tags_local_ai_source() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game();
+        let game_id = game.id.clone();
+
+        repo.save_game(&game).unwrap();
+
+        let ai_move = Move {
+            player: Player::O,
+            row: 2,
+            col: 2,
+            timestamp: 1000,
+            source: Some(MoveSource::AI),
+        };
+        repo.save_move(&game_id, &ai_move).unwrap();
+
+        let moves = repo.load_moves(&game_id).unwrap();
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].source, Some(MoveSource::AI));
+    }
+
+    #[test]
+    fn test_save_and_load_taunts() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game();
+        let game_id = game.id.clone();
+
+        repo.save_game(&game).unwrap();
+
+        // Save taunts
+        repo.save_taunt(&game_id, "You call that a move?", Some("MCP"))
+            .unwrap();
+        repo.save_taunt(&game_id, "I've seen better from a toddler!", Some("UI"))
+            .unwrap();
+
+        // Load taunts
+        let taunts = repo.load_taunts(&game_id).unwrap();
+        assert_eq!(taunts.len(), 2);
+        assert_eq!(taunts[0].body, "You call that a move?");
+        assert_eq!(taunts[0].sender, Some(shared::MoveSource::MCP));
+        assert_eq!(taunts[1].body, "I've seen better from a toddler!");
+        assert_eq!(taunts[1].sender, Some(shared::MoveSource::UI));
+    }
+
+    #[test]
+    fn test_get_taunts_defaults_to_the_most_recent_messages() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game();
+        let game_id = game.id.clone();
+        repo.save_game(&game).unwrap();
+
+        for i in 0..5 {
+            repo.save_taunt(&game_id, &format!("taunt {}", i), Some("UI"))
+                .unwrap();
+        }
+
+        let page = repo.get_taunts(&game_id, None, None, 2).unwrap();
+        assert_eq!(page.len(), 2);
+        assert_eq!(page[0].body, "taunt 3");
+        assert_eq!(page[1].body, "taunt 4");
+    }
+
+    #[test]
+    fn test_get_taunts_pages_backwards_and_forwards_by_id() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game();
+        let game_id = game.id.clone();
+        repo.save_game(&game).unwrap();
+
+        for i in 0..5 {
+            repo.save_taunt(&game_id, &format!("taunt {}", i), Some("UI"))
+                .unwrap();
+        }
+        let all = repo.get_taunts(&game_id, None, None, 5).unwrap();
+        let middle_id = all[2].id;
+
+        let before = repo.get_taunts(&game_id, Some(middle_id), None, 10).unwrap();
+        assert_eq!(
+            before.iter().map(|t| &t.body).collect::<Vec<_>>(),
+            vec!["taunt 0", "taunt 1"]
+        );
+
+        let after = repo.get_taunts(&game_id, None, Some(middle_id), 10).unwrap();
+        assert_eq!(
+            after.iter().map(|t| &t.body).collect::<Vec<_>>(),
+            vec!["taunt 3", "taunt 4"]
+        );
+    }
+
+    #[test]
+    fn test_reconstruct_board() {
+        let moves = vec![
+            Move {
+                player: Player::X,
+                row: 0,
+                col: 0,
+                timestamp: 1000,
+                source: Some(MoveSource::UI),
+            },
+            Move {
+                player: Player::O,
+                row: 1,
+                col: 1,
+                timestamp: 2000,
+                source: Some(MoveSource::UI),
+            },
+            Move {
+                player: Player::X,
+                row: 2,
+                col: 2,
+                timestamp: 3000,
+                source: Some(MoveSource::UI),
+            },
+        ];
+
+        let board = GameRepository::reconstruct_board(&moves).unwrap();
+
+        assert_eq!(board[0][0], Cell::Occupied(Player::X));
+        assert_eq!(board[1][1], Cell::Occupied(Player::O));
+        assert_eq!(board[2][2], Cell::Occupied(Player::X));
+        assert_eq!(board[0][1], Cell::Empty);
+    }
+
+    #[test]
+    fn test_serialize_board_round_trips_through_deserialize() {
+        let mut board = [[Cell::Empty; 3]; 3];
+        board[0][0] = Cell::Occupied(Player::X);
+        board[1][1] = Cell::Occupied(Player::O);
+
+        let encoded = serialize_board(&board);
+        assert_eq!(encoded, "X..O.....");
+        assert_eq!(encoded.len(), 9);
+        assert_eq!(deserialize_board(&encoded).unwrap(), board);
+    }
+
+    #[test]
+    fn test_deserialize_board_rejects_wrong_length() {
+        assert!(deserialize_board("XO").is_err());
+        assert!(deserialize_board("XO.......X").is_err());
+    }
+
+    #[test]
+    fn test_deserialize_board_rejects_invalid_characters() {
+        assert!(deserialize_board("XO???....").is_err());
+    }
+
+    #[test]
+    fn test_load_game_decodes_the_stored_board_snapshot() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game = create_test_game();
+        game.board[0][0] = Cell::Occupied(Player::X);
+        game.board[1][1] = Cell::Occupied(Player::O);
+        repo.save_game(&game).unwrap();
+
+        // No moves recorded, so a naive replay would see an empty board; the snapshot column
+        // is what makes the loaded board match what was saved.
+        let loaded = repo.load_game(&game.id).unwrap();
+        assert_eq!(loaded.board[0][0], Cell::Occupied(Player::X));
+        assert_eq!(loaded.board[1][1], Cell::Occupied(Player::O));
+    }
+
+    #[test]
+    fn test_update_game() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game = create_test_game();
+        let game_id = game.id.clone();
+
+        // Save initial game
+        repo.save_game(&game).unwrap();
+
+        // Update game state
+        game.current_turn = Player::O;
+        game.status = GameStatus::Won(Player::X);
+
+        // Save updated game
+        repo.save_game(&game).unwrap();
+
+        // Load and verify
+        let loaded = repo.load_game(&game_id).unwrap();
+        assert_eq!(loaded.current_turn, Player::O);
+        assert_eq!(loaded.status, GameStatus::Won(Player::X));
+    }
+
+    #[test]
+    fn test_list_games() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game1 = create_test_game();
+        let game2 = create_test_game();
+
+        repo.save_game(&game1).unwrap();
+        repo.save_game(&game2).unwrap();
+
+        let games = repo.list_games().unwrap();
+        assert_eq!(games.len(), 2);
+        let ids: Vec<&str> = games.iter().map(|g| g.id.as_str()).collect();
+        assert!(ids.contains(&game1.id.as_str()));
+        assert!(ids.contains(&game2.id.as_str()));
+    }
+
+    #[test]
+    fn test_get_game_version_returns_updated_at() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game();
+        repo.save_game(&game).unwrap();
+
+        let version = repo.get_game_version(&game.id).unwrap();
+        assert!(version.is_some());
+    }
+
+    #[test]
+    fn test_get_game_version_unknown_game_is_none() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        assert_eq!(repo.get_game_version("nonexistent").unwrap(), None);
+    }
+
+    #[test]
+    fn test_load_game_if_changed_returns_none_when_unchanged() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game();
+        repo.save_game(&game).unwrap();
+        let version = repo.get_game_version(&game.id).unwrap().unwrap();
+
+        let result = repo.load_game_if_changed(&game.id, version).unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_load_game_if_changed_returns_state_when_stale_since_token() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game();
+        repo.save_game(&game).unwrap();
+        let version = repo.get_game_version(&game.id).unwrap().unwrap();
+
+        let result = repo.load_game_if_changed(&game.id, version - 1).unwrap();
+        assert_eq!(result.unwrap().id, game.id);
+    }
+
+    #[test]
+    fn test_load_game_if_changed_unknown_game_is_none() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let result = repo.load_game_if_changed("nonexistent", 0).unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_record_result_credits_the_winner_and_loser() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game = create_test_game(); // human: X, ai: O
+        game.status = GameStatus::Won(Player::X);
+        repo.save_game(&game).unwrap();
+
+        repo.record_result(&game.id, "Intermediate").unwrap();
+
+        let top = repo.top_players(10).unwrap();
+        let human = top.iter().find(|p| p.player_id == "human").unwrap();
+        let ai = top.iter().find(|p| p.player_id == "ai").unwrap();
+        assert_eq!(human.wins, 1);
+        assert_eq!(ai.losses, 1);
+    }
+
+    #[test]
+    fn test_record_result_credits_both_sides_on_a_draw() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game = create_test_game();
+        game.status = GameStatus::Draw;
+        repo.save_game(&game).unwrap();
+
+        repo.record_result(&game.id, "Intermediate").unwrap();
+
+        let top = repo.top_players(10).unwrap();
+        let human = top.iter().find(|p| p.player_id == "human").unwrap();
+        let ai = top.iter().find(|p| p.player_id == "ai").unwrap();
+        assert_eq!(human.draws, 1);
+        assert_eq!(ai.draws, 1);
+    }
+
+    #[test]
+    fn test_record_result_is_idempotent_per_game() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game = create_test_game();
+        game.status = GameStatus::Won(Player::X);
+        repo.save_game(&game).unwrap();
+
+        repo.record_result(&game.id, "Intermediate").unwrap();
+        repo.record_result(&game.id, "Intermediate").unwrap();
+        repo.record_result(&game.id, "Intermediate").unwrap();
+
+        let top = repo.top_players(10).unwrap();
+        let human = top.iter().find(|p| p.player_id == "human").unwrap();
+        assert_eq!(human.wins, 1);
+    }
+
+    #[test]
+    fn test_record_result_skips_in_progress_games() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game();
+        repo.save_game(&game).unwrap();
+
+        repo.record_result(&game.id, "Intermediate").unwrap();
+
+        assert!(repo.top_players(10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_top_players_orders_by_wins_descending() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game1 = create_test_game();
+        game1.status = GameStatus::Won(Player::X); // human wins
+        repo.save_game(&game1).unwrap();
+        repo.record_result(&game1.id, "Intermediate").unwrap();
+
+        let mut game2 = create_test_game();
+        game2.status = GameStatus::Won(Player::O); // ai wins
+        repo.save_game(&game2).unwrap();
+        repo.record_result(&game2.id, "Intermediate").unwrap();
+
+        let mut game3 = create_test_game();
+        game3.status = GameStatus::Won(Player::O); // ai wins again
+        repo.save_game(&game3).unwrap();
+        repo.record_result(&game3.id, "Intermediate").unwrap();
+
+        let top = repo.top_players(10).unwrap();
+        assert_eq!(top[0].player_id, "ai");
+        assert_eq!(top[0].wins, 2);
+    }
+
+    #[test]
+    fn test_record_result_stores_move_count_and_difficulty_in_match_history() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game = create_test_game();
+        game.move_history = vec![
+            Move { player: Player::X, row: 0, col: 0, timestamp: 100, source: None },
+            Move { player: Player::O, row: 1, col: 1, timestamp: 105, source: None },
+        ];
+        game.status = GameStatus::Won(Player::X);
+        repo.save_game(&game).unwrap();
+
+        repo.record_result(&game.id, "Perfect").unwrap();
+
+        let (move_count, duration_secs, difficulty, winner): (i64, i64, String, Option<String>) = repo
+            .conn
+            .query_row(
+                "SELECT move_count, duration_secs, difficulty, winner FROM match_history WHERE game_id = ?1",
+                params![game.id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap();
+
+        assert_eq!(move_count, 2);
+        assert_eq!(duration_secs, 5);
+        assert_eq!(difficulty, "Perfect");
+        assert_eq!(winner.as_deref(), Some("human"));
+    }
+
+    #[test]
+    fn test_get_leaderboard_computes_win_rate() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game1 = create_test_game();
+        game1.status = GameStatus::Won(Player::X); // human wins
+        repo.save_game(&game1).unwrap();
+        repo.record_result(&game1.id, "Intermediate").unwrap();
+
+        let mut game2 = create_test_game();
+        game2.status = GameStatus::Won(Player::O); // ai wins, human loses
+        repo.save_game(&game2).unwrap();
+        repo.record_result(&game2.id, "Intermediate").unwrap();
+
+        let leaderboard = repo.get_leaderboard(10).unwrap();
+        let human = leaderboard.iter().find(|p| p.player_id == "human").unwrap();
+        assert_eq!(human.wins, 1);
+        assert_eq!(human.losses, 1);
+        assert_eq!(human.win_rate, 0.5);
+    }
+
+    #[test]
+    fn test_get_leaderboard_is_empty_with_no_recorded_games() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        assert!(repo.get_leaderboard(10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_leaderboard_tracks_games_played_and_last_played() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game = create_test_game();
+        game.status = GameStatus::Won(Player::X);
+        repo.save_game(&game).unwrap();
+        repo.record_result(&game.id, "Intermediate").unwrap();
+
+        let leaderboard = repo.get_leaderboard(10).unwrap();
+        let human = leaderboard.iter().find(|p| p.player_id == "human").unwrap();
+
+        assert_eq!(human.games_played, 1);
+        assert!(human.last_played.is_some());
+    }
+
+    #[test]
+    fn test_get_leaderboard_breaks_ties_in_wins_by_win_rate() {
+        let repo = GameRepository::new_in_memory().unwrap();
+
+        // "strong": 2 wins, 0 losses -> win rate 1.0
+        repo.bump_player_stat("strong", "wins").unwrap();
+        repo.bump_player_stat("strong", "wins").unwrap();
+
+        // "weak": 2 wins, 2 losses -> win rate 0.5
+        repo.bump_player_stat("weak", "wins").unwrap();
+        repo.bump_player_stat("weak", "wins").unwrap();
+        repo.bump_player_stat("weak", "losses").unwrap();
+        repo.bump_player_stat("weak", "losses").unwrap();
+
+        let leaderboard = repo.get_leaderboard(10).unwrap();
+        let strong_rank = leaderboard.iter().position(|p| p.player_id == "strong").unwrap();
+        let weak_rank = leaderboard.iter().position(|p| p.player_id == "weak").unwrap();
+
+        assert!(strong_rank < weak_rank);
+    }
+
+    #[test]
+    fn test_record_result_credits_winner_score_and_zero_for_loser() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game = create_test_game(); // human: X, ai: O
+        game.status = GameStatus::Won(Player::X);
+        repo.save_game(&game).unwrap();
+
+        repo.record_result(&game.id, "Intermediate").unwrap();
+
+        let top = repo.top_players(10).unwrap();
+        let human = top.iter().find(|p| p.player_id == "human").unwrap();
+        let ai = top.iter().find(|p| p.player_id == "ai").unwrap();
+        assert_eq!(human.score, WIN_POINTS);
+        assert_eq!(ai.score, LOSS_POINTS);
+    }
+
+    #[test]
+    fn test_record_result_credits_both_sides_draw_points() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game = create_test_game();
+        game.status = GameStatus::Draw;
+        repo.save_game(&game).unwrap();
+
+        repo.record_result(&game.id, "Intermediate").unwrap();
+
+        let top = repo.top_players(10).unwrap();
+        let human = top.iter().find(|p| p.player_id == "human").unwrap();
+        let ai = top.iter().find(|p| p.player_id == "ai").unwrap();
+        assert_eq!(human.score, DRAW_POINTS);
+        assert_eq!(ai.score, DRAW_POINTS);
+    }
+
+    #[test]
+    fn test_get_leaderboard_orders_by_score_before_wins() {
         let repo = GameRepository::new_in_memory().unwrap();
-        let game = create_test_game();
-        let game_id = game.id.clone();
 
+        // "grinder": fewer wins than "closer", but enough bonus score to rank above them
+        repo.bump_player_stat("grinder", "wins").unwrap();
+        repo.bump_player_score("grinder", WIN_POINTS).unwrap();
+        repo.bump_player_score("grinder", WIN_POINTS * 10).unwrap();
+
+        repo.bump_player_stat("closer", "wins").unwrap();
+        repo.bump_player_stat("closer", "wins").unwrap();
+        repo.bump_player_score("closer", WIN_POINTS).unwrap();
+        repo.bump_player_score("closer", WIN_POINTS).unwrap();
+
+        let leaderboard = repo.get_leaderboard(10).unwrap();
+        let grinder_rank = leaderboard.iter().position(|p| p.player_id == "grinder").unwrap();
+        let closer_rank = leaderboard.iter().position(|p| p.player_id == "closer").unwrap();
+
+        assert!(grinder_rank < closer_rank);
+    }
+
+    #[test]
+    fn test_reset_leaderboard_clears_stats_and_match_history() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game = create_test_game();
+        game.status = GameStatus::Won(Player::X);
         repo.save_game(&game).unwrap();
+        repo.record_result(&game.id, "Intermediate").unwrap();
 
-        // Save taunts
-        repo.save_taunt(&game_id, "You call that a move?", Some("MCP"))
-            .unwrap();
-        repo.save_taunt(&game_id, "I've seen better from a toddler!", Some("UI"))
+        repo.reset_leaderboard().unwrap();
+
+        assert!(repo.get_leaderboard(10).unwrap().is_empty());
+        let count: i64 = repo
+            .conn
+            .query_row("SELECT COUNT(*) FROM match_history", [], |row| row.get(0))
             .unwrap();
+        assert_eq!(count, 0);
+    }
 
-        // Load taunts
-        let taunts = repo.load_taunts(&game_id).unwrap();
-        assert_eq!(taunts.len(), 2);
-        assert_eq!(taunts[0].message, "You call that a move?");
-        assert_eq!(taunts[0].source, Some(shared::MoveSource::MCP));
-        assert_eq!(taunts[1].message, "I've seen better from a toddler!");
-        assert_eq!(taunts[1].source, Some(shared::MoveSource::UI));
+    #[test]
+    fn test_create_open_game_starts_waiting_for_opponent() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game_id = repo.create_open_game("alice").unwrap();
+
+        let waiting = repo.get_waiting_games().unwrap();
+        assert_eq!(waiting.len(), 1);
+        assert_eq!(waiting[0].id, game_id);
+        assert_eq!(waiting[0].creator_id, "alice");
+        assert_eq!(waiting[0].opponent_id, None);
     }
 
     #[test]
-    fn test_reconstruct_board() {
-        let moves = vec![
-            Move {
+    fn test_join_game_claims_the_empty_seat() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game_id = repo.create_open_game("alice").unwrap();
+
+        let joined = repo.join_game(&game_id, "bob").unwrap();
+
+        assert!(joined);
+        assert!(repo.get_waiting_games().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_join_game_rejects_a_second_joiner() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game_id = repo.create_open_game("alice").unwrap();
+
+        assert!(repo.join_game(&game_id, "bob").unwrap());
+        assert!(!repo.join_game(&game_id, "carol").unwrap());
+    }
+
+    #[test]
+    fn test_join_game_unknown_id_returns_false() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        assert!(!repo.join_game("nonexistent", "bob").unwrap());
+    }
+
+    #[test]
+    fn test_get_waiting_games_excludes_joined_games() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let open_id = repo.create_open_game("alice").unwrap();
+        let joined_id = repo.create_open_game("dave").unwrap();
+        repo.join_game(&joined_id, "erin").unwrap();
+
+        let waiting = repo.get_waiting_games().unwrap();
+        assert_eq!(waiting.len(), 1);
+        assert_eq!(waiting[0].id, open_id);
+    }
+
+    #[test]
+    fn test_write_behind_load_game_sees_unflushed_save() {
+        let repo = GameRepository::new_in_memory_with_write_behind(60_000).unwrap();
+        let game = create_test_game();
+        repo.save_game(&game).unwrap();
+
+        // Nothing should have actually hit the games table yet
+        let raw: Result<String, _> = repo.conn.query_row(
+            "SELECT id FROM games WHERE id = ?1",
+            params![&game.id],
+            |row| row.get(0),
+        );
+        assert!(raw.is_err());
+
+        let loaded = repo.load_game(&game.id).unwrap();
+        assert_eq!(loaded.id, game.id);
+    }
+
+    #[test]
+    fn test_write_behind_load_game_merges_unflushed_moves() {
+        let repo = GameRepository::new_in_memory_with_write_behind(60_000).unwrap();
+        let game = create_test_game();
+        repo.save_game(&game).unwrap();
+        repo.save_move(
+            &game.id,
+            &Move {
                 player: Player::X,
                 row: 0,
                 col: 0,
                 timestamp: 1000,
-                source: Some(MoveSource::UI),
-            },
-            Move {
-                player: Player::O,
-                row: 1,
-                col: 1,
-                timestamp: 2000,
-                source: Some(MoveSource::UI),
+                source: Some(MoveSource::MCP),
             },
-            Move {
+        )
+        .unwrap();
+
+        let loaded = repo.load_game(&game.id).unwrap();
+        assert_eq!(loaded.board[0][0], Cell::Occupied(Player::X));
+    }
+
+    #[test]
+    fn test_write_behind_flush_persists_buffered_writes() {
+        let repo = GameRepository::new_in_memory_with_write_behind(60_000).unwrap();
+        let game = create_test_game();
+        repo.save_game(&game).unwrap();
+        repo.save_move(
+            &game.id,
+            &Move {
                 player: Player::X,
-                row: 2,
-                col: 2,
-                timestamp: 3000,
-                source: Some(MoveSource::UI),
+                row: 0,
+                col: 0,
+                timestamp: 1000,
+                source: Some(MoveSource::MCP),
             },
-        ];
+        )
+        .unwrap();
 
-        let board = GameRepository::reconstruct_board(&moves).unwrap();
+        repo.flush().unwrap();
 
-        assert_eq!(board[0][0], Cell::Occupied(Player::X));
-        assert_eq!(board[1][1], Cell::Occupied(Player::O));
-        assert_eq!(board[2][2], Cell::Occupied(Player::X));
-        assert_eq!(board[0][1], Cell::Empty);
+        let raw: String = repo
+            .conn
+            .query_row(
+                "SELECT id FROM games WHERE id = ?1",
+                params![&game.id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(raw, game.id);
+        assert_eq!(repo.load_moves(&game.id).unwrap().len(), 1);
     }
 
     #[test]
-    fn test_update_game() {
+    fn test_write_behind_auto_flushes_once_lag_elapses() {
+        let repo = GameRepository::new_in_memory_with_write_behind(0).unwrap();
+        let game = create_test_game();
+        repo.save_game(&game).unwrap();
+
+        let raw: Result<String, _> = repo.conn.query_row(
+            "SELECT id FROM games WHERE id = ?1",
+            params![&game.id],
+            |row| row.get(0),
+        );
+        assert!(raw.is_ok());
+    }
+
+    #[test]
+    fn test_write_behind_flush_game_only_flushes_that_game() {
+        let repo = GameRepository::new_in_memory_with_write_behind(60_000).unwrap();
+        let game1 = create_test_game();
+        let game2 = create_test_game();
+        repo.save_game(&game1).unwrap();
+        repo.save_game(&game2).unwrap();
+
+        repo.flush_game(&game1.id).unwrap();
+
+        let persisted: Result<String, _> = repo.conn.query_row(
+            "SELECT id FROM games WHERE id = ?1",
+            params![&game2.id],
+            |row| row.get(0),
+        );
+        assert!(persisted.is_err());
+        assert!(repo.load_game(&game2.id).is_ok()); // still readable via the buffer
+    }
+
+    #[test]
+    fn test_write_behind_drop_flushes_outstanding_writes() {
+        let db_path = format!("/tmp/test-write-behind-{}.db", Uuid::new_v4());
+        let game = create_test_game();
+        {
+            let repo = GameRepository::with_write_behind(&db_path, 60_000).unwrap();
+            repo.save_game(&game).unwrap();
+        }
+
+        let repo = GameRepository::new(&db_path).unwrap();
+        assert!(repo.load_game(&game.id).is_ok());
+        let _ = std::fs::remove_file(&db_path);
+    }
+
+    fn backdate(repo: &GameRepository, game_id: &str, seconds_ago: i64) {
+        let updated_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64
+            - seconds_ago;
+        repo.conn
+            .execute(
+                "UPDATE games SET updated_at = ?1 WHERE id = ?2",
+                params![updated_at, game_id],
+            )
+            .unwrap();
+    }
+
+    fn backdate_turn(repo: &GameRepository, game_id: &str, seconds_ago: i64) {
+        let turn_started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64
+            - seconds_ago;
+        repo.conn
+            .execute(
+                "UPDATE games SET turn_started_at = ?1 WHERE id = ?2",
+                params![turn_started_at, game_id],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_save_game_sets_turn_started_at_on_insert() {
         let repo = GameRepository::new_in_memory().unwrap();
-        let mut game = create_test_game();
-        let game_id = game.id.clone();
+        let game = create_test_game();
+        repo.save_game(&game).unwrap();
 
-        // Save initial game
+        let turn_started_at: i64 = repo
+            .conn
+            .query_row(
+                "SELECT turn_started_at FROM games WHERE id = ?1",
+                params![&game.id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(turn_started_at > 0);
+    }
+
+    #[test]
+    fn test_save_game_resets_turn_started_at_when_turn_changes() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game = create_test_game();
         repo.save_game(&game).unwrap();
+        backdate_turn(&repo, &game.id, 1_000);
 
-        // Update game state
         game.current_turn = Player::O;
+        repo.save_game(&game).unwrap();
+
+        let deadline_ids = repo.games_past_deadline(10).unwrap();
+        assert!(!deadline_ids.contains(&game.id));
+    }
+
+    #[test]
+    fn test_save_game_keeps_turn_started_at_when_turn_unchanged() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game = create_test_game();
+        repo.save_game(&game).unwrap();
+        backdate_turn(&repo, &game.id, 1_000);
+
+        game.status = GameStatus::InProgress;
+        repo.save_game(&game).unwrap();
+
+        let deadline_ids = repo.games_past_deadline(10).unwrap();
+        assert!(deadline_ids.contains(&game.id));
+    }
+
+    #[test]
+    fn test_games_past_deadline_ignores_finished_games() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game = create_test_game();
         game.status = GameStatus::Won(Player::X);
+        repo.save_game(&game).unwrap();
+        backdate_turn(&repo, &game.id, 1_000);
 
-        // Save updated game
+        let deadline_ids = repo.games_past_deadline(10).unwrap();
+        assert!(!deadline_ids.contains(&game.id));
+    }
+
+    #[test]
+    fn test_resolve_timeout_forfeits_to_the_opponent() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game(); // current_turn: X
         repo.save_game(&game).unwrap();
+        backdate_turn(&repo, &game.id, 1_000);
 
-        // Load and verify
-        let loaded = repo.load_game(&game_id).unwrap();
-        assert_eq!(loaded.current_turn, Player::O);
-        assert_eq!(loaded.status, GameStatus::Won(Player::X));
+        repo.resolve_timeout(&game.id, 10).unwrap();
+
+        let loaded = repo.load_game(&game.id).unwrap();
+        assert_eq!(loaded.status, GameStatus::Won(Player::O));
+    }
+
+    #[test]
+    fn test_resolve_timeout_is_a_noop_before_the_deadline() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game();
+        repo.save_game(&game).unwrap();
+
+        repo.resolve_timeout(&game.id, 10_000).unwrap();
+
+        let loaded = repo.load_game(&game.id).unwrap();
+        assert_eq!(loaded.status, GameStatus::InProgress);
+    }
+
+    #[test]
+    fn test_resolve_timeout_is_a_noop_for_finished_games() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game = create_test_game();
+        game.status = GameStatus::Draw;
+        repo.save_game(&game).unwrap();
+        backdate_turn(&repo, &game.id, 1_000);
+
+        repo.resolve_timeout(&game.id, 10).unwrap();
+
+        let loaded = repo.load_game(&game.id).unwrap();
+        assert_eq!(loaded.status, GameStatus::Draw);
+    }
+
+    #[test]
+    fn test_cleanup_stale_games_forfeits_in_progress_game_past_turn_timeout() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game(); // InProgress by default
+        repo.save_game(&game).unwrap();
+        backdate(&repo, &game.id, 1_000);
+
+        let (forfeited, deleted) = cleanup_stale_games(&repo.conn, 10, 100_000).unwrap();
+
+        assert_eq!(forfeited, 1);
+        assert_eq!(deleted, 0);
+        assert_eq!(repo.load_game(&game.id).unwrap().status, GameStatus::Abandoned);
+    }
+
+    #[test]
+    fn test_cleanup_stale_games_keeps_recent_in_progress_game() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game();
+        repo.save_game(&game).unwrap();
+
+        let (forfeited, deleted) = cleanup_stale_games(&repo.conn, 1_000, 100_000).unwrap();
+
+        assert_eq!(forfeited, 0);
+        assert_eq!(deleted, 0);
+        assert_eq!(repo.load_game(&game.id).unwrap().status, GameStatus::InProgress);
+    }
+
+    #[test]
+    fn test_cleanup_stale_games_deletes_games_past_game_timeout_with_their_moves_and_taunts() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let mut game = create_test_game();
+        game.status = GameStatus::Won(Player::X);
+        repo.save_game(&game).unwrap();
+        repo.save_move(
+            &game.id,
+            &Move {
+                player: Player::X,
+                row: 0,
+                col: 0,
+                timestamp: 0,
+                source: None,
+            },
+        )
+        .unwrap();
+        backdate(&repo, &game.id, 1_000);
+
+        let (forfeited, deleted) = cleanup_stale_games(&repo.conn, 100_000, 10).unwrap();
+
+        assert_eq!(forfeited, 0);
+        assert_eq!(deleted, 1);
+        assert!(matches!(
+            repo.load_game(&game.id),
+            Err(GameError::GameNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_cleanup_stale_games_clears_current_game_if_reaped() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game();
+        repo.save_game(&game).unwrap();
+        repo.set_current_game_id(&game.id).unwrap();
+        backdate(&repo, &game.id, 1_000);
+
+        cleanup_stale_games(&repo.conn, 100_000, 10).unwrap();
+
+        assert_eq!(repo.get_current_game_id().unwrap(), None);
+    }
+
+    #[test]
+    fn test_delete_game_removes_the_game_its_moves_and_its_taunts() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game();
+        repo.save_game(&game).unwrap();
+        repo.save_move(
+            &game.id,
+            &Move {
+                player: Player::X,
+                row: 0,
+                col: 0,
+                timestamp: 0,
+                source: None,
+            },
+        )
+        .unwrap();
+        repo.save_taunt(&game.id, "gg", None).unwrap();
+
+        repo.delete_game(&game.id).unwrap();
+
+        assert!(matches!(
+            repo.load_game(&game.id),
+            Err(GameError::GameNotFound)
+        ));
+        assert!(repo.load_moves(&game.id).unwrap().is_empty());
+        assert!(
+            repo.get_taunts(&game.id, None, None, 10)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_delete_game_clears_current_game_if_it_pointed_there() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game();
+        repo.save_game(&game).unwrap();
+        repo.set_current_game_id(&game.id).unwrap();
+
+        repo.delete_game(&game.id).unwrap();
+
+        assert_eq!(repo.get_current_game_id().unwrap(), None);
+    }
+
+    #[test]
+    fn test_list_game_ids_with_last_activity_includes_every_game() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game1 = create_test_game();
+        let game2 = create_test_game();
+        repo.save_game(&game1).unwrap();
+        repo.save_game(&game2).unwrap();
+
+        let ids: Vec<String> = repo
+            .list_game_ids_with_last_activity()
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+
+        assert!(ids.contains(&game1.id));
+        assert!(ids.contains(&game2.id));
+        assert_eq!(ids.len(), 2);
+    }
+
+    #[test]
+    fn test_list_game_ids_with_last_activity_reflects_backdating() {
+        let repo = GameRepository::new_in_memory().unwrap();
+        let game = create_test_game();
+        repo.save_game(&game).unwrap();
+        backdate(&repo, &game.id, 1_000);
+
+        let (_, last_activity) = repo
+            .list_game_ids_with_last_activity()
+            .unwrap()
+            .into_iter()
+            .find(|(id, _)| id == &game.id)
+            .unwrap();
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        assert!(now - last_activity >= 1_000);
     }
 }
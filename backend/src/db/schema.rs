@@ -41,6 +41,97 @@ pub fn init_schema(conn: &Connection) -> Result<(), GameError> {
     let _ = conn.execute("ALTER TABLE moves ADD COLUMN source TEXT", []);
     let _ = conn.execute("ALTER TABLE taunts ADD COLUMN source TEXT", []);
 
+    // Track when the current turn started, for per-turn deadline enforcement (migration)
+    let _ = conn.execute("ALTER TABLE games ADD COLUMN turn_started_at INTEGER", []);
+
+    // Optional per-game turn-clock budget (seconds). NULL means no limit (migration).
+    let _ = conn.execute("ALTER TABLE games ADD COLUMN turn_limit_secs INTEGER", []);
+
+    // Optional per-game AI strength for `GameState::ai_choose_move` ("Easy"/"Normal"/"Hard").
+    // NULL defers to whatever strategy the caller otherwise uses (migration).
+    let _ = conn.execute("ALTER TABLE games ADD COLUMN ai_difficulty TEXT", []);
+
+    // Guards against double-counting a game's result in player_stats (migration)
+    let _ = conn.execute(
+        "ALTER TABLE games ADD COLUMN results_recorded INTEGER NOT NULL DEFAULT 0",
+        [],
+    );
+
+    // Compact 9-character board snapshot (see `serialize_board`/`deserialize_board`), so
+    // `load_game` can skip replaying every move. NULL on legacy rows written before this
+    // column existed; `load_game` falls back to move replay for those (migration).
+    let _ = conn.execute("ALTER TABLE games ADD COLUMN board TEXT", []);
+
+    // Monotonic state version, mirroring `GameManager`'s in-memory `state_versions` onto the
+    // persisted row so a reloaded `GameState` carries its own `changed_since` baseline across
+    // restarts (migration).
+    let _ = conn.execute(
+        "ALTER TABLE games ADD COLUMN version INTEGER NOT NULL DEFAULT 0",
+        [],
+    );
+
+    // The game a rematch was started from (see `GameManager::request_rematch`). NULL for
+    // games that aren't a rematch of anything (migration).
+    let _ = conn.execute("ALTER TABLE games ADD COLUMN previous_game_id TEXT", []);
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS player_stats (
+            player_id TEXT PRIMARY KEY,
+            wins INTEGER NOT NULL DEFAULT 0,
+            losses INTEGER NOT NULL DEFAULT 0,
+            draws INTEGER NOT NULL DEFAULT 0
+        )",
+        [],
+    )
+    .map_err(|e| GameError::DatabaseError {
+        message: e.to_string(),
+    })?;
+
+    // Per-entrant totals backing `get_leaderboard`'s games_played/last_played columns, alongside
+    // the win/loss/draw tally above (migration)
+    let _ = conn.execute(
+        "ALTER TABLE player_stats ADD COLUMN games_played INTEGER NOT NULL DEFAULT 0",
+        [],
+    );
+    let _ = conn.execute("ALTER TABLE player_stats ADD COLUMN last_played INTEGER", []);
+
+    // Cumulative points backing the scoring leaderboard, alongside the plain win/loss/draw
+    // tallies above (migration)
+    let _ = conn.execute(
+        "ALTER TABLE player_stats ADD COLUMN score INTEGER NOT NULL DEFAULT 0",
+        [],
+    );
+
+    // Known human players, keyed by a caller-supplied identifier
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS players (
+            id TEXT PRIMARY KEY,
+            display_name TEXT NOT NULL,
+            last_seen INTEGER NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| GameError::DatabaseError {
+        message: e.to_string(),
+    })?;
+
+    // Human-vs-human pairing lobby. Kept separate from `games` (whose `ai_player` column is
+    // NOT NULL and deeply load-bearing for the single-AI-opponent flow) rather than making that
+    // column nullable, which would require rebuilding the table.
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS open_games (
+            id TEXT PRIMARY KEY,
+            creator_id TEXT NOT NULL,
+            opponent_id TEXT,
+            status TEXT NOT NULL,
+            created_at INTEGER NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| GameError::DatabaseError {
+        message: e.to_string(),
+    })?;
+
     conn.execute(
         "CREATE TABLE IF NOT EXISTS taunts (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -56,6 +147,24 @@ pub fn init_schema(conn: &Connection) -> Result<(), GameError> {
         message: e.to_string(),
     })?;
 
+    // Per-game result detail backing `get_leaderboard`'s move-count/duration/difficulty
+    // breakdown, alongside the win/loss/draw tallies in `player_stats`
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS match_history (
+            game_id TEXT PRIMARY KEY,
+            winner TEXT,
+            move_count INTEGER NOT NULL,
+            duration_secs INTEGER NOT NULL,
+            difficulty TEXT NOT NULL,
+            recorded_at INTEGER NOT NULL,
+            FOREIGN KEY (game_id) REFERENCES games(id)
+        )",
+        [],
+    )
+    .map_err(|e| GameError::DatabaseError {
+        message: e.to_string(),
+    })?;
+
     // Table to track the current active game (singleton pattern)
     conn.execute(
         "CREATE TABLE IF NOT EXISTS current_game (
@@ -69,6 +178,15 @@ pub fn init_schema(conn: &Connection) -> Result<(), GameError> {
         message: e.to_string(),
     })?;
 
+    // Speeds up the stale-game sweep's cutoff scans, which filter on `updated_at` every pass
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_games_updated_at ON games(updated_at)",
+        [],
+    )
+    .map_err(|e| GameError::DatabaseError {
+        message: e.to_string(),
+    })?;
+
     Ok(())
 }
 
@@ -106,6 +224,19 @@ mod tests {
         assert_eq!(result.unwrap(), "taunts");
     }
 
+    #[test]
+    fn test_init_schema_creates_updated_at_index() {
+        let conn = Connection::open_in_memory().unwrap();
+        assert!(init_schema(&conn).is_ok());
+
+        let result: Result<String, _> = conn.query_row(
+            "SELECT name FROM sqlite_master WHERE type='index' AND name='idx_games_updated_at'",
+            [],
+            |row| row.get(0),
+        );
+        assert_eq!(result.unwrap(), "idx_games_updated_at");
+    }
+
     #[test]
     fn test_init_schema_idempotent() {
         let conn = Connection::open_in_memory().unwrap();
@@ -1,56 +1,112 @@
 use shared::{Cell, Player};
 
-/// Represents a 3x3 tic-tac-toe board
+/// A generalized m,n,k board: `width` x `height` cells, `k` in a row (horizontally, vertically,
+/// or diagonally) to win. Classic tic-tac-toe is the special case [`Board::standard`]
+/// (3x3, 3-in-a-row); non-standard dimensions let the same move/taunt/MCP plumbing host
+/// Gomoku/Connect-style variants, though only the standard shape round-trips through
+/// `GameState::board` today (see `to_array`).
 #[derive(Debug, Clone)]
 pub struct Board {
-    cells: [[Cell; 3]; 3],
+    width: u8,
+    height: u8,
+    k: u8,
+    cells: Vec<Cell>,
 }
 
 impl Board {
-    /// Create a new empty board
+    /// Create a new empty 3x3 board with the classic 3-in-a-row win condition.
     pub fn new() -> Self {
+        Self::with_dimensions(3, 3, 3)
+    }
+
+    /// The classic 3x3 tic-tac-toe board: 3x3 cells, 3-in-a-row to win. An explicit alias for
+    /// [`Board::new`] for call sites that want to make "the standard game" intent clear
+    /// alongside a non-standard [`Board::with_dimensions`] board.
+    pub fn standard() -> Self {
+        Self::new()
+    }
+
+    /// Create a new empty board of `width` x `height` cells, with `k` in a row to win.
+    pub fn with_dimensions(width: u8, height: u8, k: u8) -> Self {
         Self {
-            cells: [[Cell::default(); 3]; 3],
+            width,
+            height,
+            k,
+            cells: vec![Cell::default(); width as usize * height as usize],
         }
     }
 
-    /// Get the cell at the given position
-    #[allow(dead_code)] // Will be used by game state management
-    pub fn get(&self, row: u8, col: u8) -> Option<Cell> {
-        if row < 3 && col < 3 {
-            Some(self.cells[row as usize][col as usize])
+    /// Board width in cells
+    pub fn width(&self) -> u8 {
+        self.width
+    }
+
+    /// Board height in cells
+    pub fn height(&self) -> u8 {
+        self.height
+    }
+
+    /// How many consecutive cells of one player's mark constitute a win on this board
+    pub fn k(&self) -> u8 {
+        self.k
+    }
+
+    fn index(&self, row: u8, col: u8) -> Option<usize> {
+        if row < self.height && col < self.width {
+            Some(row as usize * self.width as usize + col as usize)
         } else {
             None
         }
     }
 
+    /// Get the cell at the given position
+    #[allow(dead_code)] // Will be used by game state management
+    pub fn get(&self, row: u8, col: u8) -> Option<Cell> {
+        self.index(row, col).map(|i| self.cells[i])
+    }
+
     /// Set a cell to the given player
     #[allow(dead_code)] // Will be used by game state management
     pub fn set(&mut self, row: u8, col: u8, player: Player) -> Result<(), String> {
-        if row >= 3 || col >= 3 {
-            return Err(format!("Position ({}, {}) is out of bounds", row, col));
-        }
-
-        if self.cells[row as usize][col as usize] != Cell::Empty {
+        let idx = self.index(row, col).ok_or_else(|| {
+            format!(
+                "Position ({}, {}) is out of bounds for a {}x{} board",
+                row, col, self.width, self.height
+            )
+        })?;
+
+        if self.cells[idx] != Cell::Empty {
             return Err(format!("Cell ({}, {}) is already occupied", row, col));
         }
 
-        self.cells[row as usize][col as usize] = Cell::Occupied(player);
+        self.cells[idx] = Cell::Occupied(player);
         Ok(())
     }
 
     /// Check if the board is full
     #[allow(dead_code)] // Will be used by game state management
     pub fn is_full(&self) -> bool {
-        self.cells
-            .iter()
-            .all(|row| row.iter().all(|cell| *cell != Cell::Empty))
+        self.cells.iter().all(|cell| *cell != Cell::Empty)
     }
 
-    /// Convert board to 2D array for serialization
+    /// Convert a standard 3x3 board to the fixed-size array `GameState::board` persists.
+    /// Panics if called on a non-standard-sized board: nothing upstream of the engine (DB
+    /// snapshot, MessagePack/SGF persistence, the HTTP/MCP API) carries a board shape yet, so a
+    /// non-standard `Board` has nowhere in `GameState` to go until that plumbing catches up.
     #[allow(dead_code)] // Will be used by API layer
     pub fn to_array(&self) -> [[Cell; 3]; 3] {
-        self.cells
+        assert_eq!(
+            (self.width, self.height),
+            (3, 3),
+            "to_array only supports the standard 3x3 board"
+        );
+        let mut arr = [[Cell::Empty; 3]; 3];
+        for row in 0..3u8 {
+            for col in 0..3u8 {
+                arr[row as usize][col as usize] = self.get(row, col).unwrap();
+            }
+        }
+        arr
     }
 }
 
@@ -136,4 +192,20 @@ mod tests {
         assert_eq!(board.get(0, 3), None);
         assert_eq!(board.get(5, 5), None);
     }
+
+    #[test]
+    fn test_standard_matches_new() {
+        assert_eq!(Board::standard().width(), Board::new().width());
+        assert_eq!(Board::standard().k(), 3);
+    }
+
+    #[test]
+    fn test_with_dimensions_custom_size() {
+        let mut board = Board::with_dimensions(5, 4, 4);
+        assert_eq!((board.width(), board.height(), board.k()), (5, 4, 4));
+        assert!(board.get(4, 0).is_none());
+        assert!(board.set(4, 0, Player::X).is_err());
+        board.set(2, 4, Player::O).unwrap();
+        assert_eq!(board.get(2, 4), Some(Cell::Occupied(Player::O)));
+    }
 }
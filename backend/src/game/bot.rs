@@ -0,0 +1,645 @@
+use super::board::Board;
+use super::logic::check_winner;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use shared::{AiDifficulty, Cell, GameState, Player};
+
+/// AI opponent strength, from a coin-flip to an unbeatable minimax search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Difficulty {
+    /// Picks a uniformly random empty cell
+    Random,
+    /// Wins or blocks an immediate three-in-a-row, otherwise moves randomly
+    #[default]
+    Intermediate,
+    /// Full minimax search; never loses
+    Perfect,
+}
+
+impl Difficulty {
+    /// Parse a difficulty name as used in MCP params and the `AI_DIFFICULTY` env var.
+    /// Unrecognized or missing input falls back to the default.
+    pub fn parse(name: Option<&str>) -> Self {
+        match name {
+            Some("Random") => Difficulty::Random,
+            Some("Intermediate") => Difficulty::Intermediate,
+            Some("Perfect") => Difficulty::Perfect,
+            _ => Difficulty::default(),
+        }
+    }
+
+    /// The default difficulty, taken from the `AI_DIFFICULTY` env var if set
+    pub fn from_env() -> Self {
+        Self::parse(std::env::var("AI_DIFFICULTY").ok().as_deref())
+    }
+
+    /// The name this difficulty round-trips through `parse`, for persisting match history
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Difficulty::Random => "Random",
+            Difficulty::Intermediate => "Intermediate",
+            Difficulty::Perfect => "Perfect",
+        }
+    }
+}
+
+/// The 8 winning lines on a 3x3 board
+const LINES: [[(u8, u8); 3]; 8] = [
+    [(0, 0), (0, 1), (0, 2)],
+    [(1, 0), (1, 1), (1, 2)],
+    [(2, 0), (2, 1), (2, 2)],
+    [(0, 0), (1, 0), (2, 0)],
+    [(0, 1), (1, 1), (2, 1)],
+    [(0, 2), (1, 2), (2, 2)],
+    [(0, 0), (1, 1), (2, 2)],
+    [(0, 2), (1, 1), (2, 0)],
+];
+
+/// A built-in opponent that auto-plays the AI's turn once the human has moved
+#[derive(Debug, Clone, Copy)]
+pub struct Bot {
+    pub difficulty: Difficulty,
+}
+
+impl Bot {
+    pub fn new(difficulty: Difficulty) -> Self {
+        Self { difficulty }
+    }
+
+    /// Choose `player`'s move on `board`, or `None` if the board is full
+    pub fn choose_move(&self, board: &Board, player: Player) -> Option<(u8, u8)> {
+        match self.difficulty {
+            Difficulty::Random => random_move(board),
+            Difficulty::Intermediate => {
+                win_or_block_move(board, player).or_else(|| random_move(board))
+            }
+            Difficulty::Perfect => best_move(board, player),
+        }
+    }
+}
+
+fn empty_cells(board: &Board) -> Vec<(u8, u8)> {
+    let mut cells = Vec::new();
+    for row in 0..3 {
+        for col in 0..3 {
+            if board.get(row, col) == Some(Cell::Empty) {
+                cells.push((row, col));
+            }
+        }
+    }
+    cells
+}
+
+fn random_move(board: &Board) -> Option<(u8, u8)> {
+    empty_cells(board).choose(&mut rand::thread_rng()).copied()
+}
+
+/// A winning move for `player` if one exists, else a move that blocks the opponent's
+fn win_or_block_move(board: &Board, player: Player) -> Option<(u8, u8)> {
+    find_line_completion(board, player).or_else(|| find_line_completion(board, player.opponent()))
+}
+
+/// The empty cell that would complete a two-in-a-row for `player`, if any of the 8 lines has one
+fn find_line_completion(board: &Board, player: Player) -> Option<(u8, u8)> {
+    for line in LINES {
+        let cells: Vec<Cell> = line.iter().map(|(r, c)| board.get(*r, *c).unwrap()).collect();
+        let player_count = cells.iter().filter(|c| **c == Cell::Occupied(player)).count();
+        let empty_index = cells.iter().position(|c| *c == Cell::Empty);
+
+        if let (2, Some(idx)) = (player_count, empty_index) {
+            return Some(line[idx]);
+        }
+    }
+    None
+}
+
+/// The minimax-optimal move for `ai_player` to play on `board`
+fn best_move(board: &Board, ai_player: Player) -> Option<(u8, u8)> {
+    empty_cells(board)
+        .into_iter()
+        .map(|(row, col)| {
+            let mut next = board.clone();
+            next.set(row, col, ai_player).ok();
+            let score = minimax(&next, ai_player.opponent(), ai_player, 1);
+            (score, (row, col))
+        })
+        .max_by_key(|(score, _)| *score)
+        .map(|(_, pos)| pos)
+}
+
+/// Score `board` from `ai_player`'s perspective, with `player_to_move` about to play next:
+/// `10 - depth` for an AI win, `depth - 10` for an AI loss, `0` for a draw. Recurses over every
+/// empty cell, maximizing on the AI's turns and minimizing on the opponent's.
+fn minimax(board: &Board, player_to_move: Player, ai_player: Player, depth: i32) -> i32 {
+    if let Some((winner, _)) = check_winner(board) {
+        return if winner == ai_player {
+            10 - depth
+        } else {
+            depth - 10
+        };
+    }
+    if board.is_full() {
+        return 0;
+    }
+
+    let scores = empty_cells(board).into_iter().map(|(row, col)| {
+        let mut next = board.clone();
+        next.set(row, col, player_to_move).ok();
+        minimax(&next, player_to_move.opponent(), ai_player, depth + 1)
+    });
+
+    if player_to_move == ai_player {
+        scores.max().unwrap()
+    } else {
+        scores.min().unwrap()
+    }
+}
+
+/// Per-`AiDifficulty` cap on how many of the top-scoring moves `ai_choose_move` samples from.
+const AI_EASY_MAX_CHOICES: usize = 5;
+const AI_NORMAL_MAX_CHOICES: usize = 3;
+const AI_HARD_MAX_CHOICES: usize = 1;
+
+/// Rank every empty cell on `state`'s board by minimax value for `state.ai_player`, then pick
+/// uniformly at random among the top-scoring moves: only the single best for `Hard`, the top
+/// `AI_NORMAL_MAX_CHOICES` for `Normal`, or the top `AI_EASY_MAX_CHOICES` (or fewer, if the board
+/// is nearly full) for `Easy` — letting the weaker levels blunder instead of always playing
+/// optimally. Returns `None` if `state.ai_difficulty` is unset or the board has no empty cells.
+pub fn ai_choose_move(state: &GameState) -> Option<(u8, u8)> {
+    let difficulty = state.ai_difficulty?;
+
+    let mut board = Board::new();
+    for m in &state.move_history {
+        board.set(m.row, m.col, m.player).ok();
+    }
+
+    let mut scored: Vec<(i32, (u8, u8))> = empty_cells(&board)
+        .into_iter()
+        .map(|(row, col)| {
+            let mut next = board.clone();
+            next.set(row, col, state.ai_player).ok();
+            let score = minimax(&next, state.ai_player.opponent(), state.ai_player, 1);
+            (score, (row, col))
+        })
+        .collect();
+
+    if scored.is_empty() {
+        return None;
+    }
+
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+    let pool_size = match difficulty {
+        AiDifficulty::Hard => AI_HARD_MAX_CHOICES,
+        AiDifficulty::Normal => AI_NORMAL_MAX_CHOICES,
+        AiDifficulty::Easy => AI_EASY_MAX_CHOICES,
+    };
+
+    scored[..pool_size.min(scored.len())]
+        .choose(&mut rand::thread_rng())
+        .map(|(_, pos)| *pos)
+}
+
+/// Chance `get_ai_choice` plays the minimax-optimal move on `AiDifficulty::Normal`, vs. a
+/// uniformly random legal cell the rest of the time.
+const AI_NORMAL_OPTIMAL_CHANCE: f64 = 0.7;
+
+/// A one-shot move choice for `player` on `board`, distinct from `ai_choose_move` (which reads
+/// the difficulty and AI seat off a persisted `GameState`): `Hard` always plays the
+/// minimax-optimal cell, `Normal` plays it with `AI_NORMAL_OPTIMAL_CHANCE` probability and a
+/// random legal cell otherwise, and `Easy` always plays randomly. Returns `None` once `board`
+/// is already terminal (won or full).
+pub fn get_ai_choice(board: &Board, player: Player, difficulty: AiDifficulty) -> Option<(u8, u8)> {
+    if check_winner(board).is_some() || board.is_full() {
+        return None;
+    }
+
+    match difficulty {
+        AiDifficulty::Hard => best_move(board, player),
+        AiDifficulty::Normal => {
+            if rand::thread_rng().gen_bool(AI_NORMAL_OPTIMAL_CHANCE) {
+                best_move(board, player)
+            } else {
+                random_move(board)
+            }
+        }
+        AiDifficulty::Easy => random_move(board),
+    }
+}
+
+/// Every empty cell on `board`, scanning the full `width()` x `height()` extent rather than
+/// `empty_cells`'s hard-coded 3x3 — MCTS is the one search here meant to scale past classic
+/// tic-tac-toe (see `mcts_choose_move`).
+fn legal_moves(board: &Board) -> Vec<(u8, u8)> {
+    let mut cells = Vec::new();
+    for row in 0..board.height() {
+        for col in 0..board.width() {
+            if board.get(row, col) == Some(Cell::Empty) {
+                cells.push((row, col));
+            }
+        }
+    }
+    cells
+}
+
+/// `board`'s outcome for `player`, if it's terminal: `1.0` if `player` won, `0.0` if they lost,
+/// `0.5` for a draw. `None` if the game is still in progress.
+fn terminal_value_for(board: &Board, player: Player) -> Option<f64> {
+    if let Some((winner, _)) = check_winner(board) {
+        Some(if winner == player { 1.0 } else { 0.0 })
+    } else if board.is_full() {
+        Some(0.5)
+    } else {
+        None
+    }
+}
+
+/// Play uniformly random moves from `board` (whose turn is `to_move`) to a terminal state,
+/// returning the outcome from `perspective`'s point of view.
+fn simulate(board: &Board, mut to_move: Player, perspective: Player) -> f64 {
+    let mut board = board.clone();
+    loop {
+        if let Some(value) = terminal_value_for(&board, perspective) {
+            return value;
+        }
+        let (row, col) = *legal_moves(&board)
+            .choose(&mut rand::thread_rng())
+            .expect("terminal_value_for returned None, so the board isn't full");
+        board.set(row, col, to_move).ok();
+        to_move = to_move.opponent();
+    }
+}
+
+/// Exploration constant for `uct`'s `c*sqrt(ln(N_parent)/n)` term (≈√2, the standard choice
+/// balancing exploitation of high-scoring children against exploring under-visited ones).
+const MCTS_EXPLORATION: f64 = 1.41;
+
+/// One node of the MCTS search tree: the board after `last_move` was played (`None` only at
+/// the root), who made that move (`player_just_moved`) and whose turn it is now (`to_move`),
+/// plus the usual visit count `n` and win score `w`. `w` is always from `player_just_moved`'s
+/// perspective, so a node's own children (whose `player_just_moved` is this node's `to_move`)
+/// can be compared by UCT without any sign-flipping — `child.w/child.n` already *is* the win
+/// rate for whoever is deciding between them.
+struct MctsNode {
+    board: Board,
+    player_just_moved: Player,
+    to_move: Player,
+    last_move: Option<(u8, u8)>,
+    n: u32,
+    w: f64,
+    children: Vec<MctsNode>,
+    untried_moves: Vec<(u8, u8)>,
+}
+
+impl MctsNode {
+    fn new(
+        board: Board,
+        player_just_moved: Player,
+        to_move: Player,
+        last_move: Option<(u8, u8)>,
+    ) -> Self {
+        let untried_moves = legal_moves(&board);
+        Self {
+            board,
+            player_just_moved,
+            to_move,
+            last_move,
+            n: 0,
+            w: 0.0,
+            children: Vec::new(),
+            untried_moves,
+        }
+    }
+
+    /// UCT score of this (already-visited) child, for comparison against its siblings.
+    fn uct(&self, parent_n: f64) -> f64 {
+        self.w / self.n as f64 + MCTS_EXPLORATION * (parent_n.ln() / self.n as f64).sqrt()
+    }
+}
+
+/// Run one selection/expansion/simulation/backpropagation pass starting at `node`, returning
+/// the outcome from `node.player_just_moved`'s perspective to propagate up to the caller. Every
+/// node has at least one untried move expanded into a simulated child before its existing
+/// children are compared by UCT, so no child is ever selected on zero visits.
+fn mcts_iteration(node: &mut MctsNode) -> f64 {
+    if let Some(value) = terminal_value_for(&node.board, node.player_just_moved) {
+        node.n += 1;
+        node.w += value;
+        return value;
+    }
+
+    let value = if let Some((row, col)) = node.untried_moves.pop() {
+        // Expansion: add one child per legal empty cell of this leaf.
+        let mut child_board = node.board.clone();
+        child_board.set(row, col, node.to_move).ok();
+        let mut child = MctsNode::new(child_board, node.to_move, node.to_move.opponent(), Some((row, col)));
+
+        // Simulation: play the new child out to a terminal state with random moves, scored
+        // from the mover-into-child's perspective (i.e. this node's `to_move`).
+        let rollout = simulate(&child.board, child.to_move, child.player_just_moved);
+        child.n = 1;
+        child.w = rollout;
+        node.children.push(child);
+        1.0 - rollout // flip to this node's own player_just_moved's perspective
+    } else {
+        // Selection: every legal move already has a child, so descend by maximizing UCT —
+        // `to_move` picks whichever child scores best for `to_move`, since child.w already is.
+        let parent_n = node.n as f64;
+        let best = node
+            .children
+            .iter_mut()
+            .max_by(|a, b| a.uct(parent_n).partial_cmp(&b.uct(parent_n)).unwrap())
+            .expect("a non-terminal board always has at least one legal move");
+        1.0 - mcts_iteration(best)
+    };
+
+    // Backpropagation: every node on the path gets its visit count and win score updated.
+    node.n += 1;
+    node.w += value;
+    value
+}
+
+/// Pick `ai_player`'s move on `board` via Monte Carlo Tree Search: `iterations` passes of
+/// selection, expansion, simulation, and backpropagation from a root at the current position
+/// (see `mcts_iteration`), then the root child with the most visits — the standard, more robust
+/// choice over the one with the highest average score. Lets AI strength scale past the depth a
+/// full minimax search can handle once `Board` grows beyond classic 3x3 tic-tac-toe. `None` if
+/// `board` has no empty cells.
+pub fn mcts_choose_move(board: &Board, ai_player: Player, iterations: u32) -> Option<(u8, u8)> {
+    let mut root = MctsNode::new(board.clone(), ai_player.opponent(), ai_player, None);
+    if root.untried_moves.is_empty() {
+        return None;
+    }
+
+    for _ in 0..iterations {
+        mcts_iteration(&mut root);
+    }
+
+    root.children
+        .iter()
+        .max_by_key(|child| child.n)
+        .and_then(|child| child.last_move)
+}
+
+/// Rollout budget per `AiDifficulty`, so `compute_ai_move` can scale search strength the same
+/// way `ai_choose_move`'s minimax pool sizes do.
+const MCTS_EASY_ITERATIONS: u32 = 50;
+const MCTS_NORMAL_ITERATIONS: u32 = 200;
+const MCTS_HARD_ITERATIONS: u32 = 800;
+
+/// The `mcts_choose_move` iteration budget for `difficulty`, used by `GameManager::compute_ai_move`.
+pub fn mcts_iterations_for(difficulty: AiDifficulty) -> u32 {
+    match difficulty {
+        AiDifficulty::Easy => MCTS_EASY_ITERATIONS,
+        AiDifficulty::Normal => MCTS_NORMAL_ITERATIONS,
+        AiDifficulty::Hard => MCTS_HARD_ITERATIONS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board_from(moves: &[(u8, u8, Player)]) -> Board {
+        let mut board = Board::new();
+        for (row, col, player) in moves {
+            board.set(*row, *col, *player).unwrap();
+        }
+        board
+    }
+
+    #[test]
+    fn test_difficulty_parse_recognizes_names() {
+        assert_eq!(Difficulty::parse(Some("Random")), Difficulty::Random);
+        assert_eq!(
+            Difficulty::parse(Some("Intermediate")),
+            Difficulty::Intermediate
+        );
+        assert_eq!(Difficulty::parse(Some("Perfect")), Difficulty::Perfect);
+    }
+
+    #[test]
+    fn test_difficulty_parse_falls_back_to_default() {
+        assert_eq!(Difficulty::parse(Some("nonsense")), Difficulty::default());
+        assert_eq!(Difficulty::parse(None), Difficulty::default());
+    }
+
+    #[test]
+    fn test_difficulty_as_str_round_trips_through_parse() {
+        for difficulty in [Difficulty::Random, Difficulty::Intermediate, Difficulty::Perfect] {
+            assert_eq!(Difficulty::parse(Some(difficulty.as_str())), difficulty);
+        }
+    }
+
+    #[test]
+    fn test_random_move_picks_an_empty_cell() {
+        let board = board_from(&[(0, 0, Player::X)]);
+        let bot = Bot::new(Difficulty::Random);
+        let (row, col) = bot.choose_move(&board, Player::O).unwrap();
+        assert_eq!(board.get(row, col), Some(Cell::Empty));
+    }
+
+    #[test]
+    fn test_intermediate_takes_the_winning_move() {
+        // O O _  -> O should complete the row
+        let board = board_from(&[(0, 0, Player::O), (0, 1, Player::O), (1, 0, Player::X)]);
+        let bot = Bot::new(Difficulty::Intermediate);
+        assert_eq!(bot.choose_move(&board, Player::O), Some((0, 2)));
+    }
+
+    #[test]
+    fn test_intermediate_blocks_the_opponents_winning_move() {
+        // X X _  -> O must block at (0, 2)
+        let board = board_from(&[(0, 0, Player::X), (0, 1, Player::X), (1, 0, Player::O)]);
+        let bot = Bot::new(Difficulty::Intermediate);
+        assert_eq!(bot.choose_move(&board, Player::O), Some((0, 2)));
+    }
+
+    #[test]
+    fn test_perfect_takes_the_winning_move() {
+        let board = board_from(&[(0, 0, Player::X), (0, 1, Player::X), (1, 0, Player::O)]);
+        let bot = Bot::new(Difficulty::Perfect);
+        assert_eq!(bot.choose_move(&board, Player::X), Some((0, 2)));
+    }
+
+    #[test]
+    fn test_perfect_never_loses_against_a_random_opponent() {
+        let bot = Bot::new(Difficulty::Perfect);
+
+        for _ in 0..20 {
+            let mut board = Board::new();
+            let mut turn = Player::X; // Perfect bot always plays X here
+            loop {
+                let mov = if turn == Player::X {
+                    bot.choose_move(&board, Player::X)
+                } else {
+                    random_move(&board)
+                };
+                let Some((row, col)) = mov else { break };
+                board.set(row, col, turn).unwrap();
+
+                if let Some((winner, _)) = check_winner(&board) {
+                    assert_eq!(winner, Player::X);
+                    break;
+                }
+                if board.is_full() {
+                    break;
+                }
+                turn = turn.opponent();
+            }
+        }
+    }
+
+    #[test]
+    fn test_minimax_scores_an_immediate_win_highest() {
+        let board = board_from(&[(0, 0, Player::X), (0, 1, Player::X)]);
+        let mut win = board.clone();
+        win.set(0, 2, Player::X).unwrap();
+        assert_eq!(minimax(&win, Player::O, Player::X, 1), 9);
+    }
+
+    fn state_with_moves(
+        ai_difficulty: Option<AiDifficulty>,
+        ai_player: Player,
+        moves: &[(u8, u8, Player)],
+    ) -> shared::GameState {
+        shared::GameState {
+            id: "g".to_string(),
+            board: [[Cell::Empty; 3]; 3],
+            current_turn: ai_player,
+            human_player: ai_player.opponent(),
+            ai_player,
+            status: shared::GameStatus::InProgress,
+            move_history: moves
+                .iter()
+                .map(|(row, col, player)| shared::Move {
+                    player: *player,
+                    row: *row,
+                    col: *col,
+                    timestamp: 0,
+                    source: None,
+                })
+                .collect(),
+            taunts: vec![],
+            winning_line: None,
+            turn_started_at: 0,
+            turn_limit_secs: None,
+            ai_difficulty,
+            version: 0,
+            previous_game_id: None,
+        }
+    }
+
+    #[test]
+    fn test_ai_choose_move_none_without_difficulty() {
+        let state = state_with_moves(None, Player::O, &[]);
+        assert_eq!(ai_choose_move(&state), None);
+    }
+
+    #[test]
+    fn test_ai_choose_move_hard_always_takes_the_winning_move() {
+        let state = state_with_moves(
+            Some(AiDifficulty::Hard),
+            Player::O,
+            &[(0, 0, Player::O), (0, 1, Player::O), (1, 0, Player::X)],
+        );
+        assert_eq!(ai_choose_move(&state), Some((0, 2)));
+    }
+
+    #[test]
+    fn test_ai_choose_move_easy_can_pick_a_losing_move() {
+        // O is about to lose no matter what; every empty cell scores the same, so Easy's
+        // top-5 pool is the whole board and any empty cell is a valid pick.
+        let state = state_with_moves(
+            Some(AiDifficulty::Easy),
+            Player::O,
+            &[(0, 0, Player::X), (0, 1, Player::X), (1, 0, Player::O)],
+        );
+        let (row, col) = ai_choose_move(&state).unwrap();
+        let board = board_from(&[(0, 0, Player::X), (0, 1, Player::X), (1, 0, Player::O)]);
+        assert_eq!(board.get(row, col), Some(Cell::Empty));
+    }
+
+    #[test]
+    fn test_get_ai_choice_hard_always_takes_the_winning_move() {
+        let board = board_from(&[(0, 0, Player::O), (0, 1, Player::O), (1, 0, Player::X)]);
+        assert_eq!(
+            get_ai_choice(&board, Player::O, AiDifficulty::Hard),
+            Some((0, 2))
+        );
+    }
+
+    #[test]
+    fn test_get_ai_choice_easy_returns_a_legal_cell() {
+        let board = board_from(&[(0, 0, Player::X)]);
+        let (row, col) = get_ai_choice(&board, Player::O, AiDifficulty::Easy).unwrap();
+        assert_eq!(board.get(row, col), Some(Cell::Empty));
+    }
+
+    #[test]
+    fn test_get_ai_choice_none_on_won_board() {
+        let board = board_from(&[(0, 0, Player::X), (0, 1, Player::X), (0, 2, Player::X)]);
+        assert_eq!(get_ai_choice(&board, Player::O, AiDifficulty::Hard), None);
+    }
+
+    #[test]
+    fn test_get_ai_choice_none_on_full_board() {
+        let mut moves = Vec::new();
+        for row in 0..3 {
+            for col in 0..3 {
+                moves.push((row, col, if (row + col) % 2 == 0 { Player::O } else { Player::X }));
+            }
+        }
+        let board = board_from(&moves);
+        assert_eq!(get_ai_choice(&board, Player::O, AiDifficulty::Hard), None);
+    }
+
+    #[test]
+    fn test_ai_choose_move_none_on_full_board() {
+        let mut moves = Vec::new();
+        for row in 0..3 {
+            for col in 0..3 {
+                moves.push((row, col, if (row + col) % 2 == 0 { Player::O } else { Player::X }));
+            }
+        }
+        let state = state_with_moves(Some(AiDifficulty::Hard), Player::O, &moves);
+        assert_eq!(ai_choose_move(&state), None);
+    }
+
+    #[test]
+    fn test_mcts_choose_move_takes_an_immediate_win() {
+        // X _ _ / X _ _ / O O _ -- X completes the left column at (2, 0) for the win
+        let board = board_from(&[
+            (0, 0, Player::X),
+            (1, 0, Player::X),
+            (2, 1, Player::O),
+            (1, 1, Player::O),
+        ]);
+        assert_eq!(mcts_choose_move(&board, Player::X, 200), Some((2, 0)));
+    }
+
+    #[test]
+    fn test_mcts_choose_move_blocks_an_imminent_loss() {
+        // O is one move from completing the top row; X must block at (0, 2).
+        let board = board_from(&[(0, 0, Player::O), (0, 1, Player::O), (1, 0, Player::X)]);
+        assert_eq!(mcts_choose_move(&board, Player::X, 300), Some((0, 2)));
+    }
+
+    #[test]
+    fn test_mcts_choose_move_none_on_full_board() {
+        let mut moves = Vec::new();
+        for row in 0..3 {
+            for col in 0..3 {
+                moves.push((row, col, if (row + col) % 2 == 0 { Player::O } else { Player::X }));
+            }
+        }
+        let board = board_from(&moves);
+        assert_eq!(mcts_choose_move(&board, Player::O, 100), None);
+    }
+
+    #[test]
+    fn test_mcts_iterations_for_scales_with_difficulty() {
+        assert!(mcts_iterations_for(AiDifficulty::Easy) < mcts_iterations_for(AiDifficulty::Normal));
+        assert!(mcts_iterations_for(AiDifficulty::Normal) < mcts_iterations_for(AiDifficulty::Hard));
+    }
+}
@@ -1,76 +1,79 @@
 use super::board::Board;
-use shared::{Cell, GameStatus, Player, WinningLine};
+use shared::{Cell, GameStatus, Player, WinType, WinningLine};
 
-/// Check if there's a winner on the board and return winner with winning line
+/// Every direction a line of `k` cells can run in: right, down, and both diagonals. Each cell
+/// is scanned as a potential line *start* in all four, so every orientation is covered without
+/// also needing the mirrored (left/up) directions.
+const DIRECTIONS: [(i8, i8); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+/// Check if there's a winner on the board and return winner with winning line. Scans every
+/// cell as a potential line start in all four directions, looking for `board.k()` consecutive
+/// cells held by the same player; generalizes past the classic 3x3/3-in-a-row case to any
+/// `Board::with_dimensions` size.
 #[allow(dead_code)] // Will be used by game state management
 pub fn check_winner(board: &Board) -> Option<(Player, WinningLine)> {
-    // Check rows
-    for row in 0..3 {
-        let positions = [(row, 0), (row, 1), (row, 2)];
-        if let Some(winner) = check_line(board, positions) {
-            return Some((
-                winner,
-                WinningLine {
-                    positions: positions.to_vec(),
-                },
-            ));
+    for row in 0..board.height() {
+        for col in 0..board.width() {
+            for (dr, dc) in DIRECTIONS {
+                let Some(positions) = line_from(board, row, col, dr, dc) else {
+                    continue;
+                };
+                if let Some(winner) = line_winner(board, &positions) {
+                    let win_type = Some(win_type_for(dr, dc));
+                    return Some((winner, WinningLine { positions, win_type }));
+                }
+            }
         }
     }
 
-    // Check columns
-    for col in 0..3 {
-        let positions = [(0, col), (1, col), (2, col)];
-        if let Some(winner) = check_line(board, positions) {
-            return Some((
-                winner,
-                WinningLine {
-                    positions: positions.to_vec(),
-                },
-            ));
-        }
-    }
+    None
+}
 
-    // Check diagonals
-    let positions = [(0, 0), (1, 1), (2, 2)];
-    if let Some(winner) = check_line(board, positions) {
-        return Some((
-            winner,
-            WinningLine {
-                positions: positions.to_vec(),
-            },
-        ));
+/// The `WinType` for a line scanned in direction `(dr, dc)`, one of the four `DIRECTIONS`.
+fn win_type_for(dr: i8, dc: i8) -> WinType {
+    match (dr, dc) {
+        (0, 1) => WinType::Horizontal,
+        (1, 0) => WinType::Vertical,
+        (1, 1) => WinType::DiagonalTopLeft,
+        (1, -1) => WinType::DiagonalTopRight,
+        _ => unreachable!("DIRECTIONS only contains the four scanned orientations"),
     }
+}
 
-    let positions = [(0, 2), (1, 1), (2, 0)];
-    if let Some(winner) = check_line(board, positions) {
-        return Some((
-            winner,
-            WinningLine {
-                positions: positions.to_vec(),
-            },
-        ));
+/// The `board.k()` positions starting at `(row, col)` and stepping by `(dr, dc)`, or `None` if
+/// that run would fall off the board.
+fn line_from(board: &Board, row: u8, col: u8, dr: i8, dc: i8) -> Option<Vec<(u8, u8)>> {
+    let mut positions = Vec::with_capacity(board.k() as usize);
+    let (mut r, mut c) = (row as i16, col as i16);
+
+    for _ in 0..board.k() {
+        if r < 0 || c < 0 || r >= board.height() as i16 || c >= board.width() as i16 {
+            return None;
+        }
+        positions.push((r as u8, c as u8));
+        r += dr as i16;
+        c += dc as i16;
     }
 
-    None
+    Some(positions)
 }
 
-/// Check if three cells contain the same player
-fn check_line(board: &Board, positions: [(u8, u8); 3]) -> Option<Player> {
+/// If every position holds the same player's mark, that player; otherwise `None`
+fn line_winner(board: &Board, positions: &[(u8, u8)]) -> Option<Player> {
     let cells: Vec<Cell> = positions
         .iter()
-        .filter_map(|(row, col)| board.get(*row, *col))
+        .map(|(row, col)| board.get(*row, *col).expect("line_from only yields in-bounds positions"))
         .collect();
 
-    if cells.len() != 3 {
-        return None;
-    }
+    let first = match cells[0] {
+        Cell::Occupied(p) => p,
+        Cell::Empty => return None,
+    };
 
-    // Check if all three cells are occupied by the same player
-    match (cells[0], cells[1], cells[2]) {
-        (Cell::Occupied(p1), Cell::Occupied(p2), Cell::Occupied(p3)) if p1 == p2 && p2 == p3 => {
-            Some(p1)
-        }
-        _ => None,
+    if cells.iter().all(|c| *c == Cell::Occupied(first)) {
+        Some(first)
+    } else {
+        None
     }
 }
 
@@ -105,6 +108,37 @@ mod tests {
         let (winner, line) = check_winner(&board).unwrap();
         assert_eq!(winner, Player::X);
         assert_eq!(line.positions, vec![(0, 0), (0, 1), (0, 2)]);
+        assert_eq!(line.win_type, Some(WinType::Horizontal));
+    }
+
+    #[test]
+    fn test_winner_win_type_vertical() {
+        let mut board = Board::new();
+        board.set(0, 0, Player::O).unwrap();
+        board.set(1, 0, Player::O).unwrap();
+        board.set(2, 0, Player::O).unwrap();
+        let (_, line) = check_winner(&board).unwrap();
+        assert_eq!(line.win_type, Some(WinType::Vertical));
+    }
+
+    #[test]
+    fn test_winner_win_type_diagonal_top_left() {
+        let mut board = Board::new();
+        board.set(0, 0, Player::X).unwrap();
+        board.set(1, 1, Player::X).unwrap();
+        board.set(2, 2, Player::X).unwrap();
+        let (_, line) = check_winner(&board).unwrap();
+        assert_eq!(line.win_type, Some(WinType::DiagonalTopLeft));
+    }
+
+    #[test]
+    fn test_winner_win_type_diagonal_top_right() {
+        let mut board = Board::new();
+        board.set(0, 2, Player::O).unwrap();
+        board.set(1, 1, Player::O).unwrap();
+        board.set(2, 0, Player::O).unwrap();
+        let (_, line) = check_winner(&board).unwrap();
+        assert_eq!(line.win_type, Some(WinType::DiagonalTopRight));
     }
 
     #[test]
@@ -186,6 +220,30 @@ mod tests {
         assert_eq!(check_winner(&board), None);
     }
 
+    #[test]
+    fn test_winner_four_in_a_row_on_5x5_board() {
+        let mut board = Board::with_dimensions(5, 5, 4);
+        board.set(2, 1, Player::X).unwrap();
+        board.set(2, 2, Player::X).unwrap();
+        board.set(2, 3, Player::X).unwrap();
+        board.set(2, 4, Player::X).unwrap();
+        let (winner, line) = check_winner(&board).unwrap();
+        assert_eq!(winner, Player::X);
+        assert_eq!(line.positions, vec![(2, 1), (2, 2), (2, 3), (2, 4)]);
+        assert_eq!(line.win_type, Some(WinType::Horizontal));
+    }
+
+    #[test]
+    fn test_near_miss_broken_by_blank_on_5x5_board() {
+        // Three in a row, a gap, then a fourth: no run of 4 consecutive X's exists.
+        let mut board = Board::with_dimensions(5, 5, 4);
+        board.set(2, 0, Player::X).unwrap();
+        board.set(2, 1, Player::X).unwrap();
+        board.set(2, 2, Player::X).unwrap();
+        board.set(2, 4, Player::X).unwrap();
+        assert_eq!(check_winner(&board), None);
+    }
+
     #[test]
     fn test_game_status_in_progress() {
         let mut board = Board::new();
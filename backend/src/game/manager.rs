@@ -1,16 +1,77 @@
 use super::board::Board;
+use super::bot::{Bot, Difficulty};
 use super::logic::get_game_status;
 use super::player::assign_players;
 use crate::db::repository::GameRepository;
-use shared::{Cell, GameError, GameState, GameStatus, Move, MoveSource};
-use std::time::{SystemTime, UNIX_EPOCH};
+use shared::{
+    Cell, EmoteEnum, GameError, GameState, GameStatus, Move, MoveSource, PairingStatusResponse,
+    Player,
+};
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
+/// A change a subscriber (UI or MCP client) can react to instead of polling `get_game_state` --
+/// the push-based counterpart of the per-player "opponent moved"/"game won" callbacks networked
+/// game servers expose. Fired by `make_move`/`add_taunt`/`restart_game` after they've already
+/// persisted, so a listener that immediately re-reads the game sees the state the event names.
+#[derive(Debug, Clone)]
+pub enum GameEvent {
+    MovePlayed { game_id: String, mov: Move },
+    TauntAdded { game_id: String, taunt: shared::ChatMessage },
+    StatusChanged {
+        game_id: String,
+        status: GameStatus,
+        winning_line: Option<shared::WinningLine>,
+    },
+    GameRestarted { game_id: String },
+}
+
+/// Per-seat authentication tokens for a multiplayer lobby session. A seat starts unclaimed
+/// (`None`) until `create_game`/`join_game` issues it a token.
+#[derive(Debug, Clone, Default)]
+struct SeatTokens {
+    x: Option<String>,
+    o: Option<String>,
+}
+
+/// A human-vs-human matchmaking request started by `GameManager::request_pairing`, waiting for
+/// a second player to join the lobby session at `game_id`. In-memory only, like `seat_tokens`:
+/// a restart drops pending pairings along with the lobby sessions they point at.
+#[derive(Debug, Clone)]
+struct PendingPairing {
+    game_id: String,
+    creator_id: String,
+    creator_seat: Player,
+    creator_token: String,
+    opponent_id: Option<String>,
+    opponent_seat: Option<Player>,
+    opponent_token: Option<String>,
+}
+
 /// Game state manager for coordinating game operations
 #[allow(dead_code)] // Will be used by MCP and API layers
 pub struct GameManager {
     current_game_id: Option<String>,
     repository: GameRepository,
+    /// Lobby sessions: game_id -> which seat tokens have been issued. In-memory only; a
+    /// restart drops open lobby sessions (the single-game AI path below doesn't use this).
+    seat_tokens: HashMap<String, SeatTokens>,
+    /// Monotonic state version per game_id, bumped on every mutating move/restart/taunt so
+    /// `wait_for_update` can tell a poller whether anything changed without re-sending the
+    /// whole board. In-memory only, like `seat_tokens`: it resets on restart, unlike the
+    /// mirror of the same counter persisted on `GameState::version` (see `bump_version`).
+    state_versions: HashMap<String, u64>,
+    /// Matchmaking requests started by `request_pairing`, keyed by pairing id, waiting to be
+    /// resolved by `pairing_status`. In-memory only, like `seat_tokens`.
+    pairings: HashMap<String, PendingPairing>,
+    /// The built-in AI opponent for the single implicit-game flow below. Two-human lobby
+    /// sessions (tracked in `seat_tokens`) never auto-play, since both seats are real players.
+    bot: Bot,
+    /// Listeners registered by `subscribe`, wanting every game's moves/taunts/status changes.
+    /// In-memory only, like `seat_tokens`: a restart drops them and callers must resubscribe.
+    event_subscribers: Vec<mpsc::Sender<GameEvent>>,
 }
 
 #[allow(dead_code)] // Will be used by MCP and API layers
@@ -21,9 +82,56 @@ impl GameManager {
         Ok(Self {
             current_game_id: None,
             repository,
+            seat_tokens: HashMap::new(),
+            state_versions: HashMap::new(),
+            pairings: HashMap::new(),
+            bot: Bot::new(Difficulty::from_env()),
+            event_subscribers: Vec::new(),
         })
     }
 
+    /// Register a new listener for this manager's game events (moves, taunts, status changes,
+    /// restarts) so a UI or MCP client can react immediately instead of polling `get_game_state`.
+    /// Dropping the returned `Receiver` unsubscribes; a send failure from a dropped receiver is
+    /// simply discarded the next time an event fires (see `broadcast_event`).
+    pub fn subscribe(&mut self) -> mpsc::Receiver<GameEvent> {
+        let (tx, rx) = mpsc::channel();
+        self.event_subscribers.push(tx);
+        rx
+    }
+
+    /// Fan an event out to every live subscriber, dropping any whose `Receiver` has since gone
+    /// out of scope instead of letting a dead subscriber pile up forever.
+    fn broadcast_event(&mut self, event: GameEvent) {
+        self.event_subscribers
+            .retain(|tx| tx.send(event.clone()).is_ok());
+    }
+
+    /// Current state version for a game (0 if it hasn't been mutated since this manager started)
+    pub fn state_version(&self, game_id: &str) -> u64 {
+        self.state_versions.get(game_id).copied().unwrap_or(0)
+    }
+
+    /// Bump and return a game's state version. Callers are responsible for also assigning the
+    /// result to the in-hand `GameState::version` and saving it, so the persisted row's copy
+    /// stays in lockstep with this process-local counter.
+    fn bump_version(&mut self, game_id: &str) -> u64 {
+        let version = self.state_versions.entry(game_id.to_string()).or_insert(0);
+        *version += 1;
+        *version
+    }
+
+    /// Resolve the game a single-session MCP/REST call should act on: the game with the given
+    /// ID if one was supplied, otherwise the implicit current game (creating it if needed).
+    /// This is the multi-session counterpart of the lobby's `seat_for_token`/token lookup, for
+    /// callers that just want to address a game by ID without the lobby's seat auth.
+    fn resolve_game(&mut self, game_id: Option<&str>) -> Result<GameState, GameError> {
+        match game_id {
+            Some(id) => self.repository.load_game(id),
+            None => self.get_or_create_game(),
+        }
+    }
+
     /// Get the current game, or create a new one if none exists
     pub fn get_or_create_game(&mut self) -> Result<GameState, GameError> {
         // First, check if there's a current game ID in the database (shared across processes)
@@ -45,13 +153,20 @@ impl GameManager {
         self.create_new_game()
     }
 
-    /// Create a new game
-    fn create_new_game(&mut self) -> Result<GameState, GameError> {
+    /// Build and persist a brand-new AI-opponent game under a fresh id, without registering
+    /// it as anyone's "current" game. Shared by `create_new_game` (which does register it)
+    /// and `new_game` (which deliberately doesn't).
+    fn build_new_game(&mut self) -> Result<GameState, GameError> {
         let game_id = Uuid::new_v4().to_string();
         let (human_player, ai_player, first_player) = assign_players();
 
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
         let game = GameState {
-            id: game_id.clone(),
+            id: game_id,
             board: [[Cell::Empty; 3]; 3],
             current_turn: first_player,
             human_player,
@@ -60,24 +175,71 @@ impl GameManager {
             move_history: vec![],
             taunts: vec![],
             winning_line: None,
+            turn_started_at: now,
+            turn_limit_secs: None,
+            ai_difficulty: None,
+            version: 0,
+            previous_game_id: None,
         };
 
         self.repository.save_game(&game)?;
+        Ok(game)
+    }
+
+    /// Start a brand-new AI-opponent game under a fresh id, without touching the implicit
+    /// "current game" pointer that `get_or_create_game`/`make_move` use. Lets a client run
+    /// several independent AI games concurrently, addressing each by id afterwards via
+    /// `make_move_in_game`/`get_game_state_in`, the way `create_game`'s two-human lobby flow
+    /// already does for human-vs-human sessions.
+    pub fn new_game(&mut self) -> Result<GameState, GameError> {
+        self.build_new_game()
+    }
+
+    /// Create a new game
+    fn create_new_game(&mut self) -> Result<GameState, GameError> {
+        let game = self.build_new_game()?;
+        let game_id = game.id.clone();
         self.repository.set_current_game_id(&game_id)?; // Register as current game (shared across processes)
         self.current_game_id = Some(game_id);
 
         Ok(game)
     }
 
-    /// Make a move on the board
+    /// Make a move on the board, then let the built-in AI auto-play its turn if one follows
     pub fn make_move(
         &mut self,
         row: u8,
         col: u8,
         source: MoveSource,
     ) -> Result<GameState, GameError> {
-        let mut game = self.get_or_create_game()?;
+        self.make_move_in_game(None, row, col, source)
+    }
+
+    /// Make a move in a specific session, or the implicit current game if `game_id` is `None`.
+    /// This is the multi-session counterpart of `make_move`, for callers juggling several
+    /// concurrent AI games (e.g. a client addressing games by ID instead of relying on the
+    /// one implicit "current game").
+    pub fn make_move_in_game(
+        &mut self,
+        game_id: Option<&str>,
+        row: u8,
+        col: u8,
+        source: MoveSource,
+    ) -> Result<GameState, GameError> {
+        let mut game = self.resolve_game(game_id)?;
+        self.apply_move(&mut game, row, col, source)?;
+        self.play_ai_turn_if_needed(&mut game)?;
+        Ok(game)
+    }
 
+    /// Validate and apply one move to `game`, persisting the move and the updated game state
+    fn apply_move(
+        &mut self,
+        game: &mut GameState,
+        row: u8,
+        col: u8,
+        source: MoveSource,
+    ) -> Result<(), GameError> {
         // Check if game is already over
         if game.status != GameStatus::InProgress {
             return Err(GameError::GameOver {
@@ -85,6 +247,28 @@ impl GameManager {
             });
         }
 
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        // If the current player's turn-clock has expired, forfeit the game to the opponent
+        // instead of accepting a late move.
+        if let Some(status) = game.check_timeout(now) {
+            let expired_player = game.current_turn;
+            game.status = status;
+            game.version = self.bump_version(&game.id);
+            self.repository.save_game(game)?;
+            self.broadcast_event(GameEvent::StatusChanged {
+                game_id: game.id.clone(),
+                status: game.status.clone(),
+                winning_line: game.winning_line.clone(),
+            });
+            return Err(GameError::TurnExpired {
+                player: expired_player,
+            });
+        }
+
         // Validate bounds
         if row >= 3 || col >= 3 {
             return Err(GameError::OutOfBounds { row, col });
@@ -99,17 +283,11 @@ impl GameManager {
         let current_player = game.current_turn;
         game.board[row as usize][col as usize] = Cell::Occupied(current_player);
 
-        // Record the move
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64;
-
         let mov = Move {
             player: current_player,
             row,
             col,
-            timestamp,
+            timestamp: now,
             source: Some(source),
         };
 
@@ -125,172 +303,1240 @@ impl GameManager {
         game.status = status;
         game.winning_line = winning_line;
 
-        // Switch turns if game is still in progress
+        // Switch turns if game is still in progress, resetting the turn-clock for whoever's up
         if game.status == GameStatus::InProgress {
             game.current_turn = current_player.opponent();
+            game.turn_started_at = now;
         }
 
         // Save updated game state
-        self.repository.save_game(&game)?;
+        game.version = self.bump_version(&game.id);
+        self.repository.save_game(game)?;
 
-        Ok(game)
-    }
+        self.broadcast_event(GameEvent::MovePlayed {
+            game_id: game.id.clone(),
+            mov,
+        });
+        if game.status != GameStatus::InProgress {
+            self.broadcast_event(GameEvent::StatusChanged {
+                game_id: game.id.clone(),
+                status: game.status.clone(),
+                winning_line: game.winning_line.clone(),
+            });
+        }
 
-    /// Restart the game with a new board
-    pub fn restart_game(&mut self) -> Result<GameState, GameError> {
-        self.current_game_id = None;
-        self.create_new_game()
+        // Fold a finished single-AI-opponent game into the cross-game leaderboard. Lobby
+        // sessions are skipped: both seats are real players there, so the human/ai role split
+        // `record_result` assumes doesn't apply.
+        if game.status != GameStatus::InProgress && !self.seat_tokens.contains_key(&game.id) {
+            self.repository
+                .record_result(&game.id, self.bot.difficulty.as_str())?;
+        }
+
+        Ok(())
     }
 
-    /// Add a taunt message
-    pub fn add_taunt(&mut self, message: String, source: MoveSource) -> Result<(), GameError> {
-        let game = self.get_or_create_game()?;
-        let source_str = match source {
-            MoveSource::UI => Some("UI"),
-            MoveSource::MCP => Some("MCP"),
+    /// If `game` is a single implicit-game session (not a two-human lobby one) and it's the
+    /// AI's turn, have the built-in bot play it.
+    fn play_ai_turn_if_needed(&mut self, game: &mut GameState) -> Result<(), GameError> {
+        if self.seat_tokens.contains_key(&game.id) {
+            return Ok(());
+        }
+        if game.status != GameStatus::InProgress || game.current_turn != game.ai_player {
+            return Ok(());
+        }
+
+        // A game-level `ai_difficulty` takes priority over the legacy `Bot` auto-player.
+        let chosen_move = if game.ai_difficulty.is_some() {
+            super::bot::ai_choose_move(game)
+        } else {
+            let mut board = Board::new();
+            for m in &game.move_history {
+                board.set(m.row, m.col, m.player).ok();
+            }
+            self.bot.choose_move(&board, game.ai_player)
         };
-        self.repository.save_taunt(&game.id, &message, source_str)?;
+
+        if let Some((row, col)) = chosen_move {
+            self.apply_move(game, row, col, MoveSource::MCP)?;
+        }
+
         Ok(())
     }
 
-    /// Get the current game state
-    pub fn get_game_state(&mut self) -> Result<GameState, GameError> {
-        self.get_or_create_game()
-    }
-}
+    /// Compute the move Monte Carlo Tree Search would pick for the current game's AI opponent,
+    /// without applying it — unlike `play_ai_turn_if_needed`'s auto-play path, this lets a
+    /// caller preview or drive the AI's move itself. Search strength scales with the game's
+    /// `ai_difficulty` (see `bot::mcts_iterations_for`), defaulting to `Normal` if unset.
+    /// Errors with `WrongTurn` if it isn't `ai_player`'s turn, or `GameOver` if the game has
+    /// already ended.
+    pub fn compute_ai_move(&mut self) -> Result<(u8, u8), GameError> {
+        let game = self.resolve_game(None)?;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use shared::MoveSource;
+        if game.status != GameStatus::InProgress {
+            return Err(GameError::GameOver { status: game.status });
+        }
+        if game.current_turn != game.ai_player {
+            return Err(GameError::WrongTurn { player: game.ai_player });
+        }
 
-    fn create_test_manager() -> GameManager {
-        let db_path = format!("/tmp/test-game-{}.db", Uuid::new_v4());
-        GameManager::new(&db_path).unwrap()
+        let mut board = Board::new();
+        for m in &game.move_history {
+            board.set(m.row, m.col, m.player).ok();
+        }
+
+        let difficulty = game.ai_difficulty.unwrap_or(shared::AiDifficulty::Normal);
+        let iterations = super::bot::mcts_iterations_for(difficulty);
+        super::bot::mcts_choose_move(&board, game.ai_player, iterations).ok_or_else(|| {
+            GameError::InternalError {
+                message: "MCTS found no legal move on a non-terminal board".to_string(),
+            }
+        })
     }
 
-    #[test]
-    fn test_create_new_game() {
-        let mut manager = create_test_manager();
-        let game = manager.get_or_create_game().unwrap();
+    /// Set (or clear, via `None`) a game's per-turn time budget in seconds. A move attempted
+    /// after the budget elapses forfeits the game to the opponent (see `GameState::check_timeout`).
+    pub fn set_turn_limit_in_game(
+        &mut self,
+        game_id: Option<&str>,
+        turn_limit_secs: Option<u32>,
+    ) -> Result<GameState, GameError> {
+        let mut game = self.resolve_game(game_id)?;
+        game.turn_limit_secs = turn_limit_secs;
+        game.version = self.bump_version(&game.id);
+        self.repository.save_game(&game)?;
+        Ok(game)
+    }
 
-        assert!(!game.id.is_empty());
-        assert_eq!(game.status, GameStatus::InProgress);
-        assert_eq!(game.move_history.len(), 0);
-        assert_ne!(game.human_player, game.ai_player);
+    /// Proactively resolve the implicit current game's turn-clock the same way `apply_move`
+    /// would, without needing an (otherwise rejected) move attempt to trigger it.
+    pub fn check_turn_timeout(&mut self) -> Result<GameState, GameError> {
+        self.check_turn_timeout_in(None)
     }
 
-    #[test]
-    fn test_get_existing_game() {
-        let mut manager = create_test_manager();
-        let game1 = manager.get_or_create_game().unwrap();
-        let game1_id = game1.id.clone();
+    /// The `check_turn_timeout` counterpart for a specific session, or the implicit current game
+    /// if `game_id` is `None`. If the current player's turn-clock has expired, forfeits the game
+    /// to the opponent and persists it (see `GameState::check_timeout`); otherwise returns the
+    /// game unchanged. A no-op if it isn't `InProgress` or has no turn limit set.
+    pub fn check_turn_timeout_in(&mut self, game_id: Option<&str>) -> Result<GameState, GameError> {
+        let mut game = self.resolve_game(game_id)?;
 
-        let game2 = manager.get_or_create_game().unwrap();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
 
-        assert_eq!(game1_id, game2.id);
+        if let Some(status) = game.check_timeout(now) {
+            game.status = status;
+            game.version = self.bump_version(&game.id);
+            self.repository.save_game(&game)?;
+        }
+
+        Ok(game)
     }
 
-    #[test]
-    fn test_make_valid_move() {
-        let mut manager = create_test_manager();
-        let game = manager.make_move(0, 0, MoveSource::UI).unwrap();
+    /// Set (or clear, via `None`) a game's `AiDifficulty`, which controls move selection for
+    /// `play_ai_turn_if_needed`: when set, it takes priority over the legacy `Bot` auto-player.
+    pub fn set_ai_difficulty_in_game(
+        &mut self,
+        game_id: Option<&str>,
+        ai_difficulty: Option<shared::AiDifficulty>,
+    ) -> Result<GameState, GameError> {
+        let mut game = self.resolve_game(game_id)?;
+        game.ai_difficulty = ai_difficulty;
+        game.version = self.bump_version(&game.id);
+        self.repository.save_game(&game)?;
+        Ok(game)
+    }
 
-        assert_eq!(game.move_history.len(), 1);
-        assert_eq!(game.move_history[0].row, 0);
-        assert_eq!(game.move_history[0].col, 0);
-        assert_eq!(
-            game.board[0][0],
-            Cell::Occupied(game.move_history[0].player)
-        );
+    /// Forfeit the implicit current game to the AI. A no-op (returning the game unchanged) if
+    /// it isn't `InProgress`.
+    pub fn concede_game(&mut self) -> Result<GameState, GameError> {
+        self.concede_game_in(None)
     }
 
-    #[test]
-    fn test_make_move_out_of_bounds() {
-        let mut manager = create_test_manager();
-        let result = manager.make_move(3, 0, MoveSource::UI);
+    /// Forfeit a specific session to its AI player, or the implicit current game if `game_id`
+    /// is `None`. A no-op (returning the game unchanged) if it isn't `InProgress`.
+    pub fn concede_game_in(&mut self, game_id: Option<&str>) -> Result<GameState, GameError> {
+        let mut game = self.resolve_game(game_id)?;
+        if game.status != GameStatus::InProgress {
+            return Ok(game);
+        }
 
-        assert!(matches!(result, Err(GameError::OutOfBounds { .. })));
-    }
+        game.status = GameStatus::Won(game.ai_player);
+        game.version = self.bump_version(&game.id);
+        self.repository.save_game(&game)?;
 
-    #[test]
-    fn test_make_move_cell_occupied() {
-        let mut manager = create_test_manager();
-        manager.make_move(1, 1, MoveSource::UI).unwrap();
-        let result = manager.make_move(1, 1, MoveSource::UI);
+        if !self.seat_tokens.contains_key(&game.id) {
+            self.repository
+                .record_result(&game.id, self.bot.difficulty.as_str())?;
+        }
 
-        assert!(matches!(result, Err(GameError::CellOccupied { .. })));
+        Ok(game)
     }
 
-    #[test]
-    fn test_turn_switching() {
-        let mut manager = create_test_manager();
-        let game1 = manager.make_move(0, 0, MoveSource::UI).unwrap();
-        let first_player = game1.move_history[0].player;
+    /// Restart the game with a new board
+    pub fn restart_game(&mut self) -> Result<GameState, GameError> {
+        self.restart_game_with_difficulty(None)
+    }
 
-        let game2 = manager.make_move(0, 1, MoveSource::UI).unwrap();
-        assert_eq!(game2.current_turn, first_player);
-        assert_eq!(game2.move_history[1].player, first_player.opponent());
+    /// Restart the game with a new board, optionally switching the built-in AI's difficulty
+    pub fn restart_game_with_difficulty(
+        &mut self,
+        difficulty: Option<Difficulty>,
+    ) -> Result<GameState, GameError> {
+        if let Some(difficulty) = difficulty {
+            self.bot.difficulty = difficulty;
+        }
+
+        self.current_game_id = None;
+        let mut game = self.create_new_game()?;
+        self.play_ai_turn_if_needed(&mut game)?;
+        game.version = self.bump_version(&game.id);
+        self.repository.save_game(&game)?;
+        self.broadcast_event(GameEvent::GameRestarted {
+            game_id: game.id.clone(),
+        });
+        Ok(game)
     }
 
-    #[test]
-    fn test_restart_game() {
-        let mut manager = create_test_manager();
-        manager.make_move(0, 0, MoveSource::UI).unwrap();
-        let game1_id = manager.current_game_id.clone().unwrap();
+    /// Start a rematch of the current game: unlike `restart_game`, which re-randomizes the
+    /// human/AI seat assignment, this keeps the same `human_player`/`ai_player` mapping and
+    /// just alternates who moves first, the standard "play again" experience. Only valid once
+    /// the current game has actually finished; errors with `GameError::GameStillInProgress`
+    /// otherwise. The new game's `previous_game_id` links back to the one it's a rematch of.
+    pub fn request_rematch(&mut self) -> Result<GameState, GameError> {
+        let previous = self.resolve_game(None)?;
+        if previous.status == GameStatus::InProgress {
+            return Err(GameError::GameStillInProgress);
+        }
 
-        let new_game = manager.restart_game().unwrap();
+        let previous_first_player = previous
+            .move_history
+            .first()
+            .map(|m| m.player)
+            .unwrap_or(previous.human_player);
 
-        assert_ne!(new_game.id, game1_id);
-        assert_eq!(new_game.move_history.len(), 0);
-        assert_eq!(new_game.status, GameStatus::InProgress);
-    }
+        let game_id = Uuid::new_v4().to_string();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
 
-    #[test]
-    fn test_add_taunt() {
-        let mut manager = create_test_manager();
-        manager.get_or_create_game().unwrap();
+        let mut game = GameState {
+            id: game_id.clone(),
+            board: [[Cell::Empty; 3]; 3],
+            current_turn: previous_first_player.opponent(),
+            human_player: previous.human_player,
+            ai_player: previous.ai_player,
+            status: GameStatus::InProgress,
+            move_history: vec![],
+            taunts: vec![],
+            winning_line: None,
+            turn_started_at: now,
+            turn_limit_secs: None,
+            ai_difficulty: None,
+            version: 0,
+            previous_game_id: Some(previous.id),
+        };
 
-        let result =
-            manager.add_taunt("You call that a move?".to_string(), shared::MoveSource::MCP);
-        assert!(result.is_ok());
+        self.repository.save_game(&game)?;
+        self.repository.set_current_game_id(&game_id)?;
+        self.current_game_id = Some(game_id);
 
-        // Verify taunt is persisted
-        let game = manager.get_game_state().unwrap();
-        assert_eq!(game.taunts.len(), 1);
-        assert_eq!(game.taunts[0].message, "You call that a move?");
-        assert_eq!(game.taunts[0].source, Some(shared::MoveSource::MCP));
+        self.play_ai_turn_if_needed(&mut game)?;
+        game.version = self.bump_version(&game.id);
+        self.repository.save_game(&game)?;
+        Ok(game)
     }
 
-    #[test]
-    fn test_game_state_persistence() {
-        let mut manager = create_test_manager();
+    /// Two-tier stale-game sweep, run on a timer rather than on every request -- see
+    /// `api::server::start_server`'s cleanup task and `McpServer::maybe_cleanup_stale_games` for
+    /// the two production call sites. First, any `InProgress` game whose turn has sat untouched
+    /// past `turn_timeout` is forfeited to `GameStatus::Abandoned` (nobody came back to finish
+    /// it). Then, any game at all -- freshly abandoned or not -- untouched past the longer
+    /// `idle` is deleted outright, since nobody's coming back for a board that stale either way.
+    /// Returns `(forfeited_count, deleted_count)`.
+    ///
+    /// The actual SQL lives in `db::repository::cleanup_stale_games` (via
+    /// `GameRepository::cleanup_stale_games`); this method's own job is figuring out which ids
+    /// that sweep is about to delete *before* it runs, so their in-memory `seat_tokens`/
+    /// `state_versions`/`current_game_id` bookkeeping -- which a repository-only delete can't
+    /// see -- gets cleared too.
+    pub fn cleanup_stale_games(
+        &mut self,
+        turn_timeout: Duration,
+        idle: Duration,
+    ) -> Result<(usize, usize), GameError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let cutoff = now - idle.as_secs() as i64;
 
-        // Make moves and get game state
-        manager.make_move(0, 0, MoveSource::UI).unwrap();
-        manager.make_move(1, 1, MoveSource::UI).unwrap();
-        let game_id = manager.current_game_id.clone().unwrap();
+        let doomed_ids: Vec<String> = self
+            .repository
+            .list_game_ids_with_last_activity()?
+            .into_iter()
+            .filter(|(_, last_activity)| *last_activity < cutoff)
+            .map(|(game_id, _)| game_id)
+            .collect();
 
-        // Get game state again - should have persistent moves
-        let game = manager.get_game_state().unwrap();
-        assert_eq!(game.id, game_id);
-        assert_eq!(game.move_history.len(), 2);
+        let (forfeited, deleted) = self
+            .repository
+            .cleanup_stale_games(turn_timeout.as_secs() as i64, idle.as_secs() as i64)?;
 
-        // Verify data is in database by loading directly
-        let loaded_game = manager.repository.load_game(&game_id).unwrap();
-        assert_eq!(loaded_game.move_history.len(), 2);
+        for game_id in &doomed_ids {
+            self.seat_tokens.remove(game_id);
+            self.state_versions.remove(game_id);
+            if self.current_game_id.as_deref() == Some(game_id.as_str()) {
+                self.current_game_id = None;
+            }
+        }
+
+        Ok((forfeited, deleted))
     }
 
-    #[test]
-    fn test_game_over_prevents_moves() {
-        let mut manager = create_test_manager();
+    /// Add a taunt message
+    pub fn add_taunt(&mut self, message: String, source: MoveSource) -> Result<(), GameError> {
+        self.add_taunt_in_game(None, message, source)
+    }
 
-        // Create a winning condition for X
-        // X X X
-        // O O .
-        // . . .
+    /// Add a taunt to a specific session, or the implicit current game if `game_id` is `None`.
+    pub fn add_taunt_in_game(
+        &mut self,
+        game_id: Option<&str>,
+        message: String,
+        source: MoveSource,
+    ) -> Result<(), GameError> {
+        let mut game = self.resolve_game(game_id)?;
+        let source_str = match source {
+            MoveSource::UI => Some("UI"),
+            MoveSource::MCP => Some("MCP"),
+            MoveSource::AI => Some("AI"),
+        };
+        self.repository.save_taunt(&game.id, &message, source_str)?;
+        game.version = self.bump_version(&game.id);
+        self.repository.save_game(&game)?;
 
-        // Simulate the moves directly to create a win
+        // `save_taunt` doesn't hand back the row it just inserted (it only assigns `id` on
+        // write), so fetch the single most recent taunt back out to broadcast the real thing.
+        if let Some(taunt) = self.repository.get_taunts(&game.id, None, None, 1)?.pop() {
+            self.broadcast_event(GameEvent::TauntAdded {
+                game_id: game.id.clone(),
+                taunt,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Send a predefined quick emote (see `EmoteEnum`) to a specific session, or the implicit
+    /// current game if `game_id` is `None`. Stored as an ordinary taunt `ChatMessage` — the
+    /// emoji is itself a body the MCP agent can read back via `get_taunts`/`get_game_history`.
+    pub fn add_emote_in_game(
+        &mut self,
+        game_id: Option<&str>,
+        emote: EmoteEnum,
+        source: MoveSource,
+    ) -> Result<(), GameError> {
+        self.add_taunt_in_game(game_id, emote.as_str().to_string(), source)
+    }
+
+    /// A bounded, chronologically-ordered slice of a session's chat/taunt history, for
+    /// CHATHISTORY-style scroll-back instead of loading it all via `get_game_state`. `before`/
+    /// `after` page around a message id; omitting both returns the most recent `limit` messages.
+    pub fn get_taunts(
+        &mut self,
+        game_id: Option<&str>,
+        before: Option<u64>,
+        after: Option<u64>,
+        limit: u32,
+    ) -> Result<Vec<shared::ChatMessage>, GameError> {
+        let game = self.resolve_game(game_id)?;
+        self.repository.get_taunts(&game.id, before, after, limit)
+    }
+
+    /// The most recent `limit` taunts that are quick emotes (see `EmoteEnum::from_body`),
+    /// free-text taunts filtered out, so a UI can render reaction icons without doing that
+    /// filtering itself. Since the filter runs after taking the most recent `limit` messages,
+    /// a chatty free-text run can leave this returning fewer than `limit` emotes even if older
+    /// ones exist further back.
+    pub fn get_recent_emotes_in(
+        &mut self,
+        game_id: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<(EmoteEnum, shared::ChatMessage)>, GameError> {
+        let taunts = self.get_taunts(game_id, None, None, limit)?;
+        Ok(taunts
+            .into_iter()
+            .filter_map(|taunt| EmoteEnum::from_body(&taunt.body).map(|emote| (emote, taunt)))
+            .collect())
+    }
+
+    /// Get the current game state
+    pub fn get_game_state(&mut self) -> Result<GameState, GameError> {
+        self.get_game_state_in(None)
+    }
+
+    /// Get a specific session's game state, or the implicit current game if `game_id` is `None`.
+    pub fn get_game_state_in(&mut self, game_id: Option<&str>) -> Result<GameState, GameError> {
+        self.resolve_game(game_id)
+    }
+
+    /// Create a brand-new multiplayer lobby session, independent of the single implicit
+    /// "current game" the legacy API/MCP tools use. Returns the game, the seat the creator
+    /// controls, and a token for that seat; the other seat stays open until `join_game` claims
+    /// it.
+    pub fn create_game(&mut self) -> Result<(GameState, Player, String), GameError> {
+        let game_id = Uuid::new_v4().to_string();
+        let (human_player, ai_player, first_player) = assign_players();
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let game = GameState {
+            id: game_id.clone(),
+            board: [[Cell::Empty; 3]; 3],
+            current_turn: first_player,
+            human_player,
+            ai_player,
+            status: GameStatus::InProgress,
+            move_history: vec![],
+            taunts: vec![],
+            winning_line: None,
+            turn_started_at: now,
+            turn_limit_secs: None,
+            ai_difficulty: None,
+            version: 0,
+            previous_game_id: None,
+        };
+
+        self.repository.save_game(&game)?;
+
+        let token = Uuid::new_v4().to_string();
+        let mut tokens = SeatTokens::default();
+        match human_player {
+            Player::X => tokens.x = Some(token.clone()),
+            Player::O => tokens.o = Some(token.clone()),
+        }
+        self.seat_tokens.insert(game_id, tokens);
+
+        Ok((game, human_player, token))
+    }
+
+    /// Claim the one free seat in a lobby session, returning the seat and its fresh token.
+    pub fn join_game(&mut self, game_id: &str) -> Result<(GameState, Player, String), GameError> {
+        let game = self.repository.load_game(game_id)?;
+        let tokens = self.seat_tokens.entry(game_id.to_string()).or_default();
+
+        let free_seat = match (&tokens.x, &tokens.o) {
+            (None, _) => Player::X,
+            (_, None) => Player::O,
+            (Some(_), Some(_)) => {
+                return Err(GameError::InternalError {
+                    message: "Game already has two players".to_string(),
+                });
+            }
+        };
+
+        let token = Uuid::new_v4().to_string();
+        match free_seat {
+            Player::X => tokens.x = Some(token.clone()),
+            Player::O => tokens.o = Some(token.clone()),
+        }
+
+        Ok((game, free_seat, token))
+    }
+
+    /// List every known game, regardless of whether it was started via `new_game`,
+    /// `create_game`, or the implicit current game — they all live in the same `games` table.
+    pub fn list_games(&self) -> Result<Vec<GameState>, GameError> {
+        self.repository.list_games()
+    }
+
+    /// Request a human-vs-human match for `player_id`: join the oldest pairing still waiting
+    /// for an opponent other than this player, or else open a fresh lobby session and wait.
+    /// Returns a pairing id for `pairing_status` to poll.
+    pub fn request_pairing(&mut self, player_id: &str) -> Result<String, GameError> {
+        let waiting = self
+            .pairings
+            .iter()
+            .find(|(_, pending)| pending.opponent_id.is_none() && pending.creator_id != player_id)
+            .map(|(pairing_id, pending)| (pairing_id.clone(), pending.game_id.clone()));
+
+        if let Some((pairing_id, game_id)) = waiting {
+            let (_, seat, token) = self.join_game(&game_id)?;
+            let pending = self
+                .pairings
+                .get_mut(&pairing_id)
+                .expect("pairing just looked up above");
+            pending.opponent_id = Some(player_id.to_string());
+            pending.opponent_seat = Some(seat);
+            pending.opponent_token = Some(token);
+            return Ok(pairing_id);
+        }
+
+        let (game, seat, token) = self.create_game()?;
+        let pairing_id = Uuid::new_v4().to_string();
+        self.pairings.insert(
+            pairing_id.clone(),
+            PendingPairing {
+                game_id: game.id,
+                creator_id: player_id.to_string(),
+                creator_seat: seat,
+                creator_token: token,
+                opponent_id: None,
+                opponent_seat: None,
+                opponent_token: None,
+            },
+        );
+        Ok(pairing_id)
+    }
+
+    /// Poll a pairing started by `request_pairing` on behalf of `player_id`. `None` if
+    /// `pairing_id` is unknown to this process or `player_id` isn't part of it. Otherwise
+    /// `status: "Waiting"` until an opponent has joined, then `"Matched"` with the lobby
+    /// session's id and `player_id`'s own seat and token.
+    pub fn pairing_status(
+        &self,
+        pairing_id: &str,
+        player_id: &str,
+    ) -> Option<PairingStatusResponse> {
+        let pending = self.pairings.get(pairing_id)?;
+
+        if pending.creator_id == player_id {
+            return Some(match &pending.opponent_id {
+                Some(_) => PairingStatusResponse {
+                    status: "Matched".to_string(),
+                    game_id: Some(pending.game_id.clone()),
+                    seat: Some(pending.creator_seat),
+                    token: Some(pending.creator_token.clone()),
+                },
+                None => PairingStatusResponse {
+                    status: "Waiting".to_string(),
+                    game_id: None,
+                    seat: None,
+                    token: None,
+                },
+            });
+        }
+
+        if pending.opponent_id.as_deref() == Some(player_id) {
+            return Some(PairingStatusResponse {
+                status: "Matched".to_string(),
+                game_id: Some(pending.game_id.clone()),
+                seat: pending.opponent_seat,
+                token: pending.opponent_token.clone(),
+            });
+        }
+
+        None
+    }
+
+    /// Forfeit a human-vs-human match to the other seat, identifying the leaving player by
+    /// their seat token. Used when a player disconnects mid-game (see the frontend's
+    /// `beforeunload` handler) as well as an explicit "leave match" action. A no-op (returning
+    /// the game unchanged) if it isn't `InProgress`.
+    pub fn leave_game(&mut self, game_id: &str, token: &str) -> Result<GameState, GameError> {
+        let leaving_seat = self.seat_for_token(game_id, token)?;
+        let mut game = self.repository.load_game(game_id)?;
+        if game.status != GameStatus::InProgress {
+            return Ok(game);
+        }
+
+        game.status = GameStatus::Won(leaving_seat.opponent());
+        game.version = self.bump_version(&game.id);
+        self.repository.save_game(&game)?;
+        Ok(game)
+    }
+
+    /// The top `limit` human/ai tallies by wins, with each entry's win rate, for the
+    /// cross-game leaderboard
+    pub fn get_leaderboard(&self, limit: u32) -> Result<Vec<shared::LeaderboardEntry>, GameError> {
+        self.repository.get_leaderboard(limit)
+    }
+
+    /// Wipe every recorded leaderboard tally, for starting a fresh competition. Guarded by the
+    /// same role check as `restart_game` at the MCP/HTTP layer.
+    pub fn reset_leaderboard(&self) -> Result<(), GameError> {
+        self.repository.reset_leaderboard()
+    }
+
+    /// Persist any buffered writes (a no-op unless write-behind is enabled). Called on graceful
+    /// shutdown so a pending move isn't lost to the write-behind lag window.
+    pub fn flush(&self) -> Result<(), GameError> {
+        self.repository.flush()
+    }
+
+    /// Resolve which seat a token controls in the given lobby session
+    fn seat_for_token(&self, game_id: &str, token: &str) -> Result<Player, GameError> {
+        let tokens = self.seat_tokens.get(game_id).ok_or(GameError::GameNotFound)?;
+        if tokens.x.as_deref() == Some(token) {
+            Ok(Player::X)
+        } else if tokens.o.as_deref() == Some(token) {
+            Ok(Player::O)
+        } else {
+            Err(GameError::InternalError {
+                message: "Invalid or unknown session token".to_string(),
+            })
+        }
+    }
+
+    /// Read a lobby session's state, after checking the token belongs to one of its seats
+    pub fn view_game(&mut self, game_id: &str, token: &str) -> Result<GameState, GameError> {
+        self.seat_for_token(game_id, token)?;
+        self.repository.load_game(game_id)
+    }
+
+    /// Make a move in a lobby session, after validating the token controls the seat whose turn
+    /// it currently is
+    pub fn make_move_authenticated(
+        &mut self,
+        game_id: &str,
+        token: &str,
+        row: u8,
+        col: u8,
+        source: MoveSource,
+    ) -> Result<GameState, GameError> {
+        let seat = self.seat_for_token(game_id, token)?;
+        let game = self.repository.load_game(game_id)?;
+        if game.current_turn != seat {
+            return Err(GameError::WrongTurn { player: seat });
+        }
+
+        self.current_game_id = Some(game_id.to_string());
+        self.make_move(row, col, source)
+    }
+
+    /// Add a taunt to a lobby session, after checking the token belongs to one of its seats
+    pub fn add_taunt_authenticated(
+        &mut self,
+        game_id: &str,
+        token: &str,
+        message: String,
+        source: MoveSource,
+    ) -> Result<(), GameError> {
+        self.seat_for_token(game_id, token)?;
+        let source_str = match source {
+            MoveSource::UI => Some("UI"),
+            MoveSource::MCP => Some("MCP"),
+            MoveSource::AI => Some("AI"),
+        };
+        self.repository.save_taunt(game_id, &message, source_str)?;
+        let version = self.bump_version(game_id);
+        let mut game = self.repository.load_game(game_id)?;
+        game.version = version;
+        self.repository.save_game(&game)?;
+        Ok(())
+    }
+
+    /// Restart a lobby session's board, after checking the token belongs to one of its seats
+    pub fn restart_game_authenticated(
+        &mut self,
+        game_id: &str,
+        token: &str,
+    ) -> Result<GameState, GameError> {
+        self.seat_for_token(game_id, token)?;
+        self.current_game_id = Some(game_id.to_string());
+        self.restart_game()
+    }
+
+    /// Wait (bounded by `timeout_ms`) for a game's state version to advance past
+    /// `since_version`. Returns immediately with `changed = true` if it already has; otherwise
+    /// polls until a mutation bumps the version or the timeout elapses, in which case it returns
+    /// the unchanged state with `changed = false` so callers can resume polling.
+    pub fn wait_for_update(
+        &mut self,
+        game_id: &str,
+        since_version: u64,
+        timeout_ms: u64,
+    ) -> Result<(GameState, u64, bool), GameError> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(25);
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+
+        loop {
+            let current = self.state_version(game_id);
+            if current > since_version {
+                let game = self.repository.load_game(game_id)?;
+                return Ok((game, current, true));
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                let game = self.repository.load_game(game_id)?;
+                return Ok((game, current, false));
+            }
+
+            std::thread::sleep(POLL_INTERVAL.min(remaining));
+        }
+    }
+
+    /// Immediate, non-blocking check of whether `game_id`'s persisted `updated_at` has advanced
+    /// past `since_updated_at` — the same short-circuit as `wait_for_update`/`get_state_if_changed`,
+    /// but for a poller that kept the database's raw timestamp from its last fetch instead of
+    /// `GameState::version`. Returns the current `updated_at` alongside the state, which is only
+    /// `Some` when it changed.
+    pub fn get_state_if_updated(
+        &mut self,
+        game_id: &str,
+        since_updated_at: i64,
+    ) -> Result<(Option<GameState>, i64), GameError> {
+        let updated_at = self
+            .repository
+            .get_game_version(game_id)?
+            .ok_or(GameError::GameNotFound)?;
+        let game = self.repository.load_game_if_changed(game_id, since_updated_at)?;
+        Ok((game, updated_at))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::MoveSource;
+
+    fn create_test_manager() -> GameManager {
+        let db_path = format!("/tmp/test-game-{}.db", Uuid::new_v4());
+        GameManager::new(&db_path).unwrap()
+    }
+
+    #[test]
+    fn test_create_new_game() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+
+        assert!(!game.id.is_empty());
+        assert_eq!(game.status, GameStatus::InProgress);
+        assert_eq!(game.move_history.len(), 0);
+        assert_ne!(game.human_player, game.ai_player);
+    }
+
+    #[test]
+    fn test_new_game_does_not_clobber_the_current_game_pointer() {
+        let mut manager = create_test_manager();
+        let current = manager.get_or_create_game().unwrap();
+
+        let extra = manager.new_game().unwrap();
+
+        assert!(!extra.id.is_empty());
+        assert_ne!(extra.id, current.id);
+        assert_eq!(extra.status, GameStatus::InProgress);
+        // The implicit "current game" is unaffected by creating an additional explicit-id game.
+        assert_eq!(manager.get_or_create_game().unwrap().id, current.id);
+    }
+
+    #[test]
+    fn test_new_game_is_addressable_via_the_explicit_game_id_methods() {
+        let mut manager = create_test_manager();
+        let game = manager.new_game().unwrap();
+
+        assert_eq!(manager.get_game_state_in(Some(&game.id)).unwrap().id, game.id);
+        manager
+            .make_move_in_game(Some(&game.id), 0, 0, MoveSource::MCP)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_get_existing_game() {
+        let mut manager = create_test_manager();
+        let game1 = manager.get_or_create_game().unwrap();
+        let game1_id = game1.id.clone();
+
+        let game2 = manager.get_or_create_game().unwrap();
+
+        assert_eq!(game1_id, game2.id);
+    }
+
+    #[test]
+    fn test_make_valid_move() {
+        let mut manager = create_test_manager();
+        let game = manager.make_move(0, 0, MoveSource::UI).unwrap();
+
+        assert_eq!(game.move_history.len(), 1);
+        assert_eq!(game.move_history[0].row, 0);
+        assert_eq!(game.move_history[0].col, 0);
+        assert_eq!(
+            game.board[0][0],
+            Cell::Occupied(game.move_history[0].player)
+        );
+    }
+
+    #[test]
+    fn test_make_move_out_of_bounds() {
+        let mut manager = create_test_manager();
+        let result = manager.make_move(3, 0, MoveSource::UI);
+
+        assert!(matches!(result, Err(GameError::OutOfBounds { .. })));
+    }
+
+    #[test]
+    fn test_make_move_cell_occupied() {
+        let mut manager = create_test_manager();
+        manager.make_move(1, 1, MoveSource::UI).unwrap();
+        let result = manager.make_move(1, 1, MoveSource::UI);
+
+        assert!(matches!(result, Err(GameError::CellOccupied { .. })));
+    }
+
+    #[test]
+    fn test_set_turn_limit_in_game() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+
+        let updated = manager
+            .set_turn_limit_in_game(Some(&game.id), Some(30))
+            .unwrap();
+
+        assert_eq!(updated.turn_limit_secs, Some(30));
+    }
+
+    #[test]
+    fn test_make_move_rejects_expired_turn() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        manager
+            .set_turn_limit_in_game(Some(&game.id), Some(1))
+            .unwrap();
+
+        // Back-date the turn clock so it's already expired.
+        let mut expired = manager.get_game_state_in(Some(&game.id)).unwrap();
+        expired.turn_started_at -= 1_000;
+        manager.repository.save_game(&expired).unwrap();
+
+        let expired_player = expired.current_turn;
+        let result = manager.make_move(0, 0, MoveSource::UI);
+
+        assert!(matches!(
+            result,
+            Err(GameError::TurnExpired { player }) if player == expired_player
+        ));
+
+        let game = manager.get_game_state_in(Some(&game.id)).unwrap();
+        assert_eq!(game.status, GameStatus::Won(expired_player.opponent()));
+    }
+
+    #[test]
+    fn test_check_turn_timeout_forfeits_an_expired_game_without_a_move_attempt() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        manager
+            .set_turn_limit_in_game(Some(&game.id), Some(1))
+            .unwrap();
+
+        let mut expired = manager.get_game_state_in(Some(&game.id)).unwrap();
+        expired.turn_started_at -= 1_000;
+        manager.repository.save_game(&expired).unwrap();
+        let expired_player = expired.current_turn;
+
+        let result = manager.check_turn_timeout_in(Some(&game.id)).unwrap();
+        assert_eq!(result.status, GameStatus::Won(expired_player.opponent()));
+    }
+
+    #[test]
+    fn test_check_turn_timeout_is_a_no_op_before_the_limit_elapses() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        manager
+            .set_turn_limit_in_game(Some(&game.id), Some(300))
+            .unwrap();
+
+        let result = manager.check_turn_timeout_in(Some(&game.id)).unwrap();
+        assert_eq!(result.status, GameStatus::InProgress);
+    }
+
+    #[test]
+    fn test_set_ai_difficulty_in_game() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+
+        let updated = manager
+            .set_ai_difficulty_in_game(Some(&game.id), Some(shared::AiDifficulty::Hard))
+            .unwrap();
+        assert_eq!(updated.ai_difficulty, Some(shared::AiDifficulty::Hard));
+
+        let cleared = manager
+            .set_ai_difficulty_in_game(Some(&game.id), None)
+            .unwrap();
+        assert_eq!(cleared.ai_difficulty, None);
+    }
+
+    #[test]
+    fn test_compute_ai_move_rejects_when_it_is_not_the_ai_turn() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+
+        let mut human_turn_game = game.clone();
+        human_turn_game.current_turn = human_turn_game.human_player;
+        manager.repository.save_game(&human_turn_game).unwrap();
+
+        let result = manager.compute_ai_move();
+        assert!(matches!(result, Err(GameError::WrongTurn { .. })));
+    }
+
+    #[test]
+    fn test_compute_ai_move_returns_a_legal_move_without_applying_it() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+
+        // Hand the turn to the AI without actually playing a move, so compute_ai_move can run.
+        let mut ai_turn_game = game.clone();
+        ai_turn_game.current_turn = ai_turn_game.ai_player;
+        manager.repository.save_game(&ai_turn_game).unwrap();
+
+        let (row, col) = manager.compute_ai_move().unwrap();
+        assert!(row < 3 && col < 3);
+
+        // The move was only computed, not applied.
+        let unchanged = manager.get_game_state_in(Some(&game.id)).unwrap();
+        assert_eq!(unchanged.move_history.len(), 0);
+    }
+
+    #[test]
+    fn test_ai_turn_uses_ai_difficulty_when_set() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        manager
+            .set_ai_difficulty_in_game(Some(&game.id), Some(shared::AiDifficulty::Hard))
+            .unwrap();
+
+        // O's winning move is blocked by X unless the AI is actually consulted for its turn.
+        manager.make_move(0, 0, MoveSource::UI).unwrap();
+        let game = manager.get_game_state_in(Some(&game.id)).unwrap();
+        assert_eq!(game.move_history.len(), 2);
+        assert_eq!(game.move_history[1].player, game.ai_player);
+    }
+
+    #[test]
+    fn test_concede_game_awards_win_to_the_ai() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        let conceded = manager.concede_game_in(Some(&game.id)).unwrap();
+        assert_eq!(conceded.status, GameStatus::Won(conceded.ai_player));
+    }
+
+    #[test]
+    fn test_concede_game_is_a_no_op_once_the_game_is_over() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        let first = manager.concede_game_in(Some(&game.id)).unwrap();
+        let second = manager.concede_game_in(Some(&game.id)).unwrap();
+        assert_eq!(second.version, first.version);
+    }
+
+    #[test]
+    fn test_turn_switching() {
+        let mut manager = create_test_manager();
+        let game1 = manager.make_move(0, 0, MoveSource::UI).unwrap();
+        let first_player = game1.move_history[0].player;
+
+        let game2 = manager.make_move(0, 1, MoveSource::UI).unwrap();
+        assert_eq!(game2.current_turn, first_player);
+        assert_eq!(game2.move_history[1].player, first_player.opponent());
+    }
+
+    #[test]
+    fn test_restart_game() {
+        let mut manager = create_test_manager();
+        manager.make_move(0, 0, MoveSource::UI).unwrap();
+        let game1_id = manager.current_game_id.clone().unwrap();
+
+        let new_game = manager.restart_game().unwrap();
+
+        assert_ne!(new_game.id, game1_id);
+        assert_eq!(new_game.move_history.len(), 0);
+        assert_eq!(new_game.status, GameStatus::InProgress);
+    }
+
+    #[test]
+    fn test_request_rematch_rejects_a_game_still_in_progress() {
+        let mut manager = create_test_manager();
+        manager.get_or_create_game().unwrap();
+
+        let result = manager.request_rematch();
+        assert!(matches!(result, Err(GameError::GameStillInProgress)));
+    }
+
+    #[test]
+    fn test_request_rematch_preserves_players_and_alternates_first_turn() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        let first_player = game.current_turn;
+
+        let mut finished = game.clone();
+        finished.status = GameStatus::Won(first_player);
+        manager.repository.save_game(&finished).unwrap();
+
+        let rematch = manager.request_rematch().unwrap();
+
+        assert_ne!(rematch.id, finished.id);
+        assert_eq!(rematch.human_player, finished.human_player);
+        assert_eq!(rematch.ai_player, finished.ai_player);
+        assert_eq!(rematch.previous_game_id, Some(finished.id));
+        assert_eq!(rematch.status, GameStatus::InProgress);
+
+        // No move was ever made in `finished`, so request_rematch falls back to `human_player`
+        // as "who went first" there, meaning the rematch must open with `ai_player` instead --
+        // whether that shows up as `current_turn` (if the AI hasn't auto-played yet) or as the
+        // first entry of `move_history` (if it already has).
+        let rematch_first_mover = rematch
+            .move_history
+            .first()
+            .map(|m| m.player)
+            .unwrap_or(rematch.current_turn);
+        assert_eq!(rematch_first_mover, finished.ai_player);
+    }
+
+    #[test]
+    fn test_request_rematch_alternates_first_turn_based_on_who_actually_moved_first() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        let first_player = game.current_turn;
+
+        let game = manager.make_move(0, 0, MoveSource::UI).unwrap();
+        let mut finished = game.clone();
+        finished.status = GameStatus::Won(first_player);
+        manager.repository.save_game(&finished).unwrap();
+
+        let rematch = manager.request_rematch().unwrap();
+        let rematch_first_mover = rematch
+            .move_history
+            .first()
+            .map(|m| m.player)
+            .unwrap_or(rematch.current_turn);
+        assert_eq!(rematch_first_mover, first_player.opponent());
+    }
+
+    #[test]
+    fn test_cleanup_stale_games_keeps_recently_active_games() {
+        let mut manager = create_test_manager();
+        manager.get_or_create_game().unwrap();
+
+        let (forfeited, deleted) = manager
+            .cleanup_stale_games(Duration::from_secs(3_600), Duration::from_secs(3_600))
+            .unwrap();
+
+        assert_eq!(forfeited, 0);
+        assert_eq!(deleted, 0);
+        assert!(manager.get_game_state().is_ok());
+    }
+
+    #[test]
+    fn test_cleanup_stale_games_removes_games_idle_past_the_cutoff() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+
+        // `updated_at` has 1-second resolution, so a game saved "now" only counts as idle past a
+        // 1-second cutoff once real time has actually moved past the second it was saved in.
+        std::thread::sleep(Duration::from_millis(1_100));
+
+        let (_, deleted) = manager
+            .cleanup_stale_games(Duration::from_secs(1), Duration::from_secs(1))
+            .unwrap();
+
+        assert_eq!(deleted, 1);
+        assert!(matches!(
+            manager.repository.load_game(&game.id),
+            Err(GameError::GameNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_cleanup_stale_games_forfeits_in_progress_game_past_the_turn_timeout() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        std::thread::sleep(Duration::from_millis(1_100));
+
+        // Turn timeout is past, but the (much longer) deletion idle window isn't -- the game
+        // should be forfeited in place, not deleted.
+        let (forfeited, deleted) = manager
+            .cleanup_stale_games(Duration::from_secs(1), Duration::from_secs(3_600))
+            .unwrap();
+
+        assert_eq!(forfeited, 1);
+        assert_eq!(deleted, 0);
+        assert_eq!(
+            manager.repository.load_game(&game.id).unwrap().status,
+            GameStatus::Abandoned
+        );
+    }
+
+    #[test]
+    fn test_cleanup_stale_games_clears_the_current_game_pointer_if_reaped() {
+        let mut manager = create_test_manager();
+        manager.get_or_create_game().unwrap();
+        std::thread::sleep(Duration::from_millis(1_100));
+
+        manager
+            .cleanup_stale_games(Duration::from_secs(1), Duration::from_secs(1))
+            .unwrap();
+
+        // The implicit current game was just reaped, so the next call must mint a fresh one
+        // instead of trying to load the deleted id.
+        let fresh = manager.get_or_create_game().unwrap();
+        assert!(manager.repository.load_game(&fresh.id).is_ok());
+    }
+
+    #[test]
+    fn test_subscribe_receives_move_played_and_status_changed_events() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        let rx = manager.subscribe();
+
+        // A zero-second budget has already elapsed the instant it's set, so the next move attempt
+        // forfeits the game on the spot -- the cheapest way to deterministically reach a terminal
+        // status without depending on `assign_players`'s coin flip or a real move sequence.
+        manager.set_turn_limit_in_game(None, Some(0)).unwrap();
+        let _ = manager.make_move(0, 0, MoveSource::UI);
+
+        let mut saw_status_changed = false;
+        while let Ok(event) = rx.try_recv() {
+            if let GameEvent::StatusChanged { game_id, .. } = event {
+                assert_eq!(game_id, game.id);
+                saw_status_changed = true;
+            }
+        }
+        assert!(saw_status_changed);
+    }
+
+    #[test]
+    fn test_subscribe_receives_taunt_added_events() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        let rx = manager.subscribe();
+
+        manager
+            .add_taunt("gg".to_string(), shared::MoveSource::UI)
+            .unwrap();
+
+        let event = rx.try_recv().unwrap();
+        match event {
+            GameEvent::TauntAdded { game_id, taunt } => {
+                assert_eq!(game_id, game.id);
+                assert_eq!(taunt.body, "gg");
+            }
+            other => panic!("expected TauntAdded, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_subscribe_receives_game_restarted_events() {
+        let mut manager = create_test_manager();
+        manager.get_or_create_game().unwrap();
+        let rx = manager.subscribe();
+
+        let restarted = manager.restart_game().unwrap();
+
+        // If the AI went first, `restart_game` auto-plays its opening move before broadcasting
+        // `GameRestarted`, so don't assume it's the very first event on the channel.
+        let mut saw_restarted = false;
+        while let Ok(event) = rx.try_recv() {
+            if let GameEvent::GameRestarted { game_id } = event {
+                assert_eq!(game_id, restarted.id);
+                saw_restarted = true;
+            }
+        }
+        assert!(saw_restarted);
+    }
+
+    #[test]
+    fn test_dropping_a_subscriber_receiver_does_not_break_later_broadcasts() {
+        let mut manager = create_test_manager();
+        manager.get_or_create_game().unwrap();
+
+        let rx = manager.subscribe();
+        drop(rx);
+
+        let still_alive = manager.subscribe();
+        manager
+            .add_taunt("still here?".to_string(), shared::MoveSource::UI)
+            .unwrap();
+
+        assert!(still_alive.try_recv().is_ok());
+    }
+
+    #[test]
+    fn test_add_taunt() {
+        let mut manager = create_test_manager();
+        manager.get_or_create_game().unwrap();
+
+        let result =
+            manager.add_taunt("You call that a move?".to_string(), shared::MoveSource::MCP);
+        assert!(result.is_ok());
+
+        // Verify taunt is persisted
+        let game = manager.get_game_state().unwrap();
+        assert_eq!(game.taunts.len(), 1);
+        assert_eq!(game.taunts[0].body, "You call that a move?");
+        assert_eq!(game.taunts[0].sender, Some(shared::MoveSource::MCP));
+    }
+
+    #[test]
+    fn test_get_taunts_returns_the_most_recent_page_by_default() {
+        let mut manager = create_test_manager();
+        manager.get_or_create_game().unwrap();
+
+        for i in 0..3 {
+            manager
+                .add_taunt(format!("taunt {}", i), MoveSource::MCP)
+                .unwrap();
+        }
+
+        let page = manager.get_taunts(None, None, None, 2).unwrap();
+        assert_eq!(page.len(), 2);
+        assert_eq!(page[0].body, "taunt 1");
+        assert_eq!(page[1].body, "taunt 2");
+    }
+
+    #[test]
+    fn test_get_recent_emotes_in_filters_out_free_text_taunts() {
+        let mut manager = create_test_manager();
+        manager.get_or_create_game().unwrap();
+
+        manager
+            .add_taunt("not an emote".to_string(), MoveSource::MCP)
+            .unwrap();
+        manager
+            .add_emote_in_game(None, EmoteEnum::Fire, MoveSource::MCP)
+            .unwrap();
+
+        let emotes = manager.get_recent_emotes_in(None, 10).unwrap();
+        assert_eq!(emotes.len(), 1);
+        assert_eq!(emotes[0].0, EmoteEnum::Fire);
+        assert_eq!(emotes[0].1.body, EmoteEnum::Fire.as_str());
+    }
+
+    #[test]
+    fn test_game_state_persistence() {
+        let mut manager = create_test_manager();
+
+        // Make moves and get game state
+        manager.make_move(0, 0, MoveSource::UI).unwrap();
+        manager.make_move(1, 1, MoveSource::UI).unwrap();
+        let game_id = manager.current_game_id.clone().unwrap();
+
+        // Get game state again - should have persistent moves
+        let game = manager.get_game_state().unwrap();
+        assert_eq!(game.id, game_id);
+        assert_eq!(game.move_history.len(), 2);
+
+        // Verify data is in database by loading directly
+        let loaded_game = manager.repository.load_game(&game_id).unwrap();
+        assert_eq!(loaded_game.move_history.len(), 2);
+    }
+
+    #[test]
+    fn test_game_over_prevents_moves() {
+        let mut manager = create_test_manager();
+
+        // Create a winning condition for X
+        // X X X
+        // O O .
+        // . . .
+
+        // Simulate the moves directly to create a win
         manager.make_move(0, 0, MoveSource::UI).unwrap(); // X
         manager.make_move(1, 0, MoveSource::UI).unwrap(); // O
         manager.make_move(0, 1, MoveSource::UI).unwrap(); // X
@@ -301,4 +1547,397 @@ mod tests {
         let result = manager.make_move(2, 0, MoveSource::UI);
         assert!(matches!(result, Err(GameError::GameOver { .. })));
     }
+
+    #[test]
+    fn test_create_game_issues_a_seat_token() {
+        let mut manager = create_test_manager();
+        let (game, seat, token) = manager.create_game().unwrap();
+
+        assert!(!game.id.is_empty());
+        assert!(!token.is_empty());
+        assert_eq!(manager.seat_for_token(&game.id, &token).unwrap(), seat);
+    }
+
+    #[test]
+    fn test_join_game_claims_the_other_seat() {
+        let mut manager = create_test_manager();
+        let (game, seat, _token) = manager.create_game().unwrap();
+
+        let (_game, joined_seat, joined_token) = manager.join_game(&game.id).unwrap();
+
+        assert_eq!(joined_seat, seat.opponent());
+        assert_eq!(
+            manager.seat_for_token(&game.id, &joined_token).unwrap(),
+            joined_seat
+        );
+    }
+
+    #[test]
+    fn test_join_game_rejects_a_third_player() {
+        let mut manager = create_test_manager();
+        let (game, _seat, _token) = manager.create_game().unwrap();
+        manager.join_game(&game.id).unwrap();
+
+        let result = manager.join_game(&game.id);
+        assert!(matches!(result, Err(GameError::InternalError { .. })));
+    }
+
+    #[test]
+    fn test_list_games_includes_lobby_sessions() {
+        let mut manager = create_test_manager();
+        let (game, _seat, _token) = manager.create_game().unwrap();
+
+        let games = manager.list_games().unwrap();
+        assert!(games.iter().any(|g| g.id == game.id));
+    }
+
+    #[test]
+    fn test_request_pairing_waits_until_a_second_player_arrives() {
+        let mut manager = create_test_manager();
+        let pairing_id = manager.request_pairing("alice").unwrap();
+
+        let status = manager.pairing_status(&pairing_id, "alice").unwrap();
+        assert_eq!(status.status, "Waiting");
+        assert!(status.game_id.is_none());
+    }
+
+    #[test]
+    fn test_request_pairing_matches_a_second_player() {
+        let mut manager = create_test_manager();
+        let pairing_id = manager.request_pairing("alice").unwrap();
+        manager.request_pairing("bob").unwrap();
+
+        let alice_status = manager.pairing_status(&pairing_id, "alice").unwrap();
+        assert_eq!(alice_status.status, "Matched");
+        let game_id = alice_status.game_id.unwrap();
+
+        // Alice's token really does control the seat `pairing_status` reported for her.
+        assert_eq!(
+            manager
+                .seat_for_token(&game_id, &alice_status.token.unwrap())
+                .unwrap(),
+            alice_status.seat.unwrap()
+        );
+    }
+
+    #[test]
+    fn test_request_pairing_matches_both_sides_into_the_same_game() {
+        let mut manager = create_test_manager();
+        let pairing_id = manager.request_pairing("alice").unwrap();
+        manager.request_pairing("bob").unwrap();
+
+        let alice_status = manager.pairing_status(&pairing_id, "alice").unwrap();
+        let bob_status = manager.pairing_status(&pairing_id, "bob").unwrap();
+        assert_eq!(alice_status.game_id, bob_status.game_id);
+        assert_ne!(alice_status.seat, bob_status.seat);
+    }
+
+    #[test]
+    fn test_pairing_status_unknown_id_is_none() {
+        let manager = create_test_manager();
+        assert!(manager.pairing_status("nonexistent", "alice").is_none());
+    }
+
+    #[test]
+    fn test_leave_game_forfeits_to_the_other_seat() {
+        let mut manager = create_test_manager();
+        let (game, seat, token) = manager.create_game().unwrap();
+        manager.join_game(&game.id).unwrap();
+
+        let left = manager.leave_game(&game.id, &token).unwrap();
+        assert_eq!(left.status, GameStatus::Won(seat.opponent()));
+    }
+
+    #[test]
+    fn test_make_move_authenticated_rejects_wrong_seat() {
+        let mut manager = create_test_manager();
+        let (game, seat, token) = manager.create_game().unwrap();
+        let (_game, _opponent_seat, opponent_token) = manager.join_game(&game.id).unwrap();
+
+        let wrong_token = if game.current_turn == seat {
+            &opponent_token
+        } else {
+            &token
+        };
+
+        let result =
+            manager.make_move_authenticated(&game.id, wrong_token, 0, 0, MoveSource::MCP);
+        assert!(matches!(result, Err(GameError::WrongTurn { .. })));
+    }
+
+    #[test]
+    fn test_make_move_authenticated_rejects_unknown_token() {
+        let mut manager = create_test_manager();
+        let (game, _seat, _token) = manager.create_game().unwrap();
+
+        let result = manager.make_move_authenticated(&game.id, "bogus", 0, 0, MoveSource::MCP);
+        assert!(matches!(result, Err(GameError::InternalError { .. })));
+    }
+
+    #[test]
+    fn test_make_move_authenticated_applies_valid_move() {
+        let mut manager = create_test_manager();
+        let (game, seat, token) = manager.create_game().unwrap();
+        let (_game, opponent_seat, opponent_token) = manager.join_game(&game.id).unwrap();
+
+        let (turn_seat, turn_token) = if game.current_turn == seat {
+            (seat, &token)
+        } else {
+            (opponent_seat, &opponent_token)
+        };
+
+        let updated = manager
+            .make_move_authenticated(&game.id, turn_token, 0, 0, MoveSource::MCP)
+            .unwrap();
+
+        assert_eq!(updated.move_history.len(), 1);
+        assert_eq!(updated.move_history[0].player, turn_seat);
+    }
+
+    #[test]
+    fn test_state_version_starts_at_zero_and_bumps_on_move() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        assert_eq!(manager.state_version(&game.id), 0);
+
+        manager.make_move(0, 0, MoveSource::UI).unwrap();
+        assert_eq!(manager.state_version(&game.id), 1);
+
+        manager.make_move(0, 1, MoveSource::UI).unwrap();
+        assert_eq!(manager.state_version(&game.id), 2);
+    }
+
+    #[test]
+    fn test_game_state_version_mirrors_manager_state_version_across_reload() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        assert_eq!(game.version, 0);
+
+        let updated = manager.make_move(0, 0, MoveSource::UI).unwrap();
+        assert_eq!(updated.version, manager.state_version(&game.id));
+        assert!(updated.changed_since(0));
+
+        let reloaded = manager.get_game_state_in(Some(&game.id)).unwrap();
+        assert_eq!(reloaded.version, updated.version);
+    }
+
+    #[test]
+    fn test_state_version_bumps_on_taunt_and_restart() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+
+        manager
+            .add_taunt("gg".to_string(), MoveSource::UI)
+            .unwrap();
+        assert_eq!(manager.state_version(&game.id), 1);
+
+        let restarted = manager.restart_game().unwrap();
+        assert_eq!(manager.state_version(&restarted.id), 1);
+    }
+
+    #[test]
+    fn test_wait_for_update_returns_immediately_when_already_stale() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        manager.make_move(0, 0, MoveSource::UI).unwrap();
+
+        let (state, version, changed) = manager.wait_for_update(&game.id, 0, 1_000).unwrap();
+
+        assert!(changed);
+        assert_eq!(version, 1);
+        assert_eq!(state.move_history.len(), 1);
+    }
+
+    #[test]
+    fn test_wait_for_update_times_out_with_no_change() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+
+        let (_state, version, changed) = manager.wait_for_update(&game.id, 0, 50).unwrap();
+
+        assert!(!changed);
+        assert_eq!(version, 0);
+    }
+
+    #[test]
+    fn test_get_state_if_updated_reports_no_change_for_a_current_token() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+
+        let (_, updated_at) = manager.get_state_if_updated(&game.id, 0).unwrap();
+        let (unchanged, same_updated_at) = manager.get_state_if_updated(&game.id, updated_at).unwrap();
+
+        assert!(unchanged.is_none());
+        assert_eq!(same_updated_at, updated_at);
+    }
+
+    #[test]
+    fn test_get_state_if_updated_returns_state_for_a_stale_token() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+
+        let (changed, updated_at) = manager.get_state_if_updated(&game.id, 0).unwrap();
+
+        assert_eq!(changed.unwrap().id, game.id);
+        assert!(updated_at > 0);
+    }
+
+    #[test]
+    fn test_get_state_if_updated_unknown_game_errors() {
+        let mut manager = create_test_manager();
+        assert!(matches!(
+            manager.get_state_if_updated("nonexistent", 0),
+            Err(GameError::GameNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_make_move_leaves_the_turn_with_the_human_player() {
+        // Whoever moves first, the built-in AI auto-plays its own turn immediately
+        // after, so control always returns to the human.
+        let mut manager = create_test_manager();
+        let game = manager.make_move(0, 0, MoveSource::UI).unwrap();
+
+        if game.status == GameStatus::InProgress {
+            assert_eq!(game.current_turn, game.human_player);
+        }
+    }
+
+    #[test]
+    fn test_ai_auto_play_move_is_attributed_to_the_ai_player() {
+        // Force a case where the AI moves first: the human's single call then triggers
+        // a second, AI-attributed move.
+        for _ in 0..20 {
+            let mut manager = create_test_manager();
+            let game = manager.make_move(0, 0, MoveSource::UI).unwrap();
+            if game.move_history.len() == 2 {
+                assert_eq!(game.move_history[1].player, game.ai_player);
+                return;
+            }
+        }
+        panic!("AI never went first in 20 attempts");
+    }
+
+    #[test]
+    fn test_lobby_game_does_not_auto_play_the_ai() {
+        let mut manager = create_test_manager();
+        let (game, seat, token) = manager.create_game().unwrap();
+        let (_game, opponent_seat, opponent_token) = manager.join_game(&game.id).unwrap();
+
+        let (turn_seat, turn_token) = if game.current_turn == seat {
+            (seat, &token)
+        } else {
+            (opponent_seat, &opponent_token)
+        };
+
+        let updated = manager
+            .make_move_authenticated(&game.id, turn_token, 0, 0, MoveSource::MCP)
+            .unwrap();
+
+        // Only the human's move is present; the lobby's second seat is a real player, not the bot.
+        assert_eq!(updated.move_history.len(), 1);
+        assert_eq!(updated.current_turn, turn_seat.opponent());
+    }
+
+    #[test]
+    fn test_restart_game_with_difficulty_switches_the_bot_strength() {
+        let mut manager = create_test_manager();
+
+        let game = manager
+            .restart_game_with_difficulty(Some(Difficulty::Perfect))
+            .unwrap();
+        assert_eq!(manager.bot.difficulty, Difficulty::Perfect);
+
+        // Omitting a difficulty keeps whatever was already in effect.
+        let game2 = manager.restart_game_with_difficulty(None).unwrap();
+        assert_eq!(manager.bot.difficulty, Difficulty::Perfect);
+        assert_ne!(game2.id, game.id);
+    }
+
+    #[test]
+    fn test_make_move_in_game_targets_a_specific_session() {
+        let mut manager = create_test_manager();
+        let implicit = manager.get_or_create_game().unwrap();
+        let (other, _seat, _token) = manager.create_game().unwrap();
+
+        let updated = manager
+            .make_move_in_game(Some(&other.id), 0, 0, MoveSource::MCP)
+            .unwrap();
+
+        assert_eq!(updated.id, other.id);
+        assert_eq!(updated.move_history.len(), 1);
+
+        // The implicit current game is untouched.
+        let implicit_after = manager.get_game_state_in(None).unwrap();
+        assert_eq!(implicit_after.id, implicit.id);
+        assert_eq!(implicit_after.move_history.len(), 0);
+    }
+
+    #[test]
+    fn test_get_game_state_in_rejects_an_unknown_game_id() {
+        let mut manager = create_test_manager();
+        let result = manager.get_game_state_in(Some("no-such-game"));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_finishing_a_single_ai_game_credits_the_leaderboard() {
+        let mut manager = create_test_manager();
+        manager
+            .restart_game_with_difficulty(Some(Difficulty::Perfect))
+            .unwrap();
+
+        // Play into every cell in scan order until the game (human move + auto AI reply) ends;
+        // a Perfect bot never loses, so this always finishes in a win or a draw.
+        let mut game = manager.get_game_state().unwrap();
+        for row in 0..3 {
+            for col in 0..3 {
+                if game.status != GameStatus::InProgress {
+                    break;
+                }
+                if game.board[row as usize][col as usize] == Cell::Empty {
+                    if let Ok(updated) = manager.make_move(row, col, MoveSource::UI) {
+                        game = updated;
+                    }
+                }
+            }
+        }
+        assert_ne!(game.status, GameStatus::InProgress);
+
+        let leaderboard = manager.get_leaderboard(10).unwrap();
+        let total: u32 = leaderboard
+            .iter()
+            .map(|entry| entry.wins + entry.losses + entry.draws)
+            .sum();
+        assert_eq!(total, 2); // one tally each for "human" and "ai"
+    }
+
+    #[test]
+    fn test_reset_leaderboard_clears_recorded_tallies() {
+        let mut manager = create_test_manager();
+        manager
+            .restart_game_with_difficulty(Some(Difficulty::Perfect))
+            .unwrap();
+
+        let mut game = manager.get_game_state().unwrap();
+        for row in 0..3 {
+            for col in 0..3 {
+                if game.status != GameStatus::InProgress {
+                    break;
+                }
+                if game.board[row as usize][col as usize] == Cell::Empty {
+                    if let Ok(updated) = manager.make_move(row, col, MoveSource::UI) {
+                        game = updated;
+                    }
+                }
+            }
+        }
+        assert_ne!(game.status, GameStatus::InProgress);
+        assert!(!manager.get_leaderboard(10).unwrap().is_empty());
+
+        manager.reset_leaderboard().unwrap();
+
+        assert!(manager.get_leaderboard(10).unwrap().is_empty());
+    }
 }
@@ -0,0 +1,6 @@
+pub mod board;
+pub mod bot;
+pub mod logic;
+pub mod manager;
+pub mod player;
+pub mod record;
@@ -0,0 +1,346 @@
+//! A portable, SGF-inspired text format for archiving a `GameState` outside of JSON: header
+//! properties in `KEY[value]` form, followed by one `;<player>[<coord>]` property per move.
+//! Replaying the moves through the same board/status logic `apply_move` uses reconstructs the
+//! board, status, and winning line, so the record never needs to carry them directly.
+//!
+//! Example: `(;GM[TicTacToe]ID[abc-123]HP[X]AP[O]RE[X];X[aa];O[ab];X[bb];O[ac];X[cc])`
+
+use super::board::Board;
+use super::logic::get_game_status;
+use shared::{GameError, GameState, GameStatus, Move, MoveSource, Player};
+
+/// Row/col 0-2 map to `'a'`-`'c'`; a coordinate is encoded `<row><col>`, e.g. `(0, 0)` -> `"aa"`.
+fn encode_coord(row: u8, col: u8) -> Result<String, GameError> {
+    if row > 2 || col > 2 {
+        return Err(GameError::OutOfBounds { row, col });
+    }
+    Ok(format!("{}{}", (b'a' + row) as char, (b'a' + col) as char))
+}
+
+fn decode_coord(s: &str) -> Result<(u8, u8), GameError> {
+    let bytes = s.as_bytes();
+    if bytes.len() != 2 || !bytes.iter().all(|b| b.is_ascii_lowercase()) {
+        return Err(GameError::OutOfBounds { row: 0, col: 0 });
+    }
+    let row = bytes[0] - b'a';
+    let col = bytes[1] - b'a';
+    if row > 2 || col > 2 {
+        return Err(GameError::OutOfBounds { row, col });
+    }
+    Ok((row, col))
+}
+
+fn player_from_str(s: &str) -> Result<Player, GameError> {
+    match s {
+        "X" => Ok(Player::X),
+        "O" => Ok(Player::O),
+        other => Err(GameError::InternalError {
+            message: format!("Unknown player '{other}' in record"),
+        }),
+    }
+}
+
+fn source_tag(source: &Option<MoveSource>) -> &'static str {
+    match source {
+        Some(MoveSource::UI) => "UI",
+        Some(MoveSource::MCP) => "MCP",
+        Some(MoveSource::AI) => "AI",
+        None => "?",
+    }
+}
+
+/// Escape `\` and `]` so a header value can't be mistaken for the end of its bracket.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(']', "\\]")
+}
+
+/// Scan `KEY[value]KEY[value]...`, honoring `\]`/`\\` escapes inside values. Move properties
+/// (`;X[aa]`) fall out of this naturally: the leading `;` is just part of the scanned key.
+fn parse_properties(body: &str) -> Result<Vec<(String, String)>, GameError> {
+    let chars: Vec<char> = body.chars().collect();
+    let mut props = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let key_start = i;
+        while i < chars.len() && chars[i] != '[' {
+            i += 1;
+        }
+        if i >= chars.len() {
+            break;
+        }
+        let key: String = chars[key_start..i].iter().collect();
+        i += 1; // skip '['
+
+        let mut value = String::new();
+        while i < chars.len() && chars[i] != ']' {
+            if chars[i] == '\\' && i + 1 < chars.len() {
+                i += 1;
+            }
+            value.push(chars[i]);
+            i += 1;
+        }
+        if i >= chars.len() {
+            return Err(GameError::InternalError {
+                message: "Unterminated property in game record".to_string(),
+            });
+        }
+        i += 1; // skip ']'
+        props.push((key, value));
+    }
+    Ok(props)
+}
+
+/// Serialize `state` to the portable record format.
+pub fn to_record(state: &GameState) -> String {
+    let mut out = String::from("(;GM[TicTacToe]");
+    out.push_str(&format!("ID[{}]", escape(&state.id)));
+    out.push_str(&format!("HP[{}]", state.human_player));
+    out.push_str(&format!("AP[{}]", state.ai_player));
+
+    match &state.status {
+        GameStatus::Won(player) => out.push_str(&format!("RE[{player}]")),
+        GameStatus::Draw => out.push_str("RE[Draw]"),
+        GameStatus::Abandoned => out.push_str("RE[Abandoned]"),
+        GameStatus::InProgress => {}
+    }
+
+    for taunt in &state.taunts {
+        out.push_str(&format!(
+            "TT[{}|{}]",
+            source_tag(&taunt.sender),
+            escape(&taunt.body)
+        ));
+    }
+
+    for mov in &state.move_history {
+        // Moves in a live `GameState` are always produced by `apply_move`, which already
+        // bounds-checks row/col, so this can't actually fail.
+        if let Ok(coord) = encode_coord(mov.row, mov.col) {
+            out.push_str(&format!(";{}[{}]", mov.player, coord));
+        }
+    }
+
+    out.push(')');
+    out
+}
+
+/// Parse a record produced by `to_record` back into a `GameState`, replaying its moves through
+/// the same board/status logic live games use so the two never drift apart.
+pub fn from_record(record: &str) -> Result<GameState, GameError> {
+    let body = record
+        .trim()
+        .strip_prefix("(;")
+        .and_then(|s| s.strip_suffix(')'))
+        .ok_or_else(|| GameError::InternalError {
+            message: "Game record must be wrapped in '(;' ... ')'".to_string(),
+        })?;
+
+    let props = parse_properties(body)?;
+
+    let mut id = None;
+    let mut human_player = None;
+    let mut ai_player = None;
+    let mut board = Board::new();
+    let mut move_history = Vec::new();
+    let mut taunts = Vec::new();
+
+    for (key, value) in props {
+        match key.as_str() {
+            "GM" | "RE" => {} // RE is derived from replay instead of trusted from the header
+            "ID" => id = Some(value),
+            "HP" => human_player = Some(player_from_str(&value)?),
+            "AP" => ai_player = Some(player_from_str(&value)?),
+            "TT" => {
+                let (sender_tag, body) = value.split_once('|').ok_or_else(|| {
+                    GameError::InternalError {
+                        message: format!("Malformed TT property: {value}"),
+                    }
+                })?;
+                let sender = match sender_tag {
+                    "UI" => Some(MoveSource::UI),
+                    "MCP" => Some(MoveSource::MCP),
+                    "AI" => Some(MoveSource::AI),
+                    _ => None,
+                };
+                taunts.push(shared::ChatMessage {
+                    id: taunts.len() as u64,
+                    sender,
+                    body: body.to_string(),
+                    timestamp: 0,
+                });
+            }
+            ";X" | ";O" => {
+                let player = player_from_str(&key[1..])?;
+                let (row, col) = decode_coord(&value)?;
+                if board.get(row, col) != Some(shared::Cell::Empty) {
+                    return Err(GameError::CellOccupied { row, col });
+                }
+                board.set(row, col, player).map_err(|_| GameError::OutOfBounds { row, col })?;
+                move_history.push(Move {
+                    player,
+                    row,
+                    col,
+                    timestamp: 0,
+                    source: None,
+                });
+            }
+            other => {
+                return Err(GameError::InternalError {
+                    message: format!("Unknown property '{other}' in game record"),
+                });
+            }
+        }
+    }
+
+    let id = id.ok_or_else(|| GameError::InternalError {
+        message: "Game record is missing ID[...]".to_string(),
+    })?;
+    let human_player = human_player.ok_or_else(|| GameError::InternalError {
+        message: "Game record is missing HP[...]".to_string(),
+    })?;
+    let ai_player = ai_player.ok_or_else(|| GameError::InternalError {
+        message: "Game record is missing AP[...]".to_string(),
+    })?;
+
+    let (status, winning_line) = get_game_status(&board);
+    let current_turn = match move_history.last() {
+        Some(last) if status == GameStatus::InProgress => last.player.opponent(),
+        _ => human_player,
+    };
+
+    Ok(GameState {
+        id,
+        board: board.to_array(),
+        current_turn,
+        human_player,
+        ai_player,
+        status,
+        move_history,
+        taunts,
+        winning_line,
+        turn_started_at: 0,
+        turn_limit_secs: None,
+        ai_difficulty: None,
+        version: 0,
+        previous_game_id: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_game() -> GameState {
+        GameState {
+            id: "game-1".to_string(),
+            board: [[shared::Cell::Empty; 3]; 3],
+            current_turn: Player::O,
+            human_player: Player::X,
+            ai_player: Player::O,
+            status: GameStatus::InProgress,
+            move_history: vec![Move {
+                player: Player::X,
+                row: 0,
+                col: 0,
+                timestamp: 1,
+                source: Some(MoveSource::UI),
+            }],
+            taunts: vec![shared::ChatMessage {
+                id: 0,
+                sender: Some(MoveSource::UI),
+                body: "gg".to_string(),
+                timestamp: 1,
+            }],
+            winning_line: None,
+            turn_started_at: 0,
+            turn_limit_secs: None,
+            ai_difficulty: None,
+            version: 0,
+            previous_game_id: None,
+        }
+    }
+
+    #[test]
+    fn test_round_trip_in_progress_game() {
+        let game = sample_game();
+        let record = to_record(&game);
+        let parsed = from_record(&record).unwrap();
+
+        assert_eq!(parsed.id, game.id);
+        assert_eq!(parsed.human_player, game.human_player);
+        assert_eq!(parsed.ai_player, game.ai_player);
+        assert_eq!(parsed.status, GameStatus::InProgress);
+        assert_eq!(parsed.move_history, game.move_history.into_iter().map(|m| Move { timestamp: 0, source: None, ..m }).collect::<Vec<_>>());
+        assert_eq!(parsed.taunts.len(), 1);
+        assert_eq!(parsed.taunts[0].body, "gg");
+    }
+
+    #[test]
+    fn test_round_trip_won_game_sets_result_and_winning_line() {
+        let mut game = sample_game();
+        game.move_history = vec![
+            Move { player: Player::X, row: 0, col: 0, timestamp: 1, source: Some(MoveSource::UI) },
+            Move { player: Player::O, row: 1, col: 0, timestamp: 2, source: Some(MoveSource::MCP) },
+            Move { player: Player::X, row: 0, col: 1, timestamp: 3, source: Some(MoveSource::UI) },
+            Move { player: Player::O, row: 1, col: 1, timestamp: 4, source: Some(MoveSource::MCP) },
+            Move { player: Player::X, row: 0, col: 2, timestamp: 5, source: Some(MoveSource::UI) },
+        ];
+        game.status = GameStatus::Won(Player::X);
+
+        let record = to_record(&game);
+        assert!(record.contains("RE[X]"));
+
+        let parsed = from_record(&record).unwrap();
+        assert_eq!(parsed.status, GameStatus::Won(Player::X));
+        assert!(parsed.winning_line.is_some());
+    }
+
+    #[test]
+    fn test_round_trip_draw_game() {
+        let mut game = sample_game();
+        game.status = GameStatus::Draw;
+        let record = to_record(&game);
+        assert!(record.contains("RE[Draw]"));
+    }
+
+    #[test]
+    fn test_from_record_rejects_missing_wrapper() {
+        let err = from_record("ID[x]HP[X]AP[O]").unwrap_err();
+        assert!(matches!(err, GameError::InternalError { .. }));
+    }
+
+    #[test]
+    fn test_from_record_rejects_duplicate_cell() {
+        let record = "(;ID[g]HP[X]AP[O];X[aa];O[aa])";
+        let err = from_record(record).unwrap_err();
+        assert!(matches!(err, GameError::CellOccupied { row: 0, col: 0 }));
+    }
+
+    #[test]
+    fn test_from_record_rejects_out_of_bounds_coordinate() {
+        let record = "(;ID[g]HP[X]AP[O];X[ad])";
+        let err = from_record(record).unwrap_err();
+        assert!(matches!(err, GameError::OutOfBounds { .. }));
+    }
+
+    #[test]
+    fn test_from_record_rejects_missing_header_field() {
+        let record = "(;ID[g];X[aa])";
+        let err = from_record(record).unwrap_err();
+        assert!(matches!(err, GameError::InternalError { .. }));
+    }
+
+    #[test]
+    fn test_escapes_bracket_in_taunt_body() {
+        let mut game = sample_game();
+        game.taunts = vec![shared::ChatMessage {
+            id: 0,
+            sender: Some(MoveSource::UI),
+            body: "gg] nice one".to_string(),
+            timestamp: 1,
+        }];
+        let record = to_record(&game);
+        let parsed = from_record(&record).unwrap();
+        assert_eq!(parsed.taunts[0].body, "gg] nice one");
+    }
+}
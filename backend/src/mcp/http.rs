@@ -0,0 +1,83 @@
+//! Streamable HTTP + SSE transport for the MCP server, alongside the stdio transport in
+//! `super::transport`. `handle_request`/`dispatch` are untouched; this just wires an axum
+//! router to an `HttpTransport` running the normal `McpServer::run_with_transport` loop on its
+//! own thread.
+
+use super::server::McpServer;
+use super::transport::{HttpTransport, HttpTransportHandle};
+use axum::{
+    Router,
+    extract::State,
+    response::sse::{Event, KeepAlive, Sse},
+    routing::{get, post},
+};
+use futures::stream::Stream;
+use std::convert::Infallible;
+use std::time::Duration;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+
+/// Shared state for the standalone MCP-over-HTTP router
+#[derive(Clone)]
+pub struct McpHttpState {
+    handle: HttpTransportHandle,
+}
+
+/// Spawn an `McpServer` backed by `db_path` on its own thread, driven by an `HttpTransport`,
+/// and return a router exposing it over HTTP POST + SSE. This lets MCP clients that speak the
+/// HTTP transport connect to the same tic-tac-toe server the stdio binary exposes.
+pub fn spawn_http_mcp_server(db_path: String) -> Result<Router, shared::GameError> {
+    let (mut transport, handle) = HttpTransport::channel();
+    let mut server = McpServer::new(&db_path)?;
+
+    std::thread::spawn(move || {
+        if let Err(e) = server.run_with_transport(&mut transport) {
+            tracing::error!("MCP HTTP transport loop exited: {}", e);
+        }
+    });
+
+    Ok(create_router(McpHttpState { handle }))
+}
+
+fn create_router(state: McpHttpState) -> Router {
+    Router::new()
+        .route("/mcp/rpc", post(handle_rpc))
+        .route("/mcp/events", get(handle_events))
+        .with_state(state)
+}
+
+/// POST /mcp/rpc - submit a JSON-RPC request/batch body, return the `JsonRpcResponse` body
+async fn handle_rpc(State(state): State<McpHttpState>, body: String) -> String {
+    let reply_rx = match state.handle.submit(body) {
+        Ok(rx) => rx,
+        Err(e) => {
+            tracing::error!("MCP HTTP transport unavailable: {}", e);
+            return String::new();
+        }
+    };
+
+    // The transport thread answers synchronously, so the blocking recv resolves almost
+    // immediately; run it off the async executor so it can't stall other requests.
+    tokio::task::spawn_blocking(move || reply_rx.recv().unwrap_or_default())
+        .await
+        .unwrap_or_default()
+}
+
+/// GET /mcp/events - stream every response/notification emitted by the MCP server as SSE, for
+/// long-running or server-initiated messages
+async fn handle_events(
+    State(state): State<McpHttpState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let rx = state.handle.subscribe();
+    let stream = BroadcastStream::new(rx).filter_map(|result| match result {
+        Ok(message) => Some(Ok(Event::default().data(message))),
+        Err(BroadcastStreamRecvError::Lagged(_)) => None,
+    });
+
+    Sse::new(stream).keep_alive(
+        KeepAlive::new()
+            .interval(Duration::from_secs(15))
+            .text("keep-alive"),
+    )
+}
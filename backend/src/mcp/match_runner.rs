@@ -0,0 +1,264 @@
+//! Headless bot-vs-bot match runner: drives a full game without a human JSON-RPC client in the
+//! loop, for testing strategies and generating game-history fixtures. Mirrors the
+//! `MatchConfig`/bot-player shape of planetwars-localdev's match runner, adapted to this
+//! server's JSON-RPC-over-stdio tool surface.
+
+use super::protocol::JsonRpcError;
+use super::server::McpServer;
+use rand::seq::SliceRandom;
+use serde::Serialize;
+use serde_json::{Value, json};
+use shared::{GameStatus, Move};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+/// An automated opponent for `run_match`
+#[derive(Debug, Clone)]
+pub enum MatchBot {
+    /// Picks a uniformly random legal move
+    Random,
+    /// Spawns `command` with `args` and drives it over line-delimited JSON-RPC on its
+    /// stdin/stdout, the same transport this server itself speaks
+    External { command: String, args: Vec<String> },
+}
+
+/// Describes one headless bot-vs-bot match
+#[derive(Debug, Clone)]
+pub struct MatchConfig {
+    pub player_x: MatchBot,
+    pub player_o: MatchBot,
+    /// Safety valve against a runner that never reaches a terminal state
+    pub max_moves: u32,
+}
+
+impl Default for MatchConfig {
+    fn default() -> Self {
+        Self {
+            player_x: MatchBot::Random,
+            player_o: MatchBot::Random,
+            max_moves: 9,
+        }
+    }
+}
+
+/// One full game's outcome: the final status and the ordered move log that produced it
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchResult {
+    pub game_id: String,
+    pub moves: Vec<Move>,
+    pub status: GameStatus,
+}
+
+/// A spawned external bot process, driven over line-delimited JSON-RPC
+struct ExternalBotProcess {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl ExternalBotProcess {
+    fn spawn(command: &str, args: &[String]) -> Result<Self, JsonRpcError> {
+        let mut child = Command::new(command)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                JsonRpcError::internal_error(format!("Failed to spawn bot '{}': {}", command, e))
+            })?;
+
+        let stdin = child.stdin.take().ok_or_else(|| {
+            JsonRpcError::internal_error("Bot process has no stdin".to_string())
+        })?;
+        let stdout = child.stdout.take().ok_or_else(|| {
+            JsonRpcError::internal_error("Bot process has no stdout".to_string())
+        })?;
+
+        Ok(Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            next_id: 1,
+        })
+    }
+
+    /// Ask the bot to choose a move for `seat` given the current board, via a `choose_move`
+    /// JSON-RPC call, and return the `(row, col)` it picked.
+    fn choose_move(&mut self, board: &Value, seat: shared::Player) -> Result<(u8, u8), JsonRpcError> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "choose_move",
+            "params": {"board": board, "you": seat},
+        });
+
+        writeln!(self.stdin, "{}", request)
+            .map_err(|e| JsonRpcError::internal_error(format!("Failed to write to bot: {}", e)))?;
+        self.stdin
+            .flush()
+            .map_err(|e| JsonRpcError::internal_error(format!("Failed to flush bot stdin: {}", e)))?;
+
+        let mut line = String::new();
+        self.stdout
+            .read_line(&mut line)
+            .map_err(|e| JsonRpcError::internal_error(format!("Failed to read from bot: {}", e)))?;
+
+        let response: Value = serde_json::from_str(line.trim())
+            .map_err(|e| JsonRpcError::internal_error(format!("Bot returned invalid JSON: {}", e)))?;
+
+        let row = response["result"]["row"].as_u64().ok_or_else(|| {
+            JsonRpcError::internal_error("Bot response missing 'row'".to_string())
+        })? as u8;
+        let col = response["result"]["col"].as_u64().ok_or_else(|| {
+            JsonRpcError::internal_error("Bot response missing 'col'".to_string())
+        })? as u8;
+
+        Ok((row, col))
+    }
+}
+
+impl Drop for ExternalBotProcess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// An in-process or external opponent, holding whatever state it needs between moves
+enum ActiveBot {
+    Random,
+    External(ExternalBotProcess),
+}
+
+impl ActiveBot {
+    fn new(bot: &MatchBot) -> Result<Self, JsonRpcError> {
+        match bot {
+            MatchBot::Random => Ok(Self::Random),
+            MatchBot::External { command, args } => {
+                Ok(Self::External(ExternalBotProcess::spawn(command, args)?))
+            }
+        }
+    }
+
+    fn choose_move(&mut self, board: &Value, seat: shared::Player) -> Result<(u8, u8), JsonRpcError> {
+        match self {
+            Self::Random => Ok(random_legal_move(board)),
+            Self::External(process) => process.choose_move(board, seat),
+        }
+    }
+}
+
+/// Pick a uniformly random empty cell from a serialized 3x3 board
+fn random_legal_move(board: &Value) -> (u8, u8) {
+    let mut empty_cells = Vec::new();
+    if let Some(rows) = board.as_array() {
+        for (row_idx, row) in rows.iter().enumerate() {
+            if let Some(cells) = row.as_array() {
+                for (col_idx, cell) in cells.iter().enumerate() {
+                    if cell == &json!("Empty") {
+                        empty_cells.push((row_idx as u8, col_idx as u8));
+                    }
+                }
+            }
+        }
+    }
+
+    *empty_cells
+        .choose(&mut rand::thread_rng())
+        .expect("random_legal_move called on a full board")
+}
+
+/// Drive a full game between `config.player_x` and `config.player_o` over the given server's
+/// dispatch loop, alternating turns until `view_game_state` reports a win/draw or `max_moves`
+/// is exceeded. Returns the final status and the complete move log.
+pub fn run_match(server: &mut McpServer<'_>, config: &MatchConfig) -> Result<MatchResult, JsonRpcError> {
+    server.dispatch("restart_game", json!({}))?;
+
+    let mut bot_x = ActiveBot::new(&config.player_x)?;
+    let mut bot_o = ActiveBot::new(&config.player_o)?;
+
+    let mut game_id = String::new();
+
+    for _ in 0..config.max_moves {
+        let state = server.dispatch("view_game_state", json!({}))?;
+        game_id = state["id"].as_str().unwrap_or_default().to_string();
+
+        if state["status"] != "InProgress" {
+            break;
+        }
+
+        let current_turn = state["currentTurn"].as_str().unwrap_or("X");
+        let seat = if current_turn == "X" {
+            shared::Player::X
+        } else {
+            shared::Player::O
+        };
+
+        let (row, col) = match seat {
+            shared::Player::X => bot_x.choose_move(&state["board"], seat)?,
+            shared::Player::O => bot_o.choose_move(&state["board"], seat)?,
+        };
+
+        server.dispatch("make_move", json!({"row": row, "col": col}))?;
+    }
+
+    let final_state = server.dispatch("view_game_state", json!({}))?;
+    let status = match final_state["status"].as_str().unwrap_or("InProgress") {
+        "Won_X" => GameStatus::Won(shared::Player::X),
+        "Won_O" => GameStatus::Won(shared::Player::O),
+        "Draw" => GameStatus::Draw,
+        _ => GameStatus::InProgress,
+    };
+
+    let moves: Vec<Move> = final_state["moveHistory"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|v| serde_json::from_value(v).ok())
+        .collect();
+
+    Ok(MatchResult {
+        game_id,
+        moves,
+        status,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_legal_move_avoids_occupied_cells() {
+        let board = json!([
+            ["Occupied", "Empty", "Occupied"],
+            ["Empty", "Occupied", "Empty"],
+            ["Empty", "Empty", "Empty"],
+        ]);
+
+        for _ in 0..20 {
+            let (row, col) = random_legal_move(&board);
+            assert_ne!(board[row as usize][col as usize], json!("Occupied"));
+        }
+    }
+
+    #[test]
+    fn test_run_match_between_two_random_bots_reaches_a_terminal_state() {
+        let mut manager = crate::game::manager::GameManager::new(&format!(
+            "/tmp/test-match-{}.db",
+            uuid::Uuid::new_v4()
+        ))
+        .unwrap();
+        let mut server = McpServer::new_with_manager(&mut manager);
+
+        let result = run_match(&mut server, &MatchConfig::default()).unwrap();
+
+        assert!(!result.moves.is_empty());
+        assert_ne!(result.status, GameStatus::InProgress);
+    }
+}
@@ -0,0 +1,181 @@
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Upper bounds (ms) of each latency histogram bucket. A call whose duration exceeds every
+/// bound falls into an implicit "+Inf" bucket.
+const BUCKET_BOUNDS_MS: &[u64] = &[1, 5, 10, 25, 50, 100, 250, 500, 1000];
+
+/// Call counters and a latency histogram for a single JSON-RPC method
+#[derive(Debug, Clone, Default)]
+struct MethodStats {
+    total: u64,
+    success: u64,
+    error: u64,
+    total_duration_ms: u64,
+    /// Cumulative counts per bucket bound in `BUCKET_BOUNDS_MS`, plus one trailing "+Inf" entry
+    buckets: Vec<u64>,
+}
+
+impl MethodStats {
+    fn record(&mut self, duration: Duration, success: bool) {
+        self.total += 1;
+        if success {
+            self.success += 1;
+        } else {
+            self.error += 1;
+        }
+
+        let duration_ms = duration.as_millis() as u64;
+        self.total_duration_ms += duration_ms;
+
+        if self.buckets.is_empty() {
+            self.buckets = vec![0; BUCKET_BOUNDS_MS.len() + 1];
+        }
+        let bucket_index = BUCKET_BOUNDS_MS
+            .iter()
+            .position(|bound| duration_ms <= *bound)
+            .unwrap_or(BUCKET_BOUNDS_MS.len());
+        self.buckets[bucket_index] += 1;
+    }
+
+    fn avg_duration_ms(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.total_duration_ms as f64 / self.total as f64
+        }
+    }
+}
+
+/// Per-method call counters and latency histograms for an [`super::server::McpServer`]. Counters
+/// live for as long as the server does, so operators can see which tools dominate traffic and
+/// which error out over a `run` loop's lifetime.
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    per_method: HashMap<String, MethodStats>,
+}
+
+impl Metrics {
+    /// Record the outcome and duration of a dispatched method call
+    pub fn record(&mut self, method: &str, duration: Duration, success: bool) {
+        self.per_method
+            .entry(method.to_string())
+            .or_default()
+            .record(duration, success);
+    }
+
+    /// Render the aggregates as JSON, for the `get_metrics` tool's default output
+    pub fn to_json(&self) -> Value {
+        let methods: Value = self
+            .per_method
+            .iter()
+            .map(|(method, stats)| {
+                (
+                    method.clone(),
+                    json!({
+                        "total": stats.total,
+                        "success": stats.success,
+                        "error": stats.error,
+                        "avgDurationMs": stats.avg_duration_ms(),
+                        "buckets": bucket_labels()
+                            .into_iter()
+                            .zip(stats.buckets.iter())
+                            .map(|(label, count)| json!({"le": label, "count": count}))
+                            .collect::<Vec<_>>(),
+                    }),
+                )
+            })
+            .collect();
+
+        json!({ "methods": methods })
+    }
+
+    /// Render the aggregates in Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str("# TYPE mcp_method_calls_total counter\n");
+        for (method, stats) in &self.per_method {
+            out.push_str(&format!(
+                "mcp_method_calls_total{{method=\"{}\",outcome=\"success\"}} {}\n",
+                method, stats.success
+            ));
+            out.push_str(&format!(
+                "mcp_method_calls_total{{method=\"{}\",outcome=\"error\"}} {}\n",
+                method, stats.error
+            ));
+        }
+
+        out.push_str("# TYPE mcp_method_duration_ms_bucket histogram\n");
+        for (method, stats) in &self.per_method {
+            let mut cumulative = 0;
+            for (label, count) in bucket_labels().into_iter().zip(stats.buckets.iter()) {
+                cumulative += count;
+                out.push_str(&format!(
+                    "mcp_method_duration_ms_bucket{{method=\"{}\",le=\"{}\"}} {}\n",
+                    method, label, cumulative
+                ));
+            }
+        }
+
+        out
+    }
+}
+
+/// Human-readable bucket upper bounds, matching `MethodStats::buckets`'s layout
+fn bucket_labels() -> Vec<String> {
+    BUCKET_BOUNDS_MS
+        .iter()
+        .map(|b| b.to_string())
+        .chain(std::iter::once("+Inf".to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_tracks_totals_and_outcomes() {
+        let mut metrics = Metrics::default();
+        metrics.record("make_move", Duration::from_millis(2), true);
+        metrics.record("make_move", Duration::from_millis(3), false);
+
+        let json = metrics.to_json();
+        let stats = &json["methods"]["make_move"];
+        assert_eq!(stats["total"], 2);
+        assert_eq!(stats["success"], 1);
+        assert_eq!(stats["error"], 1);
+    }
+
+    #[test]
+    fn test_record_buckets_by_duration() {
+        let mut metrics = Metrics::default();
+        metrics.record("get_turn", Duration::from_millis(0), true);
+        metrics.record("get_turn", Duration::from_millis(2000), true);
+
+        let json = metrics.to_json();
+        let buckets = json["methods"]["get_turn"]["buckets"].as_array().unwrap();
+        assert_eq!(buckets.first().unwrap()["count"], 1);
+        assert_eq!(buckets.last().unwrap()["count"], 1);
+        assert_eq!(buckets.last().unwrap()["le"], "+Inf");
+    }
+
+    #[test]
+    fn test_to_prometheus_includes_method_name() {
+        let mut metrics = Metrics::default();
+        metrics.record("view_game_state", Duration::from_millis(1), true);
+
+        let text = metrics.to_prometheus();
+        assert!(text.contains("mcp_method_calls_total"));
+        assert!(text.contains("method=\"view_game_state\""));
+    }
+
+    #[test]
+    fn test_avg_duration_ms_with_no_calls_is_zero() {
+        let metrics = Metrics::default();
+        let json = metrics.to_json();
+        assert_eq!(json["methods"].as_object().unwrap().len(), 0);
+    }
+}
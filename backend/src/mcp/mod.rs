@@ -0,0 +1,8 @@
+pub mod http;
+pub mod match_runner;
+pub mod metrics;
+pub mod protocol;
+pub mod server;
+pub mod session;
+pub mod tools;
+pub mod transport;
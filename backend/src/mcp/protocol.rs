@@ -11,25 +11,56 @@ pub const INVALID_PARAMS: i32 = -32602;
 pub const INTERNAL_ERROR: i32 = -32603;
 
 /// JSON-RPC 2.0 Request
+///
+/// `id` is `None` when the member is absent entirely, which per the spec marks this request
+/// as a notification (dispatched for its side effects, no response expected). An explicit
+/// `"id": null` is `Some(Value::Null)` and still expects a response.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct JsonRpcRequest {
     pub jsonrpc: String,
-    pub id: Value,
+    #[serde(default, deserialize_with = "deserialize_id")]
+    pub id: Option<Value>,
     pub method: String,
     pub params: Value,
 }
 
+/// Distinguish a missing `id` member (`None`, via `#[serde(default)]`) from an explicit
+/// `null` id (`Some(Value::Null)`), which the blanket `Option<Value>` deserializer would
+/// otherwise collapse into the same `None` value.
+fn deserialize_id<'de, D>(deserializer: D) -> Result<Option<Value>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(Some(Option::deserialize(deserializer)?.unwrap_or(Value::Null)))
+}
+
 impl JsonRpcRequest {
     /// Create a new JSON-RPC request
     pub fn new(id: Value, method: String, params: Value) -> Self {
         Self {
             jsonrpc: "2.0".to_string(),
-            id,
+            id: Some(id),
+            method,
+            params,
+        }
+    }
+
+    /// Create a notification: no `id`, dispatched for its side effects with no response
+    pub fn new_notification(method: String, params: Value) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: None,
             method,
             params,
         }
     }
 
+    /// A request with no `id` member is a notification: it must be dispatched but must not
+    /// produce a response.
+    pub fn is_notification(&self) -> bool {
+        self.id.is_none()
+    }
+
     /// Parse from JSON string
     pub fn from_json(json: &str) -> Result<Self, JsonRpcError> {
         serde_json::from_str(json).map_err(|e| JsonRpcError {
@@ -166,11 +197,29 @@ mod tests {
         let request = JsonRpcRequest::from_json(json).unwrap();
 
         assert_eq!(request.jsonrpc, "2.0");
-        assert_eq!(request.id, json!(1));
+        assert_eq!(request.id, Some(json!(1)));
         assert_eq!(request.method, "test_method");
         assert_eq!(request.params, json!({}));
     }
 
+    #[test]
+    fn test_parse_request_missing_id_is_notification() {
+        let json = r#"{"jsonrpc":"2.0","method":"test_method","params":{}}"#;
+        let request = JsonRpcRequest::from_json(json).unwrap();
+
+        assert_eq!(request.id, None);
+        assert!(request.is_notification());
+    }
+
+    #[test]
+    fn test_parse_request_null_id_is_not_notification() {
+        let json = r#"{"jsonrpc":"2.0","id":null,"method":"test_method","params":{}}"#;
+        let request = JsonRpcRequest::from_json(json).unwrap();
+
+        assert_eq!(request.id, Some(Value::Null));
+        assert!(!request.is_notification());
+    }
+
     #[test]
     fn test_parse_invalid_json() {
         let json = r#"{"jsonrpc":"2.0","id":1,invalid}"#;
@@ -1,14 +1,59 @@
-use super::protocol::{JsonRpcError, JsonRpcRequest, JsonRpcResponse, METHOD_NOT_FOUND};
+use super::metrics::Metrics;
+use super::protocol::{JsonRpcError, JsonRpcRequest, JsonRpcResponse, INVALID_REQUEST, METHOD_NOT_FOUND};
+use super::session::SessionStore;
 use super::tools;
+use super::transport::{StdioTransport, Transport};
 use crate::game::manager::GameManager;
 use serde_json::Value;
-use std::io::{self, BufRead, Write};
+use std::io;
+use std::time::{Duration, Instant};
+
+/// MCP protocol versions this server understands, oldest first. The last entry is the server's
+/// preferred/newest version.
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2024-11-05", "2025-03-26"];
 
 /// MCP server that handles JSON-RPC 2.0 requests via stdio
 #[allow(dead_code)] // Will be used by binary entry point
 pub struct McpServer<'a> {
     manager: Option<GameManager>,
     manager_ref: Option<&'a mut GameManager>,
+    /// Protocol version agreed on during `initialize`, used to gate version-specific dispatch
+    /// behavior. `None` until a client has initialized.
+    negotiated_version: Option<&'static str>,
+    /// Per-method call counters and latency histograms, surfaced via the `get_metrics` tool
+    metrics: Metrics,
+    /// Shared secret callers must present to `connect`, from `GAME_MCP_AUTH_TOKEN`. `None`
+    /// leaves auth disabled: `connect` still tracks sessions for reconnect, but no call is
+    /// required to carry a `sessionId`.
+    auth_secret: Option<String>,
+    /// A second, more-privileged shared secret from `GAME_MCP_ADMIN_TOKEN`. Presenting this
+    /// instead of (or in addition to) `auth_secret` at `connect` also satisfies `auth_secret`
+    /// (an admin caller shouldn't need both tokens) and marks the resulting session admin,
+    /// gating tools that can affect the host rather than just the game -- see
+    /// `caller_is_admin`. `None` means no caller can reach those tools over stdio/the Unix
+    /// socket transport; the HTTP transport gates them instead via `admin_override`.
+    admin_secret: Option<String>,
+    /// Active client sessions, created by `connect` and looked up by `sessionId` on every
+    /// later call once auth is enabled.
+    sessions: SessionStore,
+    /// Treats every call on this server instance as admin-privileged, bypassing the
+    /// per-session check. Set by the HTTP transport (`mcp_handler`'s `set_admin_override`),
+    /// which constructs a fresh `McpServer` per request and so has no persistent session to
+    /// mark admin -- it already checked the caller's JWT `Role` before this server existed.
+    admin_override: bool,
+    /// When `run_with_transport`'s loop last ran the stale-game sweep (see
+    /// `maybe_cleanup_stale_games`). There's no timer thread here -- this server is a
+    /// single-threaded blocking read loop over stdio/a Unix socket -- so cleanup instead piggybacks
+    /// on whatever request happens to arrive once `cleanup_interval` has elapsed.
+    last_cleanup: Instant,
+    /// How often to run the sweep, from `GAME_CLEANUP_INTERVAL_SECS` (default 1 hour).
+    cleanup_interval: Duration,
+    /// How long an `InProgress` game's turn may sit untouched before the sweep forfeits it to
+    /// `GameStatus::Abandoned`, from `GAME_CLEANUP_TURN_TIMEOUT_SECS` (default 30 minutes).
+    cleanup_turn_timeout: Duration,
+    /// How long a game may sit idle before the sweep deletes it outright, from
+    /// `GAME_CLEANUP_TIMEOUT_SECS` (default 24 hours).
+    cleanup_idle: Duration,
 }
 
 #[allow(dead_code)] // Will be used by binary entry point
@@ -19,6 +64,16 @@ impl<'a> McpServer<'a> {
         Ok(Self {
             manager: Some(manager),
             manager_ref: None,
+            negotiated_version: None,
+            metrics: Metrics::default(),
+            auth_secret: std::env::var("GAME_MCP_AUTH_TOKEN").ok(),
+            admin_secret: std::env::var("GAME_MCP_ADMIN_TOKEN").ok(),
+            sessions: SessionStore::default(),
+            admin_override: false,
+            last_cleanup: Instant::now(),
+            cleanup_interval: Duration::from_secs(env_u64("GAME_CLEANUP_INTERVAL_SECS", 3_600)),
+            cleanup_turn_timeout: Duration::from_secs(env_u64("GAME_CLEANUP_TURN_TIMEOUT_SECS", 30 * 60)),
+            cleanup_idle: Duration::from_secs(env_u64("GAME_CLEANUP_TIMEOUT_SECS", 24 * 3_600)),
         })
     }
 
@@ -27,9 +82,28 @@ impl<'a> McpServer<'a> {
         Self {
             manager: None,
             manager_ref: Some(manager),
+            negotiated_version: None,
+            metrics: Metrics::default(),
+            auth_secret: std::env::var("GAME_MCP_AUTH_TOKEN").ok(),
+            admin_secret: std::env::var("GAME_MCP_ADMIN_TOKEN").ok(),
+            sessions: SessionStore::default(),
+            admin_override: false,
+            last_cleanup: Instant::now(),
+            cleanup_interval: Duration::from_secs(env_u64("GAME_CLEANUP_INTERVAL_SECS", 3_600)),
+            cleanup_turn_timeout: Duration::from_secs(env_u64("GAME_CLEANUP_TURN_TIMEOUT_SECS", 30 * 60)),
+            cleanup_idle: Duration::from_secs(env_u64("GAME_CLEANUP_TIMEOUT_SECS", 24 * 3_600)),
         }
     }
 
+    /// Treat every call on this server instance as admin-privileged for the rest of its
+    /// lifetime, bypassing the per-session `is_admin` check in `caller_is_admin`. Intended for
+    /// the HTTP transport, which builds a fresh, session-less `McpServer` per request after
+    /// already checking the caller's JWT `Role` -- there's no session to mark admin, so the
+    /// server instance itself is.
+    pub fn set_admin_override(&mut self, is_admin: bool) {
+        self.admin_override = is_admin;
+    }
+
     /// Get a mutable reference to the game manager
     fn get_manager(&mut self) -> &mut GameManager {
         if let Some(ref mut manager) = self.manager {
@@ -41,75 +115,373 @@ impl<'a> McpServer<'a> {
         }
     }
 
-    /// Run the server loop, reading from stdin and writing to stdout
+    /// Run the server loop over stdin/stdout (the default transport)
     pub fn run(&mut self) -> io::Result<()> {
-        let stdin = io::stdin();
-        let mut stdout = io::stdout();
-
-        for line in stdin.lock().lines() {
-            let line = line?;
-            let response = self.handle_request(&line);
-            writeln!(stdout, "{}", response)?;
-            stdout.flush()?;
+        self.run_with_transport(&mut StdioTransport::new())
+    }
+
+    /// Run the server loop over an arbitrary [`Transport`]. `handle_request`/`dispatch` stay
+    /// transport-agnostic; this loop just wires `recv`/`send` to them.
+    pub fn run_with_transport(&mut self, transport: &mut dyn Transport) -> io::Result<()> {
+        while let Some(request) = transport.recv()? {
+            self.maybe_cleanup_stale_games();
+
+            let response = self.handle_request(&request);
+            // Notifications and all-notification batches produce no response line
+            if response.is_empty() {
+                continue;
+            }
+            transport.send(&response)?;
         }
 
         Ok(())
     }
 
-    /// Handle a single JSON-RPC request
+    /// Run the stale-game sweep if `cleanup_interval` has elapsed since it last ran. There's no
+    /// background timer here -- this server has one thread, blocked on `transport.recv()` -- so
+    /// this piggybacks on whatever request happens to arrive next; an idle connection with no
+    /// traffic simply delays the sweep, which is fine since nothing is accumulating in the
+    /// meantime anyway.
+    fn maybe_cleanup_stale_games(&mut self) {
+        if self.last_cleanup.elapsed() < self.cleanup_interval {
+            return;
+        }
+        self.last_cleanup = Instant::now();
+
+        let turn_timeout = self.cleanup_turn_timeout;
+        let idle = self.cleanup_idle;
+        match self.get_manager().cleanup_stale_games(turn_timeout, idle) {
+            Ok((forfeited, deleted)) if forfeited > 0 || deleted > 0 => tracing::info!(
+                "Cleaned up stale games: {} forfeited, {} deleted",
+                forfeited, deleted
+            ),
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to clean up stale games: {}", e),
+        }
+    }
+
+    /// Handle a single JSON-RPC request or batch, returning the response to write.
+    ///
+    /// Per the JSON-RPC 2.0 spec: a top-level array is a batch, dispatched element-by-element
+    /// into a single response array; an empty batch is an `INVALID_REQUEST` error; requests
+    /// with no `id` are notifications and contribute no entry to the response. An empty string
+    /// is returned when there is nothing to send (a lone notification, or an all-notification
+    /// batch).
     pub fn handle_request(&mut self, json: &str) -> String {
-        // Parse the request
-        let request = match JsonRpcRequest::from_json(json) {
-            Ok(req) => req,
+        let value: Value = match serde_json::from_str(json) {
+            Ok(v) => v,
             Err(e) => {
-                let response = JsonRpcResponse::error(Value::Null, e);
+                let response =
+                    JsonRpcResponse::error(Value::Null, JsonRpcError::parse_error(e.to_string()));
+                return response.to_json();
+            }
+        };
+
+        if let Some(batch) = value.as_array() {
+            if batch.is_empty() {
+                let response = JsonRpcResponse::error(
+                    Value::Null,
+                    JsonRpcError::invalid_request("Batch array must not be empty".to_string()),
+                );
                 return response.to_json();
             }
+
+            let responses: Vec<JsonRpcResponse> = batch
+                .iter()
+                .cloned()
+                .filter_map(|item| self.handle_single(item))
+                .collect();
+
+            return if responses.is_empty() {
+                String::new()
+            } else {
+                serde_json::to_string(&responses).unwrap()
+            };
+        }
+
+        match self.handle_single(value) {
+            Some(response) => response.to_json(),
+            None => String::new(),
+        }
+    }
+
+    /// Handle a single already-parsed JSON-RPC request value, returning `None` for
+    /// notifications (no `id` member).
+    fn handle_single(&mut self, value: Value) -> Option<JsonRpcResponse> {
+        let request: JsonRpcRequest = match serde_json::from_value(value) {
+            Ok(req) => req,
+            Err(e) => {
+                return Some(JsonRpcResponse::error(
+                    Value::Null,
+                    JsonRpcError::parse_error(format!("Parse error: {}", e)),
+                ));
+            }
         };
 
-        // Validate the request
         if let Err(e) = request.validate() {
-            let response = JsonRpcResponse::error(request.id.clone(), e);
-            return response.to_json();
+            return Some(JsonRpcResponse::error(
+                request.id.clone().unwrap_or(Value::Null),
+                e,
+            ));
         }
 
-        // Dispatch to the appropriate tool
+        let is_notification = request.is_notification();
         let result = self.dispatch(&request.method, request.params.clone());
 
-        // Create response
-        let response = match result {
-            Ok(value) => JsonRpcResponse::success(request.id, value),
-            Err(error) => JsonRpcResponse::error(request.id, error),
-        };
+        if is_notification {
+            return None;
+        }
 
-        response.to_json()
+        let id = request.id.clone().unwrap_or(Value::Null);
+        Some(match result {
+            Ok(value) => JsonRpcResponse::success(id, value),
+            Err(error) => JsonRpcResponse::error(id, error),
+        })
     }
 
     /// Dispatch a method call to the appropriate tool handler
-    fn dispatch(&mut self, method: &str, params: Value) -> Result<Value, JsonRpcError> {
-        match method {
+    pub(crate) fn dispatch(&mut self, method: &str, params: Value) -> Result<Value, JsonRpcError> {
+        let started_at = Instant::now();
+        let result = self.dispatch_inner(method, params);
+        self.metrics
+            .record(method, started_at.elapsed(), result.is_ok());
+        result
+    }
+
+    /// The actual method dispatch, separated out so `dispatch` can time and record every call
+    /// (including this one's own `get_metrics`) in one place.
+    fn dispatch_inner(&mut self, method: &str, params: Value) -> Result<Value, JsonRpcError> {
+        if method != "connect" && self.auth_secret.is_some() {
+            self.authorize(&params)?;
+        }
+
+        let session_id = params.get("sessionId").and_then(Value::as_str).map(String::from);
+        let params_game_id = params.get("gameId").and_then(Value::as_str).map(String::from);
+
+        let result = match method {
+            // Session handshake
+            "connect" => self.handle_connect(params),
             // MCP protocol methods
-            "initialize" => Self::handle_initialize(params),
+            "initialize" => self.handle_initialize(params),
             "tools/list" => Self::handle_tools_list(params),
             // Game tool methods
             "view_game_state" => tools::view_game_state(self.get_manager(), params),
             "get_turn" => tools::get_turn(self.get_manager(), params),
             "make_move" => tools::make_move(self.get_manager(), params),
             "taunt_player" => tools::taunt_player(self.get_manager(), params),
+            "send_emote" => tools::send_emote(self.get_manager(), params),
             "restart_game" => tools::restart_game(self.get_manager(), params),
+            "request_rematch" => tools::request_rematch(self.get_manager(), params),
+            "set_turn_limit" => tools::set_turn_limit(self.get_manager(), params),
+            "check_turn_timeout" => tools::check_turn_timeout(self.get_manager(), params),
+            "compute_ai_move" => tools::compute_ai_move(self.get_manager(), params),
+            "set_ai_difficulty" => tools::set_ai_difficulty(self.get_manager(), params),
             "get_game_history" => tools::get_game_history(self.get_manager(), params),
+            "get_taunts" => tools::get_taunts(self.get_manager(), params),
+            "get_recent_emotes" => tools::get_recent_emotes(self.get_manager(), params),
+            "new_game" => tools::new_game(self.get_manager(), params),
+            // Multi-session lobby methods
+            "create_game" => tools::create_game(self.get_manager(), params),
+            "join_game" => tools::join_game(self.get_manager(), params),
+            "list_games" => tools::list_games(self.get_manager(), params),
+            "wait_for_update" => tools::wait_for_update(self.get_manager(), params),
+            "get_state_if_changed" => tools::get_state_if_changed(self.get_manager(), params),
+            "get_state_if_updated" => tools::get_state_if_updated(self.get_manager(), params),
+            "get_leaderboard" => tools::get_leaderboard(self.get_manager(), params),
+            "reset_leaderboard" => tools::reset_leaderboard(self.get_manager(), params),
+            // Observability
+            "get_metrics" => self.handle_get_metrics(params),
+            // Headless testing
+            "run_match" => self.handle_run_match(params),
             _ => Err(JsonRpcError {
                 code: METHOD_NOT_FOUND,
                 message: format!("Method '{}' not found", method),
                 data: None,
             }),
+        };
+
+        // Keep the session's associated game fresh so a later reconnect can resume it: prefer
+        // whatever gameId the call just produced (e.g. create_game/join_game) and fall back to
+        // one it was called with (e.g. make_move).
+        if let (Some(session_id), Ok(value)) = (&session_id, &result) {
+            let game_id = value
+                .get("gameId")
+                .and_then(Value::as_str)
+                .map(String::from)
+                .or(params_game_id);
+            if let Some(game_id) = game_id {
+                self.sessions.set_game(session_id, game_id);
+            }
         }
+
+        result
+    }
+
+    /// When auth is configured (via `GAME_MCP_AUTH_TOKEN`), every call other than `connect` must
+    /// carry a `sessionId` from an active session. Missing or unknown session ids fail with the
+    /// same `INVALID_REQUEST` code `connect` itself uses for a bad token, giving callers one
+    /// error-path contract to assert against.
+    fn authorize(&self, params: &Value) -> Result<(), JsonRpcError> {
+        let session_id = params.get("sessionId").and_then(Value::as_str).ok_or_else(|| {
+            JsonRpcError::invalid_request("Missing 'sessionId'; call 'connect' first".to_string())
+        })?;
+
+        if self.sessions.get(session_id).is_none() {
+            return Err(JsonRpcError::invalid_request(format!(
+                "Unknown or expired session '{}'",
+                session_id
+            )));
+        }
+
+        Ok(())
     }
 
-    /// Handle MCP initialize request
-    fn handle_initialize(_params: Value) -> Result<Value, JsonRpcError> {
+    /// Handle the `connect` handshake. When auth is configured, `token` must match
+    /// `GAME_MCP_AUTH_TOKEN` or `GAME_MCP_ADMIN_TOKEN` or the call fails with
+    /// `INVALID_REQUEST` -- the admin token satisfies regular auth too, so an admin caller
+    /// doesn't need to present both. On success, issues a session id for `client_id`,
+    /// recording whether it connected with the admin token (see `caller_is_admin`).
+    /// Reconnecting with the same `client_id` resumes its existing session (and whatever game
+    /// it was last associated with) instead of starting fresh.
+    fn handle_connect(&mut self, params: Value) -> Result<Value, JsonRpcError> {
+        let client_id = params.get("client_id").and_then(Value::as_str).ok_or_else(|| {
+            JsonRpcError::invalid_request("Missing 'client_id' parameter".to_string())
+        })?;
+
+        let token = params.get("token").and_then(Value::as_str);
+        let is_admin = self.admin_secret.is_some() && token == self.admin_secret.as_deref();
+
+        if let Some(secret) = &self.auth_secret {
+            if token != Some(secret.as_str()) && !is_admin {
+                return Err(JsonRpcError::invalid_request(
+                    "Invalid or missing auth token".to_string(),
+                ));
+            }
+        }
+
+        let session_id = self.sessions.connect(client_id, is_admin);
+        let game_id = self.sessions.get(&session_id).and_then(|s| s.game_id.clone());
+
         Ok(serde_json::json!({
-            "protocolVersion": "2024-11-05",
+            "sessionId": session_id,
+            "gameId": game_id,
+        }))
+    }
+
+    /// Whether the caller of the in-flight request is admin-privileged: either this server
+    /// instance has `admin_override` set (the HTTP transport, which already checked the
+    /// caller's JWT `Role` before constructing a fresh server for this request), or the call's
+    /// `sessionId` resolves to a session that connected with the admin token (the stdio/Unix
+    /// socket transports, where one server instance and its `SessionStore` live for the
+    /// process's whole lifetime).
+    fn caller_is_admin(&self, params: &Value) -> bool {
+        self.admin_override
+            || params
+                .get("sessionId")
+                .and_then(Value::as_str)
+                .is_some_and(|session_id| self.sessions.is_admin(session_id))
+    }
+
+    /// Handle the run_match tool call: simulate a full bot-vs-bot game on the current game
+    /// session and return its move log and final result, for testing strategies and generating
+    /// game-history fixtures without a human client in the loop.
+    ///
+    /// The `external` bot strategy spawns an arbitrary host executable/argv (see
+    /// `match_runner::ExternalBotProcess::spawn`), so it's gated on `caller_is_admin` here --
+    /// in `dispatch_inner`, the one chokepoint every transport (stdio, the Unix socket, and
+    /// HTTP) funnels through -- rather than in any single transport's own entry point. The
+    /// `random` strategy does nothing host-affecting and stays open to any caller.
+    fn handle_run_match(&mut self, params: Value) -> Result<Value, JsonRpcError> {
+        if requests_external_bot(&params) && !self.caller_is_admin(&params) {
+            return Err(JsonRpcError::invalid_request(
+                "run_match's 'external' bot strategy requires an admin-privileged caller"
+                    .to_string(),
+            ));
+        }
+
+        let config = super::match_runner::MatchConfig {
+            player_x: parse_bot(&params["playerX"])?,
+            player_o: parse_bot(&params["playerO"])?,
+            max_moves: params["maxMoves"].as_u64().unwrap_or(9) as u32,
+        };
+
+        let result = super::match_runner::run_match(self, &config)?;
+
+        Ok(serde_json::json!({
+            "gameId": result.game_id,
+            "moves": result.moves,
+            "status": match result.status {
+                shared::GameStatus::InProgress => "InProgress",
+                shared::GameStatus::Won(p) => match p {
+                    shared::Player::X => "Won_X",
+                    shared::Player::O => "Won_O",
+                },
+                shared::GameStatus::Draw => "Draw",
+                shared::GameStatus::Abandoned => "Abandoned",
+            },
+        }))
+    }
+
+    /// Handle the get_metrics tool call: dump per-method counters/latency as JSON, or as
+    /// Prometheus text exposition format when `params.format == "prometheus"`
+    fn handle_get_metrics(&self, params: Value) -> Result<Value, JsonRpcError> {
+        if params.get("format").and_then(Value::as_str) == Some("prometheus") {
+            Ok(Value::String(self.metrics.to_prometheus()))
+        } else {
+            Ok(self.metrics.to_json())
+        }
+    }
+
+    /// Handle MCP initialize request: negotiate a protocol version with the client and store it
+    /// on `self` so later dispatch can gate version-specific tool behavior.
+    ///
+    /// The client's requested version goes in `protocolVersion`; it may optionally also send a
+    /// `supportedVersions` array if it speaks more than one dialect. We pick the newest version
+    /// present in both the client's requested set and `SUPPORTED_PROTOCOL_VERSIONS`. If the
+    /// client's exact `protocolVersion` isn't one we support but no broader set was given, we
+    /// fall back to our own newest version (the client decides whether that's acceptable). Only
+    /// a `supportedVersions` array with zero overlap is treated as a hard failure.
+    fn handle_initialize(&mut self, params: Value) -> Result<Value, JsonRpcError> {
+        let requested = params
+            .get("protocolVersion")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                JsonRpcError::invalid_params("Missing 'protocolVersion' parameter".to_string())
+            })?;
+
+        let client_versions: Vec<&str> = match params.get("supportedVersions").and_then(Value::as_array) {
+            Some(versions) => versions.iter().filter_map(Value::as_str).collect(),
+            None => vec![requested],
+        };
+
+        let negotiated = SUPPORTED_PROTOCOL_VERSIONS
+            .iter()
+            .rev()
+            .find(|v| client_versions.contains(v))
+            .copied();
+
+        let negotiated = match negotiated {
+            Some(version) => version,
+            None if client_versions == [requested] => {
+                // Single-version handshake with no match; offer our newest version rather than
+                // failing outright, same as the MCP reference servers do.
+                SUPPORTED_PROTOCOL_VERSIONS.last().copied().unwrap()
+            }
+            None => {
+                return Err(JsonRpcError {
+                    code: INVALID_REQUEST,
+                    message: "No overlapping protocol version".to_string(),
+                    data: Some(serde_json::json!({
+                        "supportedVersions": SUPPORTED_PROTOCOL_VERSIONS,
+                    })),
+                });
+            }
+        };
+
+        self.negotiated_version = Some(negotiated);
+
+        Ok(serde_json::json!({
+            "protocolVersion": negotiated,
             "serverInfo": {
                 "name": "tictactoe-mcp-server",
                 "version": "0.1.0"
@@ -124,12 +496,35 @@ impl<'a> McpServer<'a> {
     fn handle_tools_list(_params: Value) -> Result<Value, JsonRpcError> {
         Ok(serde_json::json!({
             "tools": [
+                {
+                    "name": "connect",
+                    "description": "Handshake with the server, authenticating with a token (when GAME_MCP_AUTH_TOKEN is configured) and receiving a sessionId to pass to every later call. Presenting GAME_MCP_ADMIN_TOKEN instead also satisfies GAME_MCP_AUTH_TOKEN and marks the session admin, which is required for run_match's 'external' bot strategy. Reconnecting with the same client_id resumes the previously associated game.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "token": {
+                                "type": "string",
+                                "description": "Shared secret to authenticate with, when the server has auth configured"
+                            },
+                            "client_id": {
+                                "type": "string",
+                                "description": "Stable identifier for this client, used to resume its session on reconnect"
+                            }
+                        },
+                        "required": ["client_id"]
+                    }
+                },
                 {
                     "name": "view_game_state",
                     "description": "View the current tic-tac-toe game state including board, turn, status, and history",
                     "inputSchema": {
                         "type": "object",
-                        "properties": {}
+                        "properties": {
+                            "gameId": {
+                                "type": "string",
+                                "description": "Optional ID of a specific session to view. Omit to use the implicit current game."
+                            }
+                        }
                     }
                 },
                 {
@@ -137,7 +532,12 @@ impl<'a> McpServer<'a> {
                     "description": "Get whose turn it is (X or O)",
                     "inputSchema": {
                         "type": "object",
-                        "properties": {}
+                        "properties": {
+                            "gameId": {
+                                "type": "string",
+                                "description": "Optional ID of a specific session to check. Omit to use the implicit current game."
+                            }
+                        }
                     }
                 },
                 {
@@ -157,6 +557,10 @@ impl<'a> McpServer<'a> {
                                 "description": "Column index (0-2)",
                                 "minimum": 0,
                                 "maximum": 2
+                            },
+                            "gameId": {
+                                "type": "string",
+                                "description": "Optional ID of a specific session to move in. Omit to use the implicit current game."
                             }
                         },
                         "required": ["row", "col"]
@@ -171,32 +575,387 @@ impl<'a> McpServer<'a> {
                             "message": {
                                 "type": "string",
                                 "description": "The taunt message to send"
+                            },
+                            "gameId": {
+                                "type": "string",
+                                "description": "Optional ID of a specific session to taunt in. Omit to use the implicit current game."
                             }
                         },
                         "required": ["message"]
                     }
                 },
+                {
+                    "name": "send_emote",
+                    "description": "Send a predefined quick emote to your opponent (\"Cool\", \"Fire\", \"Steam\", \"Handshake\", or \"Cry\"), rejected if it isn't one of those",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "emote": {
+                                "type": "string",
+                                "description": "One of: Cool, Fire, Steam, Handshake, Cry"
+                            },
+                            "gameId": {
+                                "type": "string",
+                                "description": "Optional ID of a specific session to send the emote in. Omit to use the implicit current game."
+                            }
+                        },
+                        "required": ["emote"]
+                    }
+                },
                 {
                     "name": "restart_game",
                     "description": "Restart the game with a fresh board",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "difficulty": {
+                                "type": "string",
+                                "description": "Optional AI strength for the new game: \"Random\", \"Intermediate\", or \"Perfect\". Omit to keep the current difficulty."
+                            }
+                        }
+                    }
+                },
+                {
+                    "name": "request_rematch",
+                    "description": "Start a rematch of the current game once it has ended: keeps the same human/AI seat assignment but alternates who moves first, unlike restart_game which re-randomizes both",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {}
+                    }
+                },
+                {
+                    "name": "set_turn_limit",
+                    "description": "Set (or clear) a game's per-turn time budget in seconds. A move attempted after the budget elapses forfeits the game to the opponent.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "turnLimitSecs": {
+                                "type": "integer",
+                                "description": "Seconds allowed per turn. Omit or pass null to disable the turn clock."
+                            },
+                            "gameId": {
+                                "type": "string",
+                                "description": "Optional ID of a specific session to configure. Omit to use the implicit current game."
+                            }
+                        }
+                    }
+                },
+                {
+                    "name": "check_turn_timeout",
+                    "description": "Proactively resolve a session's turn-clock the same way make_move would, without an (otherwise rejected) move attempt to trigger it",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "gameId": {
+                                "type": "string",
+                                "description": "Optional ID of a specific session to check. Omit to use the implicit current game."
+                            }
+                        }
+                    }
+                },
+                {
+                    "name": "compute_ai_move",
+                    "description": "Compute the move Monte Carlo Tree Search would pick for the current game's AI opponent, without applying it, so a client can preview it or drive the AI turn itself",
                     "inputSchema": {
                         "type": "object",
                         "properties": {}
                     }
                 },
+                {
+                    "name": "set_ai_difficulty",
+                    "description": "Set (or clear) a game's AiDifficulty. Once set, the AI's turn is chosen by a ranked minimax pool instead of the legacy auto-player, with weaker levels sometimes blundering on purpose.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "difficulty": {
+                                "type": "string",
+                                "description": "\"Easy\", \"Normal\", or \"Hard\". Omit or pass null to clear it and fall back to the legacy auto-player."
+                            },
+                            "gameId": {
+                                "type": "string",
+                                "description": "Optional ID of a specific session to configure. Omit to use the implicit current game."
+                            }
+                        }
+                    }
+                },
                 {
                     "name": "get_game_history",
-                    "description": "Get the complete history of moves made in the current game",
+                    "description": "Get a bounded, filterable slice of the moves made in a game, windowed and paged by move index like get_taunts pages by message id",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "gameId": {
+                                "type": "string",
+                                "description": "Optional ID of a specific session to inspect. Omit to use the implicit current game."
+                            },
+                            "before": {
+                                "type": "integer",
+                                "description": "Window the history to moves with an index earlier than this"
+                            },
+                            "after": {
+                                "type": "integer",
+                                "description": "Window the history to moves with an index later than this"
+                            },
+                            "limit": {
+                                "type": "integer",
+                                "description": "Maximum number of moves to return (default 50, capped at 200)"
+                            },
+                            "source": {
+                                "type": "string",
+                                "description": "Only return moves from this source: \"UI\" or \"MCP\""
+                            },
+                            "includeTaunts": {
+                                "type": "boolean",
+                                "description": "Also include the game's (unpaginated) taunts alongside the moves"
+                            }
+                        }
+                    }
+                },
+                {
+                    "name": "get_taunts",
+                    "description": "Get a bounded, ordered slice of a game's chat/taunt history, for CHATHISTORY-style scroll-back instead of loading it all via get_game_history",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "gameId": {
+                                "type": "string",
+                                "description": "Optional ID of a specific session to inspect. Omit to use the implicit current game."
+                            },
+                            "before": {
+                                "type": "integer",
+                                "description": "Return messages older than this message id"
+                            },
+                            "after": {
+                                "type": "integer",
+                                "description": "Return messages newer than this message id"
+                            },
+                            "limit": {
+                                "type": "integer",
+                                "description": "Maximum number of messages to return (default 50)"
+                            }
+                        }
+                    }
+                },
+                {
+                    "name": "get_recent_emotes",
+                    "description": "Like get_taunts, but pre-filtered down to quick emotes so the caller can render reaction icons without sifting free-text taunts out itself",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "gameId": {
+                                "type": "string",
+                                "description": "Optional ID of a specific session to inspect. Omit to use the implicit current game."
+                            },
+                            "limit": {
+                                "type": "integer",
+                                "description": "Maximum number of recent messages to scan for emotes (default 50)"
+                            }
+                        }
+                    }
+                },
+                {
+                    "name": "new_game",
+                    "description": "Start a brand-new AI-opponent game under a fresh id, without touching the implicit current game make_move/view_game_state fall back to. Address the returned gameId via those tools' optional gameId param to run several AI games concurrently.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {}
+                    }
+                },
+                {
+                    "name": "create_game",
+                    "description": "Start a new multiplayer lobby session, distinct from the single implicit game the other tools operate on, and receive a seat token for it",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {}
+                    }
+                },
+                {
+                    "name": "join_game",
+                    "description": "Claim the free seat in an existing multiplayer lobby session and receive its seat token",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "gameId": {
+                                "type": "string",
+                                "description": "The ID of the game to join"
+                            }
+                        },
+                        "required": ["gameId"]
+                    }
+                },
+                {
+                    "name": "list_games",
+                    "description": "List every known game and its status, whether created via new_game, create_game, or the implicit current game",
                     "inputSchema": {
                         "type": "object",
                         "properties": {}
                     }
+                },
+                {
+                    "name": "wait_for_update",
+                    "description": "Block (up to timeout_ms) until a game's state advances past since_version, to avoid busy-polling view_game_state",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "gameId": {
+                                "type": "string",
+                                "description": "The ID of the game to watch"
+                            },
+                            "since_version": {
+                                "type": "integer",
+                                "description": "Return immediately if the game's state version already exceeds this"
+                            },
+                            "timeout_ms": {
+                                "type": "integer",
+                                "description": "Maximum time to block waiting for a change (default 5000, max 30000)"
+                            }
+                        },
+                        "required": ["gameId", "since_version"]
+                    }
+                },
+                {
+                    "name": "get_state_if_changed",
+                    "description": "Immediately check whether a game's state has advanced past since_version, without blocking. Returns changed: false with no gameState if nothing changed",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "gameId": {
+                                "type": "string",
+                                "description": "The ID of the game to check"
+                            },
+                            "since_version": {
+                                "type": "integer",
+                                "description": "Return changed: false if the game's state version hasn't advanced past this"
+                            }
+                        },
+                        "required": ["gameId", "since_version"]
+                    }
+                },
+                {
+                    "name": "get_state_if_updated",
+                    "description": "Immediately check whether a game's updated_at timestamp has advanced past sinceUpdatedAt, without blocking. Like get_state_if_changed, but keyed off the raw database timestamp instead of GameState.version. Returns changed: false with no gameState if nothing changed",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "gameId": {
+                                "type": "string",
+                                "description": "The ID of the game to check"
+                            },
+                            "sinceUpdatedAt": {
+                                "type": "integer",
+                                "description": "Return changed: false if the game's updated_at hasn't advanced past this"
+                            }
+                        },
+                        "required": ["gameId", "sinceUpdatedAt"]
+                    }
+                },
+                {
+                    "name": "get_leaderboard",
+                    "description": "Get the top players by score (with win rate) across every finished single-AI-opponent game",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "limit": {
+                                "type": "integer",
+                                "description": "Maximum number of entries to return (default 10)"
+                            }
+                        }
+                    }
+                },
+                {
+                    "name": "reset_leaderboard",
+                    "description": "Wipe every recorded leaderboard tally and start a fresh competition. Gated the same way restart_game is.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {}
+                    }
+                },
+                {
+                    "name": "get_metrics",
+                    "description": "Get per-method call counts and latency histograms for this server, as JSON (default) or Prometheus text when format is 'prometheus'",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "format": {
+                                "type": "string",
+                                "description": "Output format: 'json' (default) or 'prometheus'",
+                                "enum": ["json", "prometheus"]
+                            }
+                        }
+                    }
+                },
+                {
+                    "name": "run_match",
+                    "description": "Simulate a full bot-vs-bot game with no human client in the loop, returning the move log and final result",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "playerX": {
+                                "type": "object",
+                                "description": "Strategy for X: {\"strategy\": \"random\"} or {\"strategy\": \"external\", \"command\": ..., \"args\": [...]} (spawns an arbitrary host process, so this strategy requires an admin-privileged caller -- connect with GAME_MCP_ADMIN_TOKEN, or an Admin-role token over HTTP). Defaults to random."
+                            },
+                            "playerO": {
+                                "type": "object",
+                                "description": "Strategy for O, same shape as playerX. Defaults to random."
+                            },
+                            "maxMoves": {
+                                "type": "integer",
+                                "description": "Safety valve against a match that never reaches a terminal state (default 9)"
+                            }
+                        }
+                    }
                 }
             ]
         }))
     }
 }
 
+fn env_u64(name: &str, default: u64) -> u64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Whether a `run_match` params object asks for the `external` bot strategy (for either side).
+/// See `McpServer::handle_run_match` for why that needs an admin-privileged caller.
+fn requests_external_bot(params: &Value) -> bool {
+    ["playerX", "playerO"].iter().any(|key| {
+        params
+            .get(key)
+            .and_then(|p| p.get("strategy"))
+            .and_then(Value::as_str)
+            == Some("external")
+    })
+}
+
+/// Parse a `MatchBot` from a `run_match` tool param: `{"strategy": "random"}` (the default when
+/// the value is absent) or `{"strategy": "external", "command": "...", "args": [...]}`.
+fn parse_bot(value: &Value) -> Result<super::match_runner::MatchBot, JsonRpcError> {
+    match value.get("strategy").and_then(Value::as_str) {
+        None | Some("random") => Ok(super::match_runner::MatchBot::Random),
+        Some("external") => {
+            let command = value["command"]
+                .as_str()
+                .ok_or_else(|| {
+                    JsonRpcError::invalid_params(
+                        "External bot requires a 'command' parameter".to_string(),
+                    )
+                })?
+                .to_string();
+            let args = value["args"]
+                .as_array()
+                .map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                .unwrap_or_default();
+            Ok(super::match_runner::MatchBot::External { command, args })
+        }
+        Some(other) => Err(JsonRpcError::invalid_params(format!(
+            "Unknown bot strategy '{}'",
+            other
+        ))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -336,4 +1095,365 @@ mod tests {
         let resp3 = server.handle_request(req3);
         assert!(resp3.contains(r#""id":3"#));
     }
+
+    #[test]
+    fn test_handle_notification_produces_no_response() {
+        let mut server = create_test_server();
+        let request = r#"{"jsonrpc":"2.0","method":"get_turn","params":{}}"#;
+
+        let response = server.handle_request(request);
+
+        assert!(response.is_empty());
+    }
+
+    #[test]
+    fn test_handle_notification_still_dispatches() {
+        let mut server = create_test_server();
+        let request = r#"{"jsonrpc":"2.0","method":"make_move","params":{"row":0,"col":0}}"#;
+
+        server.handle_request(request);
+
+        let result = server.dispatch("get_game_history", json!({})).unwrap();
+        assert_eq!(result["moves"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_handle_empty_batch_is_invalid_request() {
+        let mut server = create_test_server();
+        let response = server.handle_request("[]");
+
+        assert!(response.contains(r#""error""#));
+        assert!(response.contains(r#""code":-32600"#)); // INVALID_REQUEST
+    }
+
+    #[test]
+    fn test_handle_batch_returns_array_of_responses() {
+        let mut server = create_test_server();
+        let request = r#"[
+            {"jsonrpc":"2.0","id":1,"method":"view_game_state","params":{}},
+            {"jsonrpc":"2.0","id":2,"method":"get_turn","params":{}}
+        ]"#;
+
+        let response = server.handle_request(request);
+        let parsed: Value = serde_json::from_str(&response).unwrap();
+
+        let batch = parsed.as_array().unwrap();
+        assert_eq!(batch.len(), 2);
+        assert_eq!(batch[0]["id"], 1);
+        assert_eq!(batch[1]["id"], 2);
+    }
+
+    #[test]
+    fn test_handle_batch_all_notifications_produces_no_output() {
+        let mut server = create_test_server();
+        let request = r#"[
+            {"jsonrpc":"2.0","method":"make_move","params":{"row":0,"col":0}},
+            {"jsonrpc":"2.0","method":"taunt_player","params":{"message":"hi"}}
+        ]"#;
+
+        let response = server.handle_request(request);
+
+        assert!(response.is_empty());
+
+        let history = server.dispatch("get_game_history", json!({})).unwrap();
+        assert_eq!(history["moves"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_handle_batch_mixes_notifications_and_requests() {
+        let mut server = create_test_server();
+        let request = r#"[
+            {"jsonrpc":"2.0","method":"make_move","params":{"row":0,"col":0}},
+            {"jsonrpc":"2.0","id":1,"method":"get_turn","params":{}}
+        ]"#;
+
+        let response = server.handle_request(request);
+        let parsed: Value = serde_json::from_str(&response).unwrap();
+
+        let batch = parsed.as_array().unwrap();
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch[0]["id"], 1);
+    }
+
+    // Protocol version negotiation tests
+    #[test]
+    fn test_initialize_echoes_a_supported_version() {
+        let mut server = create_test_server();
+        let result = server
+            .dispatch("initialize", json!({"protocolVersion": "2024-11-05"}))
+            .unwrap();
+
+        assert_eq!(result["protocolVersion"], "2024-11-05");
+        assert_eq!(server.negotiated_version, Some("2024-11-05"));
+    }
+
+    #[test]
+    fn test_initialize_falls_back_to_newest_for_unknown_single_version() {
+        let mut server = create_test_server();
+        let result = server
+            .dispatch("initialize", json!({"protocolVersion": "1999-01-01"}))
+            .unwrap();
+
+        assert_eq!(
+            result["protocolVersion"],
+            *SUPPORTED_PROTOCOL_VERSIONS.last().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_initialize_picks_newest_overlap_from_supported_versions() {
+        let mut server = create_test_server();
+        let result = server
+            .dispatch(
+                "initialize",
+                json!({"protocolVersion": "2024-11-05", "supportedVersions": ["2024-11-05", "2025-03-26"]}),
+            )
+            .unwrap();
+
+        assert_eq!(result["protocolVersion"], "2025-03-26");
+    }
+
+    #[test]
+    fn test_initialize_no_overlap_is_invalid_request() {
+        let mut server = create_test_server();
+        let result = server.dispatch(
+            "initialize",
+            json!({"protocolVersion": "1999-01-01", "supportedVersions": ["1999-01-01"]}),
+        );
+
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert_eq!(err.code, INVALID_REQUEST);
+        assert!(err.data.is_some());
+    }
+
+    #[test]
+    fn test_initialize_missing_version_is_invalid_params() {
+        let mut server = create_test_server();
+        let result = server.dispatch("initialize", json!({}));
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().code,
+            super::super::protocol::INVALID_PARAMS
+        );
+    }
+
+    // Session handshake tests
+    #[test]
+    fn test_connect_without_configured_auth_issues_a_session_id() {
+        let mut server = create_test_server();
+        let result = server
+            .dispatch("connect", json!({"client_id": "client-a"}))
+            .unwrap();
+
+        assert!(result["sessionId"].is_string());
+    }
+
+    #[test]
+    fn test_calls_without_a_session_succeed_when_auth_is_not_configured() {
+        let mut server = create_test_server();
+        let result = server.dispatch("get_turn", json!({}));
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_connect_rejects_wrong_token_when_auth_is_configured() {
+        let mut server = create_test_server();
+        server.auth_secret = Some("s3cret".to_string());
+
+        let result = server.dispatch(
+            "connect",
+            json!({"client_id": "client-a", "token": "wrong"}),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().code, INVALID_REQUEST);
+    }
+
+    #[test]
+    fn test_connect_rejects_missing_client_id() {
+        let mut server = create_test_server();
+        let result = server.dispatch("connect", json!({}));
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().code, INVALID_REQUEST);
+    }
+
+    #[test]
+    fn test_call_without_session_is_rejected_when_auth_is_configured() {
+        let mut server = create_test_server();
+        server.auth_secret = Some("s3cret".to_string());
+
+        let result = server.dispatch("get_turn", json!({}));
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().code, INVALID_REQUEST);
+    }
+
+    #[test]
+    fn test_call_with_valid_session_succeeds_when_auth_is_configured() {
+        let mut server = create_test_server();
+        server.auth_secret = Some("s3cret".to_string());
+
+        let connected = server
+            .dispatch("connect", json!({"client_id": "client-a", "token": "s3cret"}))
+            .unwrap();
+        let session_id = connected["sessionId"].as_str().unwrap();
+
+        let result = server.dispatch("get_turn", json!({"sessionId": session_id}));
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_reconnect_with_same_client_id_resumes_its_game() {
+        let mut server = create_test_server();
+        server.auth_secret = Some("s3cret".to_string());
+
+        let connected = server
+            .dispatch("connect", json!({"client_id": "client-a", "token": "s3cret"}))
+            .unwrap();
+        let session_id = connected["sessionId"].as_str().unwrap().to_string();
+
+        let created = server
+            .dispatch(
+                "create_game",
+                json!({"sessionId": session_id}),
+            )
+            .unwrap();
+        let game_id = created["gameId"].as_str().unwrap().to_string();
+
+        let reconnected = server
+            .dispatch("connect", json!({"client_id": "client-a", "token": "s3cret"}))
+            .unwrap();
+
+        assert_eq!(reconnected["sessionId"], session_id);
+        assert_eq!(reconnected["gameId"], game_id);
+    }
+
+    // Metrics tests
+    #[test]
+    fn test_get_metrics_tracks_calls_per_method() {
+        let mut server = create_test_server();
+        server.dispatch("get_turn", json!({})).unwrap();
+        server.dispatch("get_turn", json!({})).unwrap();
+        server.dispatch("make_move", json!({"row": 5, "col": 0})).ok();
+
+        let result = server.dispatch("get_metrics", json!({})).unwrap();
+        assert_eq!(result["methods"]["get_turn"]["total"], 2);
+        assert_eq!(result["methods"]["make_move"]["error"], 1);
+    }
+
+    #[test]
+    fn test_get_metrics_prometheus_format() {
+        let mut server = create_test_server();
+        server.dispatch("get_turn", json!({})).unwrap();
+
+        let result = server
+            .dispatch("get_metrics", json!({"format": "prometheus"}))
+            .unwrap();
+
+        let text = result.as_str().unwrap();
+        assert!(text.contains("mcp_method_calls_total"));
+        assert!(text.contains("get_turn"));
+    }
+
+    #[test]
+    fn test_get_metrics_counts_itself() {
+        let mut server = create_test_server();
+        server.dispatch("get_metrics", json!({})).unwrap();
+
+        let result = server.dispatch("get_metrics", json!({})).unwrap();
+        assert_eq!(result["methods"]["get_metrics"]["total"], 1);
+    }
+
+    // run_match tests
+    #[test]
+    fn test_run_match_with_two_random_bots() {
+        let mut server = create_test_server();
+        let result = server.dispatch("run_match", json!({})).unwrap();
+
+        assert!(result["gameId"].is_string());
+        assert!(!result["moves"].as_array().unwrap().is_empty());
+        assert_ne!(result["status"], "InProgress");
+    }
+
+    #[test]
+    fn test_run_match_rejects_unknown_strategy() {
+        let mut server = create_test_server();
+        let result = server.dispatch(
+            "run_match",
+            json!({"playerX": {"strategy": "nonsense"}}),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().code,
+            super::super::protocol::INVALID_PARAMS
+        );
+    }
+
+    #[test]
+    fn test_run_match_rejects_external_strategy_without_admin() {
+        let mut server = create_test_server();
+        let result = server.dispatch(
+            "run_match",
+            json!({"playerX": {"strategy": "external", "command": "/bin/sh"}}),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().code, INVALID_REQUEST);
+    }
+
+    #[test]
+    fn test_run_match_allows_external_strategy_for_an_admin_session() {
+        let mut server = create_test_server();
+        server.admin_secret = Some("admin-s3cret".to_string());
+
+        let connected = server
+            .dispatch(
+                "connect",
+                json!({"client_id": "client-a", "token": "admin-s3cret"}),
+            )
+            .unwrap();
+        let session_id = connected["sessionId"].as_str().unwrap().to_string();
+
+        let result = server.dispatch(
+            "run_match",
+            json!({
+                "sessionId": session_id,
+                "playerX": {"strategy": "external", "command": "/no/such/bot-binary"},
+            }),
+        );
+
+        // Rejected for failing to spawn the (nonexistent) bot, not for lacking admin privilege.
+        assert!(result.is_err());
+        assert_ne!(result.unwrap_err().code, INVALID_REQUEST);
+    }
+
+    #[test]
+    fn test_run_match_allows_external_strategy_with_admin_override() {
+        let mut server = create_test_server();
+        server.admin_override = true;
+
+        let result = server.dispatch(
+            "run_match",
+            json!({"playerX": {"strategy": "external", "command": "/no/such/bot-binary"}}),
+        );
+
+        assert!(result.is_err());
+        assert_ne!(result.unwrap_err().code, INVALID_REQUEST);
+    }
+
+    #[test]
+    fn test_run_match_still_allows_random_strategy_without_admin() {
+        let mut server = create_test_server();
+        server.admin_secret = Some("admin-s3cret".to_string());
+
+        let result = server.dispatch("run_match", json!({})).unwrap();
+
+        assert!(result["gameId"].is_string());
+    }
 }
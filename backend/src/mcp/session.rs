@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+/// A client's authenticated connection to the MCP server, created by `connect` and looked up by
+/// every subsequent call's `sessionId`. Reconnecting with the same `client_id` reuses the
+/// existing session (and whatever game it was last associated with) instead of starting fresh.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub client_id: String,
+    pub game_id: Option<String>,
+    /// Whether this session connected with the admin token (`GAME_MCP_ADMIN_TOKEN`), gating
+    /// tool calls that can affect the host rather than just the game -- see
+    /// `McpServer::caller_is_admin`.
+    pub is_admin: bool,
+}
+
+/// Tracks sessions for servers that have auth configured (see `McpServer`'s `connect` handler).
+/// Sessions are keyed by an opaque id handed out from `connect`; a `client_id` index lets a
+/// reconnecting client find and resume its existing session instead of getting a new one.
+#[derive(Default)]
+pub struct SessionStore {
+    sessions: HashMap<String, Session>,
+    by_client_id: HashMap<String, String>,
+}
+
+impl SessionStore {
+    /// Create or resume a session for `client_id`, returning its session id. `is_admin` reflects
+    /// whether this particular `connect` call presented the admin token; resuming with a
+    /// different privilege level than last time updates the session to match (most recent
+    /// credential wins, rather than leaving a stale admin session privileged after a client
+    /// reconnects without the admin token, or vice versa).
+    pub fn connect(&mut self, client_id: &str, is_admin: bool) -> String {
+        if let Some(session_id) = self.by_client_id.get(client_id) {
+            let session_id = session_id.clone();
+            if let Some(session) = self.sessions.get_mut(&session_id) {
+                session.is_admin = is_admin;
+            }
+            return session_id;
+        }
+
+        let session_id = uuid::Uuid::new_v4().to_string();
+        self.sessions.insert(
+            session_id.clone(),
+            Session {
+                client_id: client_id.to_string(),
+                game_id: None,
+                is_admin,
+            },
+        );
+        self.by_client_id.insert(client_id.to_string(), session_id.clone());
+        session_id
+    }
+
+    /// Look up a session by id, for validating that a call carries a live session.
+    pub fn get(&self, session_id: &str) -> Option<&Session> {
+        self.sessions.get(session_id)
+    }
+
+    /// Whether `session_id` resolves to a session that connected with the admin token. Unknown
+    /// session ids are treated as non-admin rather than erroring -- callers that care whether
+    /// the session exists at all should go through `get`/`authorize` first.
+    pub fn is_admin(&self, session_id: &str) -> bool {
+        self.sessions.get(session_id).is_some_and(|s| s.is_admin)
+    }
+
+    /// Remember the game a session is currently playing, so a later reconnect can resume it.
+    pub fn set_game(&mut self, session_id: &str, game_id: String) {
+        if let Some(session) = self.sessions.get_mut(session_id) {
+            session.game_id = Some(game_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connect_issues_a_new_session_id() {
+        let mut store = SessionStore::default();
+        let session_id = store.connect("client-a", false);
+
+        assert!(store.get(&session_id).is_some());
+    }
+
+    #[test]
+    fn test_reconnect_with_same_client_id_resumes_the_session() {
+        let mut store = SessionStore::default();
+        let first = store.connect("client-a", false);
+        store.set_game(&first, "game-1".to_string());
+
+        let second = store.connect("client-a", false);
+
+        assert_eq!(first, second);
+        assert_eq!(
+            store.get(&second).unwrap().game_id,
+            Some("game-1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_different_client_ids_get_different_sessions() {
+        let mut store = SessionStore::default();
+        let a = store.connect("client-a", false);
+        let b = store.connect("client-b", false);
+
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_unknown_session_id_is_not_found() {
+        let store = SessionStore::default();
+        assert!(store.get("nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_connect_with_admin_marks_the_session_admin() {
+        let mut store = SessionStore::default();
+        let session_id = store.connect("client-a", true);
+
+        assert!(store.get(&session_id).unwrap().is_admin);
+        assert!(store.is_admin(&session_id));
+    }
+
+    #[test]
+    fn test_reconnect_without_admin_downgrades_the_session() {
+        let mut store = SessionStore::default();
+        let session_id = store.connect("client-a", true);
+        assert!(store.is_admin(&session_id));
+
+        store.connect("client-a", false);
+
+        assert!(!store.is_admin(&session_id));
+    }
+
+    #[test]
+    fn test_unknown_session_id_is_not_admin() {
+        let store = SessionStore::default();
+        assert!(!store.is_admin("nonexistent"));
+    }
+}
@@ -3,13 +3,16 @@
 use super::protocol::JsonRpcError;
 use crate::game::manager::GameManager;
 use serde_json::{Value, json};
-use shared::{GameError, MoveSource};
+use shared::{AiDifficulty, EmoteEnum, GameError, MoveSource};
 
-/// Handle the view_game_state tool call
-pub fn view_game_state(manager: &mut GameManager, _params: Value) -> Result<Value, JsonRpcError> {
-    let game = manager
-        .get_game_state()
-        .map_err(|e| JsonRpcError::internal_error(format!("Failed to get game state: {}", e)))?;
+/// Handle the view_game_state tool call. An optional `gameId` targets a specific session
+/// instead of the implicit current game.
+pub fn view_game_state(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
+    let game_id = params["gameId"].as_str();
+    let game = manager.get_game_state_in(game_id).map_err(|e| match e {
+        GameError::GameNotFound => JsonRpcError::invalid_params(format!("No such game: {}", e)),
+        _ => JsonRpcError::internal_error(format!("Failed to get game state: {}", e)),
+    })?;
 
     Ok(json!({
         "id": game.id,
@@ -33,17 +36,22 @@ pub fn view_game_state(manager: &mut GameManager, _params: Value) -> Result<Valu
                 shared::Player::O => "Won_O",
             },
             shared::GameStatus::Draw => "Draw",
+            shared::GameStatus::Abandoned => "Abandoned",
         },
         "moveHistory": game.move_history,
         "taunts": game.taunts,
+        "stateVersion": manager.state_version(&game.id),
     }))
 }
 
-/// Handle the get_turn tool call
-pub fn get_turn(manager: &mut GameManager, _params: Value) -> Result<Value, JsonRpcError> {
-    let game = manager
-        .get_game_state()
-        .map_err(|e| JsonRpcError::internal_error(format!("Failed to get game state: {}", e)))?;
+/// Handle the get_turn tool call. An optional `gameId` targets a specific session instead of
+/// the implicit current game.
+pub fn get_turn(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
+    let game_id = params["gameId"].as_str();
+    let game = manager.get_game_state_in(game_id).map_err(|e| match e {
+        GameError::GameNotFound => JsonRpcError::invalid_params(format!("No such game: {}", e)),
+        _ => JsonRpcError::internal_error(format!("Failed to get game state: {}", e)),
+    })?;
 
     let current_turn_str = match game.current_turn {
         shared::Player::X => "X",
@@ -57,7 +65,8 @@ pub fn get_turn(manager: &mut GameManager, _params: Value) -> Result<Value, Json
     }))
 }
 
-/// Handle the make_move tool call
+/// Handle the make_move tool call. An optional `gameId` targets a specific session instead of
+/// the implicit current game.
 pub fn make_move(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
     let row = params["row"].as_u64().ok_or_else(|| {
         JsonRpcError::invalid_params("Missing or invalid 'row' parameter".to_string())
@@ -67,8 +76,10 @@ pub fn make_move(manager: &mut GameManager, params: Value) -> Result<Value, Json
         JsonRpcError::invalid_params("Missing or invalid 'col' parameter".to_string())
     })? as u8;
 
+    let game_id = params["gameId"].as_str();
+
     let game = manager
-        .make_move(row, col, MoveSource::MCP)
+        .make_move_in_game(game_id, row, col, MoveSource::MCP)
         .map_err(|e| match e {
             GameError::OutOfBounds { .. } => {
                 JsonRpcError::invalid_params(format!("Move out of bounds: {}", e))
@@ -79,6 +90,12 @@ pub fn make_move(manager: &mut GameManager, params: Value) -> Result<Value, Json
             GameError::GameOver { .. } => {
                 JsonRpcError::invalid_params(format!("Game is already over: {}", e))
             }
+            GameError::GameNotFound => {
+                JsonRpcError::invalid_params(format!("No such game: {}", e))
+            }
+            GameError::TurnExpired { .. } => {
+                JsonRpcError::invalid_params(format!("Turn expired: {}", e))
+            }
             _ => JsonRpcError::internal_error(format!("Failed to make move: {}", e)),
         })?;
 
@@ -98,13 +115,15 @@ pub fn make_move(manager: &mut GameManager, params: Value) -> Result<Value, Json
                     shared::Player::O => "Won_O",
                 },
                 shared::GameStatus::Draw => "Draw",
+                shared::GameStatus::Abandoned => "Abandoned",
             },
         },
         "message": "Move made successfully"
     }))
 }
 
-/// Handle the taunt_player tool call
+/// Handle the taunt_player tool call. An optional `gameId` targets a specific session instead
+/// of the implicit current game.
 pub fn taunt_player(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
     let message = params["message"]
         .as_str()
@@ -113,9 +132,16 @@ pub fn taunt_player(manager: &mut GameManager, params: Value) -> Result<Value, J
         })?
         .to_string();
 
+    let game_id = params["gameId"].as_str();
+
     manager
-        .add_taunt(message, shared::MoveSource::MCP)
-        .map_err(|e| JsonRpcError::internal_error(format!("Failed to add taunt: {}", e)))?;
+        .add_taunt_in_game(game_id, message, shared::MoveSource::MCP)
+        .map_err(|e| match e {
+            GameError::GameNotFound => {
+                JsonRpcError::invalid_params(format!("No such game: {}", e))
+            }
+            _ => JsonRpcError::internal_error(format!("Failed to add taunt: {}", e)),
+        })?;
 
     Ok(json!({
         "success": true,
@@ -123,10 +149,16 @@ pub fn taunt_player(manager: &mut GameManager, params: Value) -> Result<Value, J
     }))
 }
 
-/// Handle the restart_game tool call
-pub fn restart_game(manager: &mut GameManager, _params: Value) -> Result<Value, JsonRpcError> {
+/// Handle the restart_game tool call. An optional `difficulty` param ("Random", "Intermediate",
+/// or "Perfect") switches the built-in AI's strength for the new game; omitting it keeps
+/// whatever difficulty was already in effect.
+pub fn restart_game(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
+    let difficulty = params["difficulty"]
+        .as_str()
+        .map(|name| crate::game::bot::Difficulty::parse(Some(name)));
+
     let game = manager
-        .restart_game()
+        .restart_game_with_difficulty(difficulty)
         .map_err(|e| JsonRpcError::internal_error(format!("Failed to restart game: {}", e)))?;
 
     Ok(json!({
@@ -152,17 +184,553 @@ pub fn restart_game(manager: &mut GameManager, _params: Value) -> Result<Value,
     }))
 }
 
-/// Handle the get_game_history tool call
-pub fn get_game_history(manager: &mut GameManager, _params: Value) -> Result<Value, JsonRpcError> {
+/// Handle the request_rematch tool call: start a fresh game that keeps the current game's
+/// human/AI seat assignment but alternates who moves first, the standard "play again" flow.
+/// Unlike `restart_game`, only succeeds once the current game has actually ended.
+pub fn request_rematch(manager: &mut GameManager, _params: Value) -> Result<Value, JsonRpcError> {
+    let game = manager.request_rematch().map_err(|e| match e {
+        GameError::GameStillInProgress => {
+            JsonRpcError::invalid_params(format!("Cannot start a rematch: {}", e))
+        }
+        GameError::GameNotFound => JsonRpcError::invalid_params(format!("No such game: {}", e)),
+        _ => JsonRpcError::internal_error(format!("Failed to start rematch: {}", e)),
+    })?;
+
+    Ok(json!({
+        "success": true,
+        "gameState": {
+            "id": game.id,
+            "board": game.board,
+            "currentTurn": match game.current_turn {
+                shared::Player::X => "X",
+                shared::Player::O => "O",
+            },
+            "humanPlayer": match game.human_player {
+                shared::Player::X => "X",
+                shared::Player::O => "O",
+            },
+            "aiPlayer": match game.ai_player {
+                shared::Player::X => "X",
+                shared::Player::O => "O",
+            },
+            "status": "InProgress",
+        },
+        "previousGameId": game.previous_game_id,
+        "message": "Rematch started"
+    }))
+}
+
+/// Handle the set_turn_limit tool call: set (or, with `turnLimitSecs` omitted/null, clear) a
+/// session's per-turn time budget. Once set, a move attempted after the budget elapses forfeits
+/// the game to the opponent instead of being accepted. An optional `gameId` targets a specific
+/// session instead of the implicit current game.
+pub fn set_turn_limit(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
+    let game_id = params["gameId"].as_str();
+    let turn_limit_secs = params["turnLimitSecs"].as_u64().map(|v| v as u32);
+
+    let game = manager
+        .set_turn_limit_in_game(game_id, turn_limit_secs)
+        .map_err(|e| match e {
+            GameError::GameNotFound => JsonRpcError::invalid_params(format!("No such game: {}", e)),
+            _ => JsonRpcError::internal_error(format!("Failed to set turn limit: {}", e)),
+        })?;
+
+    Ok(json!({
+        "success": true,
+        "turnLimitSecs": game.turn_limit_secs,
+    }))
+}
+
+/// Handle the check_turn_timeout tool call: proactively resolve a session's turn-clock the same
+/// way `make_move` would, without an (otherwise rejected) move attempt to trigger it. Useful for
+/// a client that wants to notice "my opponent timed out" before trying to move itself.
+pub fn check_turn_timeout(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
+    let game_id = params["gameId"].as_str();
+
+    let game = manager
+        .check_turn_timeout_in(game_id)
+        .map_err(|e| match e {
+            GameError::GameNotFound => JsonRpcError::invalid_params(format!("No such game: {}", e)),
+            _ => JsonRpcError::internal_error(format!("Failed to check turn timeout: {}", e)),
+        })?;
+
+    Ok(json!({
+        "status": match &game.status {
+            shared::GameStatus::InProgress => "InProgress",
+            shared::GameStatus::Won(p) => match p {
+                shared::Player::X => "Won_X",
+                shared::Player::O => "Won_O",
+            },
+            shared::GameStatus::Draw => "Draw",
+            shared::GameStatus::Abandoned => "Abandoned",
+        },
+    }))
+}
+
+/// Handle the compute_ai_move tool call: the MCTS-computed move for the current game's AI
+/// opponent, without applying it. A client can preview the AI's move, or drive it itself
+/// instead of relying on `make_move`'s automatic auto-play.
+pub fn compute_ai_move(manager: &mut GameManager, _params: Value) -> Result<Value, JsonRpcError> {
+    let (row, col) = manager.compute_ai_move().map_err(|e| match e {
+        GameError::GameNotFound => JsonRpcError::invalid_params(format!("No such game: {}", e)),
+        GameError::WrongTurn { .. } | GameError::GameOver { .. } => {
+            JsonRpcError::invalid_params(format!("Cannot compute an AI move: {}", e))
+        }
+        _ => JsonRpcError::internal_error(format!("Failed to compute AI move: {}", e)),
+    })?;
+
+    Ok(json!({ "row": row, "col": col }))
+}
+
+/// Handle the set_ai_difficulty tool call: set (or, with `difficulty` omitted/null, clear) a
+/// session's `AiDifficulty`. Once set, the AI's turn is chosen by `GameState::ai_choose_move`'s
+/// ranked minimax pool instead of the legacy `Bot` auto-player. An optional `gameId` targets a
+/// specific session instead of the implicit current game.
+pub fn set_ai_difficulty(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
+    let game_id = params["gameId"].as_str();
+    let difficulty = match params["difficulty"].as_str() {
+        Some(name) => Some(AiDifficulty::parse(name).ok_or_else(|| {
+            JsonRpcError::invalid_params(format!("Unknown AI difficulty: {name}"))
+        })?),
+        None => None,
+    };
+
+    let game = manager
+        .set_ai_difficulty_in_game(game_id, difficulty)
+        .map_err(|e| match e {
+            GameError::GameNotFound => JsonRpcError::invalid_params(format!("No such game: {}", e)),
+            _ => JsonRpcError::internal_error(format!("Failed to set AI difficulty: {}", e)),
+        })?;
+
+    Ok(json!({
+        "success": true,
+        "aiDifficulty": game.ai_difficulty.map(|d| d.as_str()),
+    }))
+}
+
+/// Default and maximum page size for `get_game_history` when `limit` is omitted/exceeds the cap.
+const DEFAULT_HISTORY_LIMIT: u64 = 50;
+const MAX_HISTORY_LIMIT: u64 = 200;
+
+/// Handle the get_game_history tool call: a bounded, filterable slice of a session's move
+/// history, paged by 0-based move index the same way `get_taunts` pages by message id.
+/// `before`/`after` window around an index; omitting both windows the whole history. The most
+/// recent `limit` moves in that window are returned, along with a `hasMore` flag and the
+/// window's bounds. `source` restricts to moves from a single `MoveSource` ("UI" or "MCP").
+/// `includeTaunts` additionally returns the game's (unpaginated) taunts alongside the moves. An
+/// optional `gameId` targets a specific session instead of the implicit current game.
+pub fn get_game_history(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
+    let game_id = params["gameId"].as_str();
+    let game = manager.get_game_state_in(game_id).map_err(|e| match e {
+        GameError::GameNotFound => JsonRpcError::invalid_params(format!("No such game: {}", e)),
+        _ => JsonRpcError::internal_error(format!("Failed to get game state: {}", e)),
+    })?;
+
+    let total = game.move_history.len() as u64;
+
+    let before = params["before"].as_u64();
+    if let Some(before) = before
+        && before > total
+    {
+        return Err(JsonRpcError::invalid_params(format!(
+            "'before' index {} is out of range (history has {} moves)",
+            before, total
+        )));
+    }
+
+    let after = params["after"].as_u64();
+    if let Some(after) = after
+        && after >= total
+    {
+        return Err(JsonRpcError::invalid_params(format!(
+            "'after' index {} is out of range (history has {} moves)",
+            after, total
+        )));
+    }
+
+    let limit = params["limit"]
+        .as_u64()
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .min(MAX_HISTORY_LIMIT);
+
+    let source = match params["source"].as_str() {
+        Some("UI") => Some(MoveSource::UI),
+        Some("MCP") => Some(MoveSource::MCP),
+        Some("AI") => Some(MoveSource::AI),
+        Some(other) => {
+            return Err(JsonRpcError::invalid_params(format!(
+                "Unknown 'source' filter: {}",
+                other
+            )));
+        }
+        None => None,
+    };
+
+    let window_start = after.map(|a| a + 1).unwrap_or(0) as usize;
+    let window_end = before.unwrap_or(total) as usize;
+    let window = game
+        .move_history
+        .get(window_start.min(window_end)..window_end)
+        .unwrap_or_default();
+
+    let filtered: Vec<&shared::Move> = window
+        .iter()
+        .filter(|m| source.as_ref().is_none_or(|s| m.source.as_ref() == Some(s)))
+        .collect();
+
+    let has_more = filtered.len() as u64 > limit;
+    let page_start = filtered.len().saturating_sub(limit as usize);
+    let moves = &filtered[page_start..];
+
+    let mut result = json!({
+        "moves": moves,
+        "hasMore": has_more,
+        "windowStart": window_start,
+        "windowEnd": window_end,
+    });
+
+    if params["includeTaunts"].as_bool().unwrap_or(false) {
+        result["taunts"] = json!(game.taunts);
+    }
+
+    Ok(result)
+}
+
+/// Handle the send_emote tool call: a predefined quick emote (see `EmoteEnum`), rejected with
+/// `INVALID_PARAMS` if `emote` isn't one of its variant names (e.g. "Cool"), instead of
+/// `taunt_player`'s free-form text.
+pub fn send_emote(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
+    let emote: EmoteEnum = serde_json::from_value(params["emote"].clone()).map_err(|_| {
+        JsonRpcError::invalid_params(format!(
+            "Missing or unrecognized 'emote' parameter; expected one of {:?}",
+            EmoteEnum::ALL.map(|e| e.as_str())
+        ))
+    })?;
+
+    let game_id = params["gameId"].as_str();
+
+    manager
+        .add_emote_in_game(game_id, emote, MoveSource::MCP)
+        .map_err(|e| match e {
+            GameError::GameNotFound => {
+                JsonRpcError::invalid_params(format!("No such game: {}", e))
+            }
+            _ => JsonRpcError::internal_error(format!("Failed to send emote: {}", e)),
+        })?;
+
+    Ok(json!({
+        "success": true,
+        "message": "Emote sent successfully"
+    }))
+}
+
+/// Default page size for `get_taunts` when `limit` is omitted.
+const DEFAULT_TAUNT_LIMIT: u64 = 50;
+
+/// Handle the get_taunts tool call: a bounded, ordered slice of a game's chat/taunt history, for
+/// CHATHISTORY-style scroll-back instead of loading it all via `get_game_history`. `before`/
+/// `after` page around a message id; omitting both returns the most recent `limit` messages.
+pub fn get_taunts(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
+    let game_id = params["gameId"].as_str();
+    let before = params["before"].as_u64();
+    let after = params["after"].as_u64();
+    let limit = params["limit"].as_u64().unwrap_or(DEFAULT_TAUNT_LIMIT) as u32;
+
+    let taunts = manager
+        .get_taunts(game_id, before, after, limit)
+        .map_err(|e| match e {
+            GameError::GameNotFound => JsonRpcError::invalid_params(format!("No such game: {}", e)),
+            _ => JsonRpcError::internal_error(format!("Failed to get taunts: {}", e)),
+        })?;
+
+    Ok(json!({ "taunts": taunts }))
+}
+
+/// Handle the get_recent_emotes tool call: like `get_taunts`, but pre-filtered down to quick
+/// emotes (see `EmoteEnum`) so the caller can render reaction icons without sifting free-text
+/// taunts out on its own.
+pub fn get_recent_emotes(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
+    let game_id = params["gameId"].as_str();
+    let limit = params["limit"].as_u64().unwrap_or(DEFAULT_TAUNT_LIMIT) as u32;
+
+    let emotes = manager
+        .get_recent_emotes_in(game_id, limit)
+        .map_err(|e| match e {
+            GameError::GameNotFound => JsonRpcError::invalid_params(format!("No such game: {}", e)),
+            _ => JsonRpcError::internal_error(format!("Failed to get recent emotes: {}", e)),
+        })?;
+
+    Ok(json!({
+        "emotes": emotes.iter().map(|(emote, taunt)| json!({
+            "emote": emote.as_str(),
+            "timestamp": taunt.timestamp,
+            "source": taunt.sender,
+        })).collect::<Vec<_>>(),
+    }))
+}
+
+/// Default and maximum bound (ms) for `wait_for_update`'s `timeout_ms` param, so a forgetful or
+/// malicious client can't park a dispatch thread indefinitely.
+const DEFAULT_WAIT_TIMEOUT_MS: u64 = 5_000;
+const MAX_WAIT_TIMEOUT_MS: u64 = 30_000;
+
+/// Handle the wait_for_update tool call: long-polls for a game's state to advance past
+/// `since_version`, instead of making the caller busy-poll `view_game_state` on a timer.
+pub fn wait_for_update(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
+    let game_id = require_str(&params, "gameId")?;
+
+    let since_version = params["since_version"].as_u64().ok_or_else(|| {
+        JsonRpcError::invalid_params("Missing or invalid 'since_version' parameter".to_string())
+    })?;
+
+    let timeout_ms = params["timeout_ms"]
+        .as_u64()
+        .unwrap_or(DEFAULT_WAIT_TIMEOUT_MS)
+        .min(MAX_WAIT_TIMEOUT_MS);
+
+    let (game, version, changed) = manager
+        .wait_for_update(&game_id, since_version, timeout_ms)
+        .map_err(|e| match e {
+            GameError::GameNotFound => JsonRpcError::invalid_params(format!("No such game: {}", e)),
+            _ => JsonRpcError::internal_error(format!("Failed to wait for update: {}", e)),
+        })?;
+
+    if !changed {
+        return Ok(json!({
+            "changed": false,
+            "stateVersion": version,
+            "message": format!("No change, still at version {}", version),
+        }));
+    }
+
+    Ok(json!({
+        "changed": true,
+        "stateVersion": version,
+        "gameState": {
+            "id": game.id,
+            "board": game.board,
+            "currentTurn": match game.current_turn {
+                shared::Player::X => "X",
+                shared::Player::O => "O",
+            },
+            "status": match &game.status {
+                shared::GameStatus::InProgress => "InProgress",
+                shared::GameStatus::Won(p) => match p {
+                    shared::Player::X => "Won_X",
+                    shared::Player::O => "Won_O",
+                },
+                shared::GameStatus::Draw => "Draw",
+                shared::GameStatus::Abandoned => "Abandoned",
+            },
+        },
+    }))
+}
+
+/// Handle the get_state_if_changed tool call: an immediate, non-blocking check of whether a
+/// game has advanced past `since_version`, for a caller that wants to poll on its own schedule
+/// instead of long-polling via `wait_for_update`.
+pub fn get_state_if_changed(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
+    let game_id = require_str(&params, "gameId")?;
+
+    let since_version = params["since_version"].as_u64().ok_or_else(|| {
+        JsonRpcError::invalid_params("Missing or invalid 'since_version' parameter".to_string())
+    })?;
+
+    let (game, version, changed) = manager
+        .wait_for_update(&game_id, since_version, 0)
+        .map_err(|e| match e {
+            GameError::GameNotFound => JsonRpcError::invalid_params(format!("No such game: {}", e)),
+            _ => JsonRpcError::internal_error(format!("Failed to check game state: {}", e)),
+        })?;
+
+    if !changed {
+        return Ok(json!({
+            "changed": false,
+            "stateVersion": version,
+        }));
+    }
+
+    Ok(json!({
+        "changed": true,
+        "stateVersion": version,
+        "gameState": {
+            "id": game.id,
+            "board": game.board,
+            "currentTurn": match game.current_turn {
+                shared::Player::X => "X",
+                shared::Player::O => "O",
+            },
+            "status": match &game.status {
+                shared::GameStatus::InProgress => "InProgress",
+                shared::GameStatus::Won(p) => match p {
+                    shared::Player::X => "Won_X",
+                    shared::Player::O => "Won_O",
+                },
+                shared::GameStatus::Draw => "Draw",
+                shared::GameStatus::Abandoned => "Abandoned",
+            },
+        },
+    }))
+}
+
+/// Handle the get_state_if_updated tool call: like `get_state_if_changed`, but keyed off the
+/// database's raw `updated_at` timestamp (`sinceUpdatedAt`) instead of `GameState::version`, for
+/// a caller that only kept the former from its last fetch.
+pub fn get_state_if_updated(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
+    let game_id = require_str(&params, "gameId")?;
+
+    let since_updated_at = params["sinceUpdatedAt"].as_i64().ok_or_else(|| {
+        JsonRpcError::invalid_params("Missing or invalid 'sinceUpdatedAt' parameter".to_string())
+    })?;
+
+    let (game, updated_at) = manager
+        .get_state_if_updated(&game_id, since_updated_at)
+        .map_err(|e| match e {
+            GameError::GameNotFound => JsonRpcError::invalid_params(format!("No such game: {}", e)),
+            _ => JsonRpcError::internal_error(format!("Failed to check game state: {}", e)),
+        })?;
+
+    let Some(game) = game else {
+        return Ok(json!({
+            "changed": false,
+            "updatedAt": updated_at,
+        }));
+    };
+
+    Ok(json!({
+        "changed": true,
+        "updatedAt": updated_at,
+        "gameState": {
+            "id": game.id,
+            "board": game.board,
+            "currentTurn": match game.current_turn {
+                shared::Player::X => "X",
+                shared::Player::O => "O",
+            },
+            "status": match &game.status {
+                shared::GameStatus::InProgress => "InProgress",
+                shared::GameStatus::Won(p) => match p {
+                    shared::Player::X => "Won_X",
+                    shared::Player::O => "Won_O",
+                },
+                shared::GameStatus::Draw => "Draw",
+                shared::GameStatus::Abandoned => "Abandoned",
+            },
+        },
+    }))
+}
+
+/// Extract a required string parameter, for the multi-session lobby tools below
+fn require_str(params: &Value, key: &str) -> Result<String, JsonRpcError> {
+    params[key]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| JsonRpcError::invalid_params(format!("Missing or invalid '{}' parameter", key)))
+}
+
+/// Handle the create_game tool call: starts a new, independent multiplayer lobby session
+pub fn create_game(manager: &mut GameManager, _params: Value) -> Result<Value, JsonRpcError> {
+    let (game, seat, token) = manager
+        .create_game()
+        .map_err(|e| JsonRpcError::internal_error(format!("Failed to create game: {}", e)))?;
+
+    Ok(json!({
+        "gameId": game.id,
+        "seat": match seat {
+            shared::Player::X => "X",
+            shared::Player::O => "O",
+        },
+        "token": token,
+    }))
+}
+
+/// Handle the new_game tool call: starts a brand-new AI-opponent game under a fresh id,
+/// without touching the implicit current game `make_move`/`view_game_state` fall back to when
+/// `gameId` is omitted. Lets a client juggle several independent AI games concurrently,
+/// addressing each afterwards via `make_move`/`view_game_state`'s optional `gameId` param.
+pub fn new_game(manager: &mut GameManager, _params: Value) -> Result<Value, JsonRpcError> {
     let game = manager
-        .get_game_state()
-        .map_err(|e| JsonRpcError::internal_error(format!("Failed to get game state: {}", e)))?;
+        .new_game()
+        .map_err(|e| JsonRpcError::internal_error(format!("Failed to create game: {}", e)))?;
+
+    Ok(json!({ "gameId": game.id }))
+}
+
+/// Handle the join_game tool call: claims the free seat in an existing lobby session
+pub fn join_game(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
+    let game_id = require_str(&params, "gameId")?;
+
+    let (game, seat, token) = manager.join_game(&game_id).map_err(|e| match e {
+        GameError::GameNotFound => JsonRpcError::invalid_params(format!("No such game: {}", e)),
+        _ => JsonRpcError::internal_error(format!("Failed to join game: {}", e)),
+    })?;
+
+    Ok(json!({
+        "gameId": game.id,
+        "seat": match seat {
+            shared::Player::X => "X",
+            shared::Player::O => "O",
+        },
+        "token": token,
+    }))
+}
+
+/// Handle the list_games tool call: lists every known game, regardless of whether it was
+/// started via `new_game`, `create_game`, or the implicit current game
+pub fn list_games(manager: &mut GameManager, _params: Value) -> Result<Value, JsonRpcError> {
+    let games = manager
+        .list_games()
+        .map_err(|e| JsonRpcError::internal_error(format!("Failed to list games: {}", e)))?;
+
+    Ok(json!({
+        "games": games.iter().map(|game| json!({
+            "gameId": game.id,
+            "status": match &game.status {
+                shared::GameStatus::InProgress => "InProgress",
+                shared::GameStatus::Won(p) => match p {
+                    shared::Player::X => "Won_X",
+                    shared::Player::O => "Won_O",
+                },
+                shared::GameStatus::Draw => "Draw",
+                shared::GameStatus::Abandoned => "Abandoned",
+            },
+        })).collect::<Vec<_>>(),
+    }))
+}
+
+/// Handle the get_leaderboard tool call: the top players by wins, with win rate, across every
+/// finished single-AI-opponent game
+pub fn get_leaderboard(manager: &mut GameManager, params: Value) -> Result<Value, JsonRpcError> {
+    let limit = params["limit"].as_u64().unwrap_or(10) as u32;
+
+    let entries = manager
+        .get_leaderboard(limit)
+        .map_err(|e| JsonRpcError::internal_error(format!("Failed to get leaderboard: {}", e)))?;
 
     Ok(json!({
-        "moves": game.move_history
+        "leaderboard": entries.iter().map(|entry| json!({
+            "playerId": entry.player_id,
+            "wins": entry.wins,
+            "losses": entry.losses,
+            "draws": entry.draws,
+            "gamesPlayed": entry.games_played,
+            "lastPlayed": entry.last_played,
+            "winRate": entry.win_rate,
+            "score": entry.score,
+        })).collect::<Vec<_>>(),
     }))
 }
 
+/// Handle the reset_leaderboard tool call: wipes every recorded tally, for starting a fresh
+/// competition. Gated the same way `restart_game` is at the HTTP/MCP transport layer.
+pub fn reset_leaderboard(manager: &mut GameManager, _params: Value) -> Result<Value, JsonRpcError> {
+    manager
+        .reset_leaderboard()
+        .map_err(|e| JsonRpcError::internal_error(format!("Failed to reset leaderboard: {}", e)))?;
+
+    Ok(json!({ "success": true }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,23 +774,59 @@ mod tests {
 
     // get_turn tests
     #[test]
-    fn test_get_turn_success() {
+    fn test_view_game_state_with_game_id_targets_that_session() {
         let mut manager = create_test_manager();
-        let result = get_turn(&mut manager, json!({}));
+        let implicit = view_game_state(&mut manager, json!({})).unwrap();
+        let (other, _seat, _token) = manager.create_game().unwrap();
 
-        assert!(result.is_ok());
-        let value = result.unwrap();
-        assert!(value["currentTurn"].is_string());
-        assert!(value["isHumanTurn"].is_boolean());
-        assert!(value["isAiTurn"].is_boolean());
+        let result = view_game_state(&mut manager, json!({"gameId": other.id})).unwrap();
+
+        assert_eq!(result["id"], other.id);
+        assert_ne!(result["id"], implicit["id"]);
     }
 
     #[test]
-    fn test_get_turn_alternates() {
+    fn test_view_game_state_rejects_unknown_game_id() {
         let mut manager = create_test_manager();
-        let game = manager.get_or_create_game().unwrap();
-        let first_player = game.current_turn;
-        let human_player = game.human_player;
+        let result = view_game_state(&mut manager, json!({"gameId": "bogus"}));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_make_move_with_game_id_does_not_touch_the_implicit_game() {
+        let mut manager = create_test_manager();
+        let implicit = manager.get_or_create_game().unwrap();
+        let (other, _seat, _token) = manager.create_game().unwrap();
+
+        make_move(&mut manager, json!({"row": 0, "col": 0, "gameId": other.id})).unwrap();
+
+        let implicit_after = view_game_state(&mut manager, json!({})).unwrap();
+        assert_eq!(implicit_after["id"], implicit.id);
+        assert_eq!(
+            implicit_after["moveHistory"].as_array().unwrap().len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_get_turn_success() {
+        let mut manager = create_test_manager();
+        let result = get_turn(&mut manager, json!({}));
+
+        assert!(result.is_ok());
+        let value = result.unwrap();
+        assert!(value["currentTurn"].is_string());
+        assert!(value["isHumanTurn"].is_boolean());
+        assert!(value["isAiTurn"].is_boolean());
+    }
+
+    #[test]
+    fn test_get_turn_alternates() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        let first_player = game.current_turn;
+        let human_player = game.human_player;
 
         let turn1 = get_turn(&mut manager, json!({})).unwrap();
         assert_eq!(
@@ -309,6 +913,97 @@ mod tests {
         assert_eq!(taunts.len(), 1);
     }
 
+    // get_taunts tests
+    #[test]
+    fn test_get_taunts_returns_the_most_recent_messages_by_default() {
+        let mut manager = create_test_manager();
+        for i in 0..3 {
+            taunt_player(&mut manager, json!({"message": format!("taunt {}", i)})).unwrap();
+        }
+
+        let result = get_taunts(&mut manager, json!({"limit": 2})).unwrap();
+        let taunts = result["taunts"].as_array().unwrap();
+        assert_eq!(taunts.len(), 2);
+        assert_eq!(taunts[0]["body"], "taunt 1");
+        assert_eq!(taunts[1]["body"], "taunt 2");
+    }
+
+    #[test]
+    fn test_get_taunts_pages_with_before_and_after() {
+        let mut manager = create_test_manager();
+        for i in 0..3 {
+            taunt_player(&mut manager, json!({"message": format!("taunt {}", i)})).unwrap();
+        }
+        let all = get_taunts(&mut manager, json!({})).unwrap();
+        let middle_id = all["taunts"][1]["id"].as_u64().unwrap();
+
+        let before = get_taunts(&mut manager, json!({"before": middle_id})).unwrap();
+        assert_eq!(before["taunts"].as_array().unwrap().len(), 1);
+        assert_eq!(before["taunts"][0]["body"], "taunt 0");
+
+        let after = get_taunts(&mut manager, json!({"after": middle_id})).unwrap();
+        assert_eq!(after["taunts"].as_array().unwrap().len(), 1);
+        assert_eq!(after["taunts"][0]["body"], "taunt 2");
+    }
+
+    // send_emote tests
+    #[test]
+    fn test_send_emote_success() {
+        let mut manager = create_test_manager();
+        let result = send_emote(&mut manager, json!({"emote": "Fire"}));
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap()["success"], true);
+    }
+
+    #[test]
+    fn test_send_emote_rejects_an_unknown_variant() {
+        let mut manager = create_test_manager();
+        let result = send_emote(&mut manager, json!({"emote": "Laser"}));
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().code,
+            super::super::protocol::INVALID_PARAMS
+        );
+    }
+
+    #[test]
+    fn test_send_emote_missing_params() {
+        let mut manager = create_test_manager();
+        let result = send_emote(&mut manager, json!({}));
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().code,
+            super::super::protocol::INVALID_PARAMS
+        );
+    }
+
+    // get_recent_emotes tests
+    #[test]
+    fn test_get_recent_emotes_filters_out_free_text_taunts() {
+        let mut manager = create_test_manager();
+        taunt_player(&mut manager, json!({"message": "plain text, not an emote"})).unwrap();
+        send_emote(&mut manager, json!({"emote": "Cool"})).unwrap();
+        send_emote(&mut manager, json!({"emote": "Handshake"})).unwrap();
+
+        let result = get_recent_emotes(&mut manager, json!({})).unwrap();
+        let emotes = result["emotes"].as_array().unwrap();
+        assert_eq!(emotes.len(), 2);
+        assert_eq!(emotes[0]["emote"], EmoteEnum::Cool.as_str());
+        assert_eq!(emotes[1]["emote"], EmoteEnum::Handshake.as_str());
+    }
+
+    #[test]
+    fn test_get_recent_emotes_empty_with_no_emotes_sent() {
+        let mut manager = create_test_manager();
+        taunt_player(&mut manager, json!({"message": "just talk"})).unwrap();
+
+        let result = get_recent_emotes(&mut manager, json!({})).unwrap();
+        assert!(result["emotes"].as_array().unwrap().is_empty());
+    }
+
     // restart_game tests
     #[test]
     fn test_restart_game_success() {
@@ -324,6 +1019,18 @@ mod tests {
         assert_eq!(value["gameState"]["status"], "InProgress");
     }
 
+    #[test]
+    fn test_restart_game_accepts_a_difficulty_param() {
+        let mut manager = create_test_manager();
+
+        let result = restart_game(&mut manager, json!({"difficulty": "Perfect"}));
+
+        assert!(result.is_ok());
+        let value = result.unwrap();
+        assert_eq!(value["success"], true);
+        assert_eq!(value["gameState"]["status"], "InProgress");
+    }
+
     #[test]
     fn test_restart_game_clears_board() {
         let mut manager = create_test_manager();
@@ -337,6 +1044,169 @@ mod tests {
         assert_eq!(moves.len(), 0);
     }
 
+    // request_rematch tests
+    #[test]
+    fn test_request_rematch_rejects_a_game_still_in_progress() {
+        let mut manager = create_test_manager();
+
+        let result = request_rematch(&mut manager, json!({}));
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().code,
+            super::super::protocol::INVALID_PARAMS
+        );
+    }
+
+    #[test]
+    fn test_request_rematch_starts_a_fresh_game_after_the_previous_one_ends() {
+        let mut manager = create_test_manager();
+        let previous_id = manager.get_game_state().unwrap().id;
+
+        // A zero-second budget has already elapsed the instant it's set, forfeiting the game.
+        set_turn_limit(&mut manager, json!({"turnLimitSecs": 0})).unwrap();
+        check_turn_timeout(&mut manager, json!({})).unwrap();
+
+        let result = request_rematch(&mut manager, json!({})).unwrap();
+        assert_eq!(result["success"], true);
+        assert_eq!(result["gameState"]["status"], "InProgress");
+        assert_ne!(result["gameState"]["id"], json!(previous_id));
+        assert_eq!(result["previousGameId"], json!(previous_id));
+    }
+
+    #[test]
+    fn test_set_turn_limit_sets_value() {
+        let mut manager = create_test_manager();
+        let result = set_turn_limit(&mut manager, json!({"turnLimitSecs": 30}));
+
+        assert!(result.is_ok());
+        let value = result.unwrap();
+        assert_eq!(value["success"], true);
+        assert_eq!(value["turnLimitSecs"], 30);
+    }
+
+    #[test]
+    fn test_set_turn_limit_clears_value() {
+        let mut manager = create_test_manager();
+        set_turn_limit(&mut manager, json!({"turnLimitSecs": 30})).unwrap();
+
+        let result = set_turn_limit(&mut manager, json!({})).unwrap();
+
+        assert_eq!(result["turnLimitSecs"], Value::Null);
+    }
+
+    // check_turn_timeout tests
+    #[test]
+    fn test_check_turn_timeout_reports_in_progress_before_the_limit_elapses() {
+        let mut manager = create_test_manager();
+        set_turn_limit(&mut manager, json!({"turnLimitSecs": 300})).unwrap();
+
+        let result = check_turn_timeout(&mut manager, json!({})).unwrap();
+        assert_eq!(result["status"], "InProgress");
+    }
+
+    #[test]
+    fn test_check_turn_timeout_forfeits_without_a_move_attempt() {
+        let mut manager = create_test_manager();
+        let expired_player = manager.get_game_state().unwrap().current_turn;
+        // A zero-second budget has already elapsed the instant it's set.
+        set_turn_limit(&mut manager, json!({"turnLimitSecs": 0})).unwrap();
+
+        let result = check_turn_timeout(&mut manager, json!({})).unwrap();
+        let expected = match expired_player {
+            shared::Player::X => "Won_O",
+            shared::Player::O => "Won_X",
+        };
+        assert_eq!(result["status"], expected);
+    }
+
+    // compute_ai_move tests
+    #[test]
+    fn test_compute_ai_move_rejects_when_it_is_not_the_ai_turn() {
+        // assign_players() is a 50/50 coin flip for who goes first; retry a fresh game until
+        // it's the human's turn, the state this test means to exercise.
+        for _ in 0..50 {
+            let mut manager = create_test_manager();
+            let game = manager.get_game_state().unwrap();
+            if game.current_turn == game.ai_player {
+                continue;
+            }
+
+            let result = compute_ai_move(&mut manager, json!({}));
+            assert!(result.is_err());
+            let err = result.unwrap_err();
+            assert_eq!(err.code, super::super::protocol::INVALID_PARAMS);
+            return;
+        }
+        panic!("the human never went first in 50 attempts (assign_players should be ~50/50)");
+    }
+
+    #[test]
+    fn test_compute_ai_move_returns_a_legal_move_without_applying_it() {
+        // assign_players() is a 50/50 coin flip for who goes first; retry a fresh game until
+        // the AI does, since that's the only state compute_ai_move accepts.
+        for _ in 0..50 {
+            let mut manager = create_test_manager();
+            let game = manager.get_game_state().unwrap();
+            if game.current_turn != game.ai_player {
+                continue;
+            }
+
+            let result = compute_ai_move(&mut manager, json!({})).unwrap();
+            assert!(result["row"].as_u64().unwrap() < 3);
+            assert!(result["col"].as_u64().unwrap() < 3);
+
+            let unchanged = manager.get_game_state().unwrap();
+            assert_eq!(unchanged.move_history.len(), 0);
+            return;
+        }
+        panic!("AI never went first in 50 attempts (assign_players should be ~50/50)");
+    }
+
+    #[test]
+    fn test_set_turn_limit_rejects_unknown_game() {
+        let mut manager = create_test_manager();
+        let result = set_turn_limit(&mut manager, json!({"gameId": "nope", "turnLimitSecs": 30}));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_set_ai_difficulty_sets_value() {
+        let mut manager = create_test_manager();
+        let result = set_ai_difficulty(&mut manager, json!({"difficulty": "Hard"}));
+
+        assert!(result.is_ok());
+        let value = result.unwrap();
+        assert_eq!(value["success"], true);
+        assert_eq!(value["aiDifficulty"], "Hard");
+    }
+
+    #[test]
+    fn test_set_ai_difficulty_clears_value() {
+        let mut manager = create_test_manager();
+        set_ai_difficulty(&mut manager, json!({"difficulty": "Hard"})).unwrap();
+
+        let result = set_ai_difficulty(&mut manager, json!({})).unwrap();
+
+        assert_eq!(result["aiDifficulty"], Value::Null);
+    }
+
+    #[test]
+    fn test_set_ai_difficulty_rejects_unknown_name() {
+        let mut manager = create_test_manager();
+        let result = set_ai_difficulty(&mut manager, json!({"difficulty": "Nightmare"}));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_set_ai_difficulty_rejects_unknown_game() {
+        let mut manager = create_test_manager();
+        let result = set_ai_difficulty(&mut manager, json!({"gameId": "nope", "difficulty": "Hard"}));
+
+        assert!(result.is_err());
+    }
+
     // get_game_history tests
     #[test]
     fn test_get_game_history_empty() {
@@ -380,4 +1250,350 @@ mod tests {
         assert_eq!(moves[2]["row"], 0);
         assert_eq!(moves[2]["col"], 2);
     }
+
+    #[test]
+    fn test_get_game_history_limit_reports_has_more() {
+        let mut manager = create_test_manager();
+        make_move(&mut manager, json!({"row": 0, "col": 0})).unwrap();
+        make_move(&mut manager, json!({"row": 0, "col": 1})).unwrap();
+        make_move(&mut manager, json!({"row": 0, "col": 2})).unwrap();
+
+        let result = get_game_history(&mut manager, json!({"limit": 2})).unwrap();
+        let moves = result["moves"].as_array().unwrap();
+
+        assert_eq!(moves.len(), 2);
+        assert_eq!(moves[0]["col"], 1);
+        assert_eq!(moves[1]["col"], 2);
+        assert_eq!(result["hasMore"], true);
+    }
+
+    #[test]
+    fn test_get_game_history_pages_with_before_and_after() {
+        let mut manager = create_test_manager();
+        make_move(&mut manager, json!({"row": 0, "col": 0})).unwrap();
+        make_move(&mut manager, json!({"row": 0, "col": 1})).unwrap();
+        make_move(&mut manager, json!({"row": 0, "col": 2})).unwrap();
+
+        let before = get_game_history(&mut manager, json!({"before": 1})).unwrap();
+        let before_moves = before["moves"].as_array().unwrap();
+        assert_eq!(before_moves.len(), 1);
+        assert_eq!(before_moves[0]["col"], 0);
+
+        let after = get_game_history(&mut manager, json!({"after": 1})).unwrap();
+        let after_moves = after["moves"].as_array().unwrap();
+        assert_eq!(after_moves.len(), 1);
+        assert_eq!(after_moves[0]["col"], 2);
+    }
+
+    #[test]
+    fn test_get_game_history_rejects_out_of_range_before() {
+        let mut manager = create_test_manager();
+        make_move(&mut manager, json!({"row": 0, "col": 0})).unwrap();
+
+        let result = get_game_history(&mut manager, json!({"before": 99}));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_get_game_history_filters_by_source() {
+        let mut manager = create_test_manager();
+        manager.make_move_in_game(None, 0, 0, MoveSource::UI).unwrap();
+        make_move(&mut manager, json!({"row": 1, "col": 1})).unwrap();
+
+        let result = get_game_history(&mut manager, json!({"source": "MCP"})).unwrap();
+        let moves = result["moves"].as_array().unwrap();
+
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0]["row"], 1);
+        assert_eq!(moves[0]["col"], 1);
+    }
+
+    #[test]
+    fn test_get_game_history_rejects_unknown_source() {
+        let mut manager = create_test_manager();
+        let result = get_game_history(&mut manager, json!({"source": "AI"}));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_get_game_history_can_include_taunts() {
+        let mut manager = create_test_manager();
+        make_move(&mut manager, json!({"row": 0, "col": 0})).unwrap();
+        taunt_player(&mut manager, json!({"message": "gg"})).unwrap();
+
+        let result = get_game_history(&mut manager, json!({"includeTaunts": true})).unwrap();
+
+        assert_eq!(result["taunts"].as_array().unwrap().len(), 1);
+        assert_eq!(result["taunts"][0]["body"], "gg");
+    }
+
+    // new_game tests
+    #[test]
+    fn test_new_game_returns_a_fresh_id_without_disturbing_the_current_game() {
+        let mut manager = create_test_manager();
+        let current = view_game_state(&mut manager, json!({})).unwrap();
+
+        let result = new_game(&mut manager, json!({})).unwrap();
+        let new_id = result["gameId"].as_str().unwrap().to_string();
+
+        assert_ne!(new_id, current["id"]);
+        let still_current = view_game_state(&mut manager, json!({})).unwrap();
+        assert_eq!(still_current["id"], current["id"]);
+    }
+
+    #[test]
+    fn test_new_game_is_playable_by_its_returned_id() {
+        let mut manager = create_test_manager();
+        let result = new_game(&mut manager, json!({})).unwrap();
+        let game_id = result["gameId"].as_str().unwrap().to_string();
+
+        let moved = make_move(&mut manager, json!({"gameId": game_id, "row": 0, "col": 0}));
+        assert!(moved.is_ok());
+    }
+
+    // create_game / join_game / list_games tests
+    #[test]
+    fn test_create_game_returns_token_for_a_seat() {
+        let mut manager = create_test_manager();
+        let result = create_game(&mut manager, json!({})).unwrap();
+
+        assert!(result["gameId"].is_string());
+        assert!(result["seat"] == "X" || result["seat"] == "O");
+        assert!(!result["token"].as_str().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_join_game_claims_the_other_seat() {
+        let mut manager = create_test_manager();
+        let created = create_game(&mut manager, json!({})).unwrap();
+        let game_id = created["gameId"].as_str().unwrap().to_string();
+
+        let joined = join_game(&mut manager, json!({"gameId": game_id})).unwrap();
+
+        assert_ne!(joined["seat"], created["seat"]);
+        assert_ne!(joined["token"], created["token"]);
+    }
+
+    #[test]
+    fn test_join_game_unknown_id() {
+        let mut manager = create_test_manager();
+        let result = join_game(&mut manager, json!({"gameId": "nonexistent"}));
+
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert_eq!(err.code, super::super::protocol::INVALID_PARAMS);
+    }
+
+    #[test]
+    fn test_join_game_missing_game_id() {
+        let mut manager = create_test_manager();
+        let result = join_game(&mut manager, json!({}));
+
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert_eq!(err.code, super::super::protocol::INVALID_PARAMS);
+    }
+
+    #[test]
+    fn test_list_games_includes_created_games() {
+        let mut manager = create_test_manager();
+        let created = create_game(&mut manager, json!({})).unwrap();
+        let game_id = created["gameId"].as_str().unwrap().to_string();
+
+        let result = list_games(&mut manager, json!({})).unwrap();
+        let games = result["games"].as_array().unwrap();
+
+        assert!(games.iter().any(|g| g["gameId"] == game_id));
+    }
+
+    #[test]
+    fn test_view_game_state_includes_state_version() {
+        let mut manager = create_test_manager();
+        let result = view_game_state(&mut manager, json!({})).unwrap();
+        assert_eq!(result["stateVersion"], 0);
+
+        make_move(&mut manager, json!({"row": 0, "col": 0})).unwrap();
+        let result = view_game_state(&mut manager, json!({})).unwrap();
+        assert_eq!(result["stateVersion"], 1);
+    }
+
+    // wait_for_update tests
+    #[test]
+    fn test_wait_for_update_returns_immediately_when_changed() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        manager.make_move(0, 0, shared::MoveSource::UI).unwrap();
+
+        let result = wait_for_update(
+            &mut manager,
+            json!({"gameId": game.id, "since_version": 0}),
+        )
+        .unwrap();
+
+        assert_eq!(result["changed"], true);
+        assert_eq!(result["stateVersion"], 1);
+        assert!(result.get("gameState").is_some());
+    }
+
+    #[test]
+    fn test_wait_for_update_times_out() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+
+        let result = wait_for_update(
+            &mut manager,
+            json!({"gameId": game.id, "since_version": 0, "timeout_ms": 50}),
+        )
+        .unwrap();
+
+        assert_eq!(result["changed"], false);
+        assert!(result["message"].as_str().unwrap().contains("No change"));
+    }
+
+    #[test]
+    fn test_wait_for_update_missing_params() {
+        let mut manager = create_test_manager();
+        let result = wait_for_update(&mut manager, json!({}));
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().code,
+            super::super::protocol::INVALID_PARAMS
+        );
+    }
+
+    #[test]
+    fn test_get_state_if_changed_reports_a_change_without_blocking() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        manager.make_move(0, 0, shared::MoveSource::UI).unwrap();
+
+        let result = get_state_if_changed(
+            &mut manager,
+            json!({"gameId": game.id, "since_version": 0}),
+        )
+        .unwrap();
+
+        assert_eq!(result["changed"], true);
+        assert_eq!(result["stateVersion"], 1);
+        assert!(result.get("gameState").is_some());
+    }
+
+    #[test]
+    fn test_get_state_if_changed_reports_no_change() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+
+        let result = get_state_if_changed(
+            &mut manager,
+            json!({"gameId": game.id, "since_version": 0}),
+        )
+        .unwrap();
+
+        assert_eq!(result["changed"], false);
+        assert!(result.get("gameState").is_none());
+    }
+
+    #[test]
+    fn test_get_state_if_changed_missing_params() {
+        let mut manager = create_test_manager();
+        let result = get_state_if_changed(&mut manager, json!({}));
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().code,
+            super::super::protocol::INVALID_PARAMS
+        );
+    }
+
+    #[test]
+    fn test_get_state_if_updated_reports_a_change_without_blocking() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+        manager.make_move(0, 0, shared::MoveSource::UI).unwrap();
+
+        let result = get_state_if_updated(
+            &mut manager,
+            json!({"gameId": game.id, "sinceUpdatedAt": 0}),
+        )
+        .unwrap();
+
+        assert_eq!(result["changed"], true);
+        assert!(result["updatedAt"].as_i64().unwrap() > 0);
+        assert!(result.get("gameState").is_some());
+    }
+
+    #[test]
+    fn test_get_state_if_updated_reports_no_change() {
+        let mut manager = create_test_manager();
+        let game = manager.get_or_create_game().unwrap();
+
+        let first = get_state_if_updated(
+            &mut manager,
+            json!({"gameId": game.id, "sinceUpdatedAt": 0}),
+        )
+        .unwrap();
+        let since_updated_at = first["updatedAt"].as_i64().unwrap();
+
+        let result = get_state_if_updated(
+            &mut manager,
+            json!({"gameId": game.id, "sinceUpdatedAt": since_updated_at}),
+        )
+        .unwrap();
+
+        assert_eq!(result["changed"], false);
+        assert!(result.get("gameState").is_none());
+    }
+
+    #[test]
+    fn test_get_state_if_updated_missing_params() {
+        let mut manager = create_test_manager();
+        let result = get_state_if_updated(&mut manager, json!({}));
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().code,
+            super::super::protocol::INVALID_PARAMS
+        );
+    }
+
+    // get_leaderboard tests
+    #[test]
+    fn test_get_leaderboard_returns_an_empty_list_with_no_recorded_games() {
+        let mut manager = create_test_manager();
+        let result = get_leaderboard(&mut manager, json!({})).unwrap();
+
+        assert_eq!(result["leaderboard"], json!([]));
+    }
+
+    #[test]
+    fn test_get_leaderboard_defaults_the_limit_to_ten() {
+        let mut manager = create_test_manager();
+        let result = get_leaderboard(&mut manager, json!({})).unwrap();
+
+        assert!(result["leaderboard"].is_array());
+    }
+
+    // reset_leaderboard tests
+    #[test]
+    fn test_reset_leaderboard_reports_success() {
+        let mut manager = create_test_manager();
+        let result = reset_leaderboard(&mut manager, json!({})).unwrap();
+
+        assert_eq!(result["success"], true);
+    }
+
+    #[test]
+    fn test_reset_leaderboard_clears_recorded_entries() {
+        let mut manager = create_test_manager();
+        manager.make_move(0, 0, MoveSource::UI).ok();
+        manager.restart_game().ok();
+
+        reset_leaderboard(&mut manager, json!({})).unwrap();
+
+        let result = get_leaderboard(&mut manager, json!({})).unwrap();
+        assert_eq!(result["leaderboard"], json!([]));
+    }
 }
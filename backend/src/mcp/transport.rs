@@ -0,0 +1,361 @@
+use std::io::{self, BufRead, Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::sync::mpsc;
+use std::thread;
+use tokio::sync::broadcast;
+
+/// Carries line-oriented JSON-RPC text in and out of the server, independent of whether the
+/// underlying channel is stdio, a socket, or an HTTP connection.
+pub trait Transport {
+    /// Read the next request payload, or `Ok(None)` when the transport has closed.
+    fn recv(&mut self) -> io::Result<Option<String>>;
+
+    /// Write a response payload (already serialized to JSON). Notifications and
+    /// all-notification batches produce an empty string, which callers should not send.
+    fn send(&mut self, message: &str) -> io::Result<()>;
+}
+
+/// Line-delimited JSON-RPC over stdin/stdout (the original transport)
+pub struct StdioTransport {
+    stdin: io::Stdin,
+    stdout: io::Stdout,
+}
+
+impl StdioTransport {
+    pub fn new() -> Self {
+        Self {
+            stdin: io::stdin(),
+            stdout: io::stdout(),
+        }
+    }
+}
+
+impl Default for StdioTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transport for StdioTransport {
+    fn recv(&mut self) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        let bytes_read = self.stdin.lock().read_line(&mut line)?;
+        if bytes_read == 0 {
+            return Ok(None);
+        }
+        if line.ends_with('\n') {
+            line.pop();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+        Ok(Some(line))
+    }
+
+    fn send(&mut self, message: &str) -> io::Result<()> {
+        writeln!(self.stdout, "{}", message)?;
+        self.stdout.flush()
+    }
+}
+
+/// One HTTP POST's request body paired with the channel its response should be sent back on.
+type PendingRequest = (String, mpsc::Sender<String>);
+
+/// Streamable HTTP transport: a request arrives from an axum handler over `request_rx` (paired
+/// with a one-shot reply channel), and every outgoing message is also published on `sse_tx` so
+/// long-running or server-initiated messages can be streamed to clients connected over SSE.
+pub struct HttpTransport {
+    request_rx: mpsc::Receiver<PendingRequest>,
+    reply_tx: Option<mpsc::Sender<String>>,
+    sse_tx: broadcast::Sender<String>,
+}
+
+impl HttpTransport {
+    fn new(request_rx: mpsc::Receiver<PendingRequest>, sse_tx: broadcast::Sender<String>) -> Self {
+        Self {
+            request_rx,
+            reply_tx: None,
+            sse_tx,
+        }
+    }
+
+    /// Create a transport/handle pair: the transport is driven by `McpServer::run_with_transport`
+    /// on a dedicated thread, and the handle is cloned into axum handlers to submit requests and
+    /// subscribe to the SSE feed.
+    pub fn channel() -> (Self, HttpTransportHandle) {
+        let (request_tx, request_rx) = mpsc::channel();
+        let (sse_tx, _) = broadcast::channel(64);
+        let transport = Self::new(request_rx, sse_tx.clone());
+        let handle = HttpTransportHandle { request_tx, sse_tx };
+        (transport, handle)
+    }
+}
+
+impl Transport for HttpTransport {
+    fn recv(&mut self) -> io::Result<Option<String>> {
+        match self.request_rx.recv() {
+            Ok((body, reply_tx)) => {
+                self.reply_tx = Some(reply_tx);
+                Ok(Some(body))
+            }
+            Err(_) => Ok(None), // All handles dropped; shut down cleanly
+        }
+    }
+
+    fn send(&mut self, message: &str) -> io::Result<()> {
+        if let Some(reply_tx) = self.reply_tx.take() {
+            let _ = reply_tx.send(message.to_string());
+        }
+        // Best-effort fan-out to SSE subscribers; no listeners is not an error
+        let _ = self.sse_tx.send(message.to_string());
+        Ok(())
+    }
+}
+
+/// Shared handle used by axum handlers to talk to an [`HttpTransport`] running on its own thread
+#[derive(Clone)]
+pub struct HttpTransportHandle {
+    request_tx: mpsc::Sender<PendingRequest>,
+    sse_tx: broadcast::Sender<String>,
+}
+
+impl HttpTransportHandle {
+    /// Submit a JSON-RPC request body and block (via a blocking recv on the caller's side)
+    /// until the transport thread produces a response.
+    pub fn submit(&self, body: String) -> Result<mpsc::Receiver<String>, String> {
+        let (reply_tx, reply_rx) = mpsc::channel();
+        self.request_tx
+            .send((body, reply_tx))
+            .map_err(|_| "MCP transport thread is not running".to_string())?;
+        Ok(reply_rx)
+    }
+
+    /// Subscribe to every response/notification emitted over this transport, for SSE streaming
+    pub fn subscribe(&self) -> broadcast::Receiver<String> {
+        self.sse_tx.subscribe()
+    }
+}
+
+/// Unix domain socket transport: a background thread accepts connections and hands each one its
+/// own reader thread, all funneling requests into the single `request_rx` this transport drains
+/// on `McpServer`'s thread — so many clients can stay connected concurrently even though the
+/// server itself processes one request at a time. Each message is framed with a 4-byte
+/// big-endian length prefix followed by the JSON payload.
+pub struct UnixSocketTransport {
+    request_rx: mpsc::Receiver<PendingRequest>,
+    reply_tx: Option<mpsc::Sender<String>>,
+}
+
+impl UnixSocketTransport {
+    /// Bind a Unix domain socket at `path`, removing a stale socket file left behind by a
+    /// previous run, and start accepting connections on a background thread.
+    pub fn bind(path: &str) -> io::Result<Self> {
+        let _ = std::fs::remove_file(path);
+        let listener = UnixListener::bind(path)?;
+        let (request_tx, request_rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                let request_tx = request_tx.clone();
+                thread::spawn(move || handle_unix_connection(stream, request_tx));
+            }
+        });
+
+        Ok(Self {
+            request_rx,
+            reply_tx: None,
+        })
+    }
+}
+
+impl Transport for UnixSocketTransport {
+    fn recv(&mut self) -> io::Result<Option<String>> {
+        match self.request_rx.recv() {
+            Ok((body, reply_tx)) => {
+                self.reply_tx = Some(reply_tx);
+                Ok(Some(body))
+            }
+            Err(_) => Ok(None), // No connections left and the listener thread is gone
+        }
+    }
+
+    fn send(&mut self, message: &str) -> io::Result<()> {
+        if let Some(reply_tx) = self.reply_tx.take() {
+            // The connection's reader thread owns the socket and writes the framed response;
+            // a closed receiver just means that client already disconnected.
+            let _ = reply_tx.send(message.to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Read length-prefixed frames off `stream` and forward each one, paired with a reply channel,
+/// to `request_tx`; write back whatever response (if any) comes back on that channel, framed
+/// the same way.
+fn handle_unix_connection(mut stream: UnixStream, request_tx: mpsc::Sender<PendingRequest>) {
+    loop {
+        let frame = match read_frame(&mut stream) {
+            Ok(Some(frame)) => frame,
+            Ok(None) => return, // Client disconnected
+            Err(e) => {
+                tracing::warn!("Unix socket frame read error: {}", e);
+                return;
+            }
+        };
+
+        let (reply_tx, reply_rx) = mpsc::channel();
+        if request_tx.send((frame, reply_tx)).is_err() {
+            return; // Server shut down
+        }
+
+        match reply_rx.recv() {
+            Ok(response) if !response.is_empty() => {
+                if let Err(e) = write_frame(&mut stream, &response) {
+                    tracing::warn!("Unix socket frame write error: {}", e);
+                    return;
+                }
+            }
+            Ok(_) => {} // Empty response: a notification, nothing to send back
+            Err(_) => return,
+        }
+    }
+}
+
+fn read_frame(stream: &mut UnixStream) -> io::Result<Option<String>> {
+    let mut len_buf = [0u8; 4];
+    if let Err(e) = stream.read_exact(&mut len_buf) {
+        return if e.kind() == io::ErrorKind::UnexpectedEof {
+            Ok(None)
+        } else {
+            Err(e)
+        };
+    }
+
+    let len = u32::from_be_bytes(len_buf) as usize;
+    let mut payload = vec![0u8; len];
+    stream.read_exact(&mut payload)?;
+    String::from_utf8(payload)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn write_frame(stream: &mut UnixStream, message: &str) -> io::Result<()> {
+    let bytes = message.as_bytes();
+    stream.write_all(&(bytes.len() as u32).to_be_bytes())?;
+    stream.write_all(bytes)?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A transport double that replays a fixed script of inbound lines and records what was sent
+    struct ScriptedTransport {
+        inbound: std::collections::VecDeque<String>,
+        outbound: Vec<String>,
+    }
+
+    impl Transport for ScriptedTransport {
+        fn recv(&mut self) -> io::Result<Option<String>> {
+            Ok(self.inbound.pop_front())
+        }
+
+        fn send(&mut self, message: &str) -> io::Result<()> {
+            self.outbound.push(message.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_http_transport_roundtrip() {
+        let (mut transport, handle) = HttpTransport::channel();
+        let reply_rx = handle
+            .submit(r#"{"jsonrpc":"2.0","id":1,"method":"get_turn","params":{}}"#.to_string())
+            .unwrap();
+
+        let received = transport.recv().unwrap().unwrap();
+        assert!(received.contains("get_turn"));
+
+        transport.send(r#"{"jsonrpc":"2.0","id":1,"result":{}}"#).unwrap();
+
+        let response = reply_rx.recv().unwrap();
+        assert!(response.contains(r#""id":1"#));
+    }
+
+    #[test]
+    fn test_http_transport_fans_out_to_sse_subscribers() {
+        let (mut transport, handle) = HttpTransport::channel();
+        let mut sse_rx = handle.subscribe();
+
+        let _reply_rx = handle.submit("{}".to_string()).unwrap();
+        transport.recv().unwrap();
+        transport.send(r#"{"jsonrpc":"2.0","id":1,"result":{}}"#).unwrap();
+
+        let event = sse_rx.try_recv().unwrap();
+        assert!(event.contains("result"));
+    }
+
+    #[test]
+    fn test_unix_socket_transport_roundtrip() {
+        let dir = std::env::temp_dir().join(format!(
+            "game-mcp-test-{:?}",
+            thread::current().id()
+        ));
+        let _ = std::fs::create_dir_all(&dir);
+        let path = dir.join("roundtrip.sock");
+        let path = path.to_str().unwrap();
+
+        let mut transport = UnixSocketTransport::bind(path).unwrap();
+
+        let mut client = UnixStream::connect(path).unwrap();
+        write_frame(&mut client, r#"{"jsonrpc":"2.0","id":1,"method":"get_turn"}"#).unwrap();
+
+        let received = transport.recv().unwrap().unwrap();
+        assert!(received.contains("get_turn"));
+
+        transport.send(r#"{"jsonrpc":"2.0","id":1,"result":{}}"#).unwrap();
+
+        let response = read_frame(&mut client).unwrap().unwrap();
+        assert!(response.contains(r#""id":1"#));
+    }
+
+    #[test]
+    fn test_unix_socket_transport_serves_multiple_clients() {
+        let dir = std::env::temp_dir().join(format!(
+            "game-mcp-test-{:?}",
+            thread::current().id()
+        ));
+        let _ = std::fs::create_dir_all(&dir);
+        let path = dir.join("multi.sock");
+        let path = path.to_str().unwrap();
+
+        let mut transport = UnixSocketTransport::bind(path).unwrap();
+
+        let mut client_a = UnixStream::connect(path).unwrap();
+        let mut client_b = UnixStream::connect(path).unwrap();
+        write_frame(&mut client_a, "from-a").unwrap();
+        write_frame(&mut client_b, "from-b").unwrap();
+
+        let mut seen = vec![
+            transport.recv().unwrap().unwrap(),
+            transport.recv().unwrap().unwrap(),
+        ];
+        seen.sort();
+        assert_eq!(seen, vec!["from-a".to_string(), "from-b".to_string()]);
+    }
+
+    #[test]
+    fn test_scripted_transport_double() {
+        let mut transport = ScriptedTransport {
+            inbound: vec!["one".to_string(), "two".to_string()].into(),
+            outbound: vec![],
+        };
+
+        assert_eq!(transport.recv().unwrap(), Some("one".to_string()));
+        transport.send("one-response").unwrap();
+        assert_eq!(transport.recv().unwrap(), Some("two".to_string()));
+        assert_eq!(transport.outbound, vec!["one-response".to_string()]);
+    }
+}
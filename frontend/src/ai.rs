@@ -0,0 +1,165 @@
+//! A local minimax opponent that stands in for the MCP agent when it doesn't respond to a human
+//! move in time. Works directly on `GameState::board`'s fixed 3x3 array rather than the
+//! backend's `Board`/`logic` types, which the frontend has no access to across the crate
+//! boundary.
+
+use rand::seq::SliceRandom;
+use shared::{AiDifficulty, Cell, Player};
+
+/// The 8 winning lines on a 3x3 board
+const LINES: [[(u8, u8); 3]; 8] = [
+    [(0, 0), (0, 1), (0, 2)],
+    [(1, 0), (1, 1), (1, 2)],
+    [(2, 0), (2, 1), (2, 2)],
+    [(0, 0), (1, 0), (2, 0)],
+    [(0, 1), (1, 1), (2, 1)],
+    [(0, 2), (1, 2), (2, 2)],
+    [(0, 0), (1, 1), (2, 2)],
+    [(0, 2), (1, 1), (2, 0)],
+];
+
+fn winner(board: &[[Cell; 3]; 3]) -> Option<Player> {
+    for line in LINES {
+        let cells: Vec<Cell> = line.iter().map(|(r, c)| board[*r as usize][*c as usize]).collect();
+        if let Cell::Occupied(first) = cells[0] {
+            if cells.iter().all(|c| *c == Cell::Occupied(first)) {
+                return Some(first);
+            }
+        }
+    }
+    None
+}
+
+fn is_full(board: &[[Cell; 3]; 3]) -> bool {
+    board.iter().flatten().all(|cell| *cell != Cell::Empty)
+}
+
+fn empty_cells(board: &[[Cell; 3]; 3]) -> Vec<(u8, u8)> {
+    let mut cells = Vec::new();
+    for row in 0..3u8 {
+        for col in 0..3u8 {
+            if board[row as usize][col as usize] == Cell::Empty {
+                cells.push((row, col));
+            }
+        }
+    }
+    cells
+}
+
+/// Score `board` from `ai_player`'s perspective with `player_to_move` about to play next:
+/// `10 - depth` for an AI win, `depth - 10` for an AI loss, `0` for a draw (the depth adjustment
+/// makes the AI prefer faster wins and slower losses). Maximizes on the AI's turns, minimizes on
+/// the opponent's.
+fn minimax(board: &[[Cell; 3]; 3], player_to_move: Player, ai_player: Player, depth: i32) -> i32 {
+    if let Some(w) = winner(board) {
+        return if w == ai_player { 10 - depth } else { depth - 10 };
+    }
+    if is_full(board) {
+        return 0;
+    }
+
+    let scores = empty_cells(board).into_iter().map(|(row, col)| {
+        let mut next = *board;
+        next[row as usize][col as usize] = Cell::Occupied(player_to_move);
+        minimax(&next, player_to_move.opponent(), ai_player, depth + 1)
+    });
+
+    if player_to_move == ai_player {
+        scores.max().unwrap()
+    } else {
+        scores.min().unwrap()
+    }
+}
+
+/// The minimax-optimal move for `ai_player` to play on `board`, or `None` if it's full.
+fn best_move(board: &[[Cell; 3]; 3], ai_player: Player) -> Option<(u8, u8)> {
+    empty_cells(board)
+        .into_iter()
+        .map(|(row, col)| {
+            let mut next = *board;
+            next[row as usize][col as usize] = Cell::Occupied(ai_player);
+            let score = minimax(&next, ai_player.opponent(), ai_player, 1);
+            (score, (row, col))
+        })
+        .max_by_key(|(score, _)| *score)
+        .map(|(_, pos)| pos)
+}
+
+fn random_move(board: &[[Cell; 3]; 3]) -> Option<(u8, u8)> {
+    empty_cells(board).choose(&mut rand::thread_rng()).copied()
+}
+
+/// Choose `ai_player`'s move on `board` at the given difficulty, or `None` if the board is full.
+/// `Easy` picks a uniformly random empty cell, `Normal` mixes random and minimax 50/50, and
+/// `Hard` is always minimax-optimal.
+pub fn get_ai_move(
+    board: &[[Cell; 3]; 3],
+    ai_player: Player,
+    difficulty: AiDifficulty,
+) -> Option<(u8, u8)> {
+    match difficulty {
+        AiDifficulty::Easy => random_move(board),
+        AiDifficulty::Normal => {
+            if rand::random::<bool>() {
+                random_move(board)
+            } else {
+                best_move(board, ai_player)
+            }
+        }
+        AiDifficulty::Hard => best_move(board, ai_player),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board_from(moves: &[(u8, u8, Player)]) -> [[Cell; 3]; 3] {
+        let mut board = [[Cell::Empty; 3]; 3];
+        for (row, col, player) in moves {
+            board[*row as usize][*col as usize] = Cell::Occupied(*player);
+        }
+        board
+    }
+
+    #[test]
+    fn test_hard_takes_the_winning_move() {
+        let board = board_from(&[(0, 0, Player::O), (0, 1, Player::O), (1, 0, Player::X)]);
+        assert_eq!(
+            get_ai_move(&board, Player::O, AiDifficulty::Hard),
+            Some((0, 2))
+        );
+    }
+
+    #[test]
+    fn test_hard_blocks_the_opponents_winning_move() {
+        let board = board_from(&[(0, 0, Player::X), (0, 1, Player::X), (1, 0, Player::O)]);
+        assert_eq!(
+            get_ai_move(&board, Player::O, AiDifficulty::Hard),
+            Some((0, 2))
+        );
+    }
+
+    #[test]
+    fn test_easy_picks_an_empty_cell() {
+        let board = board_from(&[(0, 0, Player::X)]);
+        let (row, col) = get_ai_move(&board, Player::O, AiDifficulty::Easy).unwrap();
+        assert_eq!(board[row as usize][col as usize], Cell::Empty);
+    }
+
+    #[test]
+    fn test_no_move_on_full_board() {
+        let board = board_from(&[
+            (0, 0, Player::X),
+            (0, 1, Player::O),
+            (0, 2, Player::X),
+            (1, 0, Player::X),
+            (1, 1, Player::O),
+            (1, 2, Player::O),
+            (2, 0, Player::O),
+            (2, 1, Player::X),
+            (2, 2, Player::X),
+        ]);
+        assert_eq!(get_ai_move(&board, Player::O, AiDifficulty::Hard), None);
+    }
+}
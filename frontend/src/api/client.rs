@@ -1,15 +1,25 @@
 /// HTTP client for backend API
 /// Only compiled for WASM target
 use gloo_net::http::Request;
-use shared::{GameState, MakeMoveRequest, TauntRequest};
+use shared::{
+    ConcedeRequest, EmoteEnum, GameState, LeaveGameRequest, MakeMoveRequest,
+    PairingRequestResponse, PairingStatusResponse, SendEmoteRequest, TauntRequest,
+};
 
 const API_BASE: &str = "/api";
 
 pub async fn fetch_game_state() -> Result<GameState, String> {
-    let response = Request::get(&format!("{}/game", API_BASE))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    fetch_game_state_for(None).await
+}
+
+/// Fetch a specific session's state, or the implicit current game if `game_id` is `None`.
+pub async fn fetch_game_state_for(game_id: Option<&str>) -> Result<GameState, String> {
+    let url = match game_id {
+        Some(id) => format!("{}/game?gameId={}", API_BASE, id),
+        None => format!("{}/game", API_BASE),
+    };
+
+    let response = Request::get(&url).send().await.map_err(|e| e.to_string())?;
 
     if !response.ok() {
         return Err(format!("HTTP {}", response.status()));
@@ -18,6 +28,38 @@ pub async fn fetch_game_state() -> Result<GameState, String> {
     response.json().await.map_err(|e| e.to_string())
 }
 
+/// Conditionally fetch a game's state: `None` if it hasn't advanced past `since_version` (a
+/// 304 from the backend), otherwise `Some((state, version))` with the fresh state and its new
+/// version. Lets the caller skip a re-render when nothing changed.
+pub async fn fetch_game_state_if_changed(
+    game_id: Option<&str>,
+    since_version: u64,
+) -> Result<Option<(GameState, u64)>, String> {
+    let base = match game_id {
+        Some(id) => format!("{}/game?gameId={}", API_BASE, id),
+        None => format!("{}/game", API_BASE),
+    };
+    let url = format!("{}{}sinceVersion={}", base, if game_id.is_some() { "&" } else { "?" }, since_version);
+
+    let response = Request::get(&url).send().await.map_err(|e| e.to_string())?;
+
+    if response.status() == 304 {
+        return Ok(None);
+    }
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+
+    let version = response
+        .headers()
+        .get("X-State-Version")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(since_version);
+
+    let state = response.json().await.map_err(|e| e.to_string())?;
+    Ok(Some((state, version)))
+}
+
 pub async fn create_new_game() -> Result<GameState, String> {
     let response = Request::post(&format!("{}/game/new", API_BASE))
         .send()
@@ -32,7 +74,33 @@ pub async fn create_new_game() -> Result<GameState, String> {
 }
 
 pub async fn make_move(row: u8, col: u8) -> Result<GameState, String> {
-    let request_body = MakeMoveRequest { row, col };
+    make_move_in(None, row, col).await
+}
+
+/// Make a move in a specific session, or the implicit current game if `game_id` is `None`.
+pub async fn make_move_in(game_id: Option<&str>, row: u8, col: u8) -> Result<GameState, String> {
+    send_move(game_id, row, col, false).await
+}
+
+/// Make a move chosen by the local AI opponent (see `ai::get_ai_move`), tagging it with
+/// `MoveSource::AI` instead of `MoveSource::UI` so the event log and taunt labels distinguish it
+/// from a move the human actually clicked.
+pub async fn make_ai_move_in(game_id: Option<&str>, row: u8, col: u8) -> Result<GameState, String> {
+    send_move(game_id, row, col, true).await
+}
+
+async fn send_move(
+    game_id: Option<&str>,
+    row: u8,
+    col: u8,
+    local_ai: bool,
+) -> Result<GameState, String> {
+    let request_body = MakeMoveRequest {
+        row,
+        col,
+        game_id: game_id.map(|id| id.to_string()),
+        local_ai,
+    };
 
     let response = Request::post(&format!("{}/game/move", API_BASE))
         .json(&request_body)
@@ -49,7 +117,15 @@ pub async fn make_move(row: u8, col: u8) -> Result<GameState, String> {
 }
 
 pub async fn send_taunt(message: String) -> Result<(), String> {
-    let request_body = TauntRequest { message };
+    send_taunt_in(None, message).await
+}
+
+/// Send a taunt in a specific session, or the implicit current game if `game_id` is `None`.
+pub async fn send_taunt_in(game_id: Option<&str>, message: String) -> Result<(), String> {
+    let request_body = TauntRequest {
+        message,
+        game_id: game_id.map(|id| id.to_string()),
+    };
 
     let response = Request::post(&format!("{}/game/taunt", API_BASE))
         .json(&request_body)
@@ -64,3 +140,98 @@ pub async fn send_taunt(message: String) -> Result<(), String> {
 
     Ok(())
 }
+
+pub async fn concede_game() -> Result<GameState, String> {
+    concede_game_in(None).await
+}
+
+/// Forfeit a specific session, or the implicit current game if `game_id` is `None`.
+pub async fn concede_game_in(game_id: Option<&str>) -> Result<GameState, String> {
+    let request_body = ConcedeRequest {
+        game_id: game_id.map(|id| id.to_string()),
+    };
+
+    let response = Request::post(&format!("{}/game/concede", API_BASE))
+        .json(&request_body)
+        .map_err(|e| e.to_string())?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+
+    response.json().await.map_err(|e| e.to_string())
+}
+
+pub async fn send_emote(emote: EmoteEnum) -> Result<(), String> {
+    send_emote_in(None, emote).await
+}
+
+/// Send an emote in a specific session, or the implicit current game if `game_id` is `None`.
+pub async fn send_emote_in(game_id: Option<&str>, emote: EmoteEnum) -> Result<(), String> {
+    let request_body = SendEmoteRequest {
+        emote,
+        game_id: game_id.map(|id| id.to_string()),
+    };
+
+    let response = Request::post(&format!("{}/game/emote", API_BASE))
+        .json(&request_body)
+        .map_err(|e| e.to_string())?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+
+    Ok(())
+}
+
+/// Ask the backend to find a human opponent. Returns a pairing id to poll via
+/// `pairing_status` until it resolves to a matched game.
+pub async fn request_pairing() -> Result<PairingRequestResponse, String> {
+    let response = Request::post(&format!("{}/pairing/request", API_BASE))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+
+    response.json().await.map_err(|e| e.to_string())
+}
+
+/// Poll a pairing requested via `request_pairing`.
+pub async fn pairing_status(pairing_id: &str) -> Result<PairingStatusResponse, String> {
+    let url = format!("{}/pairing/status?pairingId={}", API_BASE, pairing_id);
+
+    let response = Request::get(&url).send().await.map_err(|e| e.to_string())?;
+
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+
+    response.json().await.map_err(|e| e.to_string())
+}
+
+/// Forfeit a human-vs-human match to the other seat, e.g. when leaving the page.
+pub async fn leave_game(game_id: String, token: String) -> Result<(), String> {
+    let request_body = LeaveGameRequest { game_id, token };
+
+    let response = Request::post(&format!("{}/game/leave", API_BASE))
+        .json(&request_body)
+        .map_err(|e| e.to_string())?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+
+    Ok(())
+}
@@ -4,4 +4,8 @@
 mod client;
 
 #[cfg(target_arch = "wasm32")]
-pub use client::{create_new_game, fetch_game_state, make_move, send_taunt};
+pub use client::{
+    concede_game, create_new_game, fetch_game_state, fetch_game_state_for,
+    fetch_game_state_if_changed, leave_game, make_ai_move_in, make_move, pairing_status,
+    request_pairing, send_emote, send_taunt,
+};
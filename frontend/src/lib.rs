@@ -1,10 +1,11 @@
+mod ai;
 mod api;
 
 use log::info;
 
 #[cfg(target_arch = "wasm32")]
 use log::error;
-use shared::{Cell, GameState, GameStatus, MoveSource, Player};
+use shared::{AiDifficulty, Cell, EmoteEnum, GameState, GameStatus, MoveSource, Player, WinType};
 use yew::prelude::*;
 
 #[cfg(target_arch = "wasm32")]
@@ -16,6 +17,60 @@ use wasm_bindgen::JsCast;
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::closure::Closure;
 
+#[cfg(target_arch = "wasm32")]
+use std::cell::{Cell as AttemptCell, RefCell};
+#[cfg(target_arch = "wasm32")]
+use std::rc::Rc;
+
+/// How the client is currently receiving game-state updates: pushed live over SSE, mid-backoff
+/// after an SSE error, or falling back to polling `api::fetch_game_state()` after SSE has failed
+/// too many times in a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConnectionStatus {
+    Live,
+    Reconnecting,
+    Polling,
+}
+
+/// Which opponent the current game is played against, chosen from the pre-game mode menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GameMode {
+    /// The default: the MCP agent plays the other seat, with a local AI fallback if it stalls.
+    VsMcp,
+    /// No MCP agent attached; the local AI (see `ai::get_ai_move`) replies to every human move.
+    VsLocalAi,
+    /// Two humans sharing this browser, passing the device back and forth.
+    LocalMultiplayer,
+    /// A human opponent matched via `api::request_pairing`/`api::pairing_status`, each on their
+    /// own browser.
+    VsNetworked,
+}
+
+/// Which seat this browser is allowed to move for, generalizing `current_turn == human_player`
+/// (true only for the fixed single-player seat) to the other modes: in local multiplayer both
+/// seats are "mine" since one device passes between two humans, and in a networked match it's
+/// whichever seat `api::pairing_status` assigned this client, not necessarily `human_player`
+/// (the matched opponent may have created the lobby game and taken that seat instead).
+fn my_player(game_mode: GameMode, state: &GameState, my_seat: Option<Player>) -> Player {
+    match game_mode {
+        GameMode::LocalMultiplayer => state.current_turn,
+        GameMode::VsNetworked => my_seat.unwrap_or(state.human_player),
+        GameMode::VsMcp | GameMode::VsLocalAi => state.human_player,
+    }
+}
+
+/// CSS class for the `draw_overlay`'s connecting-line element, oriented to match the winning
+/// line. `None` for games won before `WinType` was tracked, where there's nothing to orient.
+fn win_line_orientation_class(win_type: Option<WinType>) -> Option<&'static str> {
+    match win_type {
+        Some(WinType::Horizontal) => Some("win-line-horizontal"),
+        Some(WinType::Vertical) => Some("win-line-vertical"),
+        Some(WinType::DiagonalTopLeft) => Some("win-line-diagonal-tl"),
+        Some(WinType::DiagonalTopRight) => Some("win-line-diagonal-tr"),
+        None => None,
+    }
+}
+
 /// Format Unix timestamp (seconds) to YYYY/MM/DD HH:MM
 #[cfg(target_arch = "wasm32")]
 fn format_timestamp(timestamp: i64) -> String {
@@ -34,15 +89,341 @@ fn format_timestamp(timestamp: i64) -> String {
     )
 }
 
+/// How long to wait for the MCP agent to respond to a human move before the local AI steps in
+/// and plays the AI's turn itself.
+#[cfg(target_arch = "wasm32")]
+const AI_FALLBACK_TIMEOUT_MS: u32 = 5000;
+
+/// After a human move, give the MCP agent `AI_FALLBACK_TIMEOUT_MS` to reply via SSE. If the
+/// board is still waiting on the AI by the time the timer fires, re-fetch the latest state (in
+/// case the agent answered just as the timer expired) and, if it's still the AI's turn, play its
+/// move locally so the game stays playable without an MCP agent attached.
+#[cfg(target_arch = "wasm32")]
+fn schedule_local_ai_fallback(log_event: Callback<String>, difficulty: AiDifficulty) {
+    let timeout = gloo::timers::callback::Timeout::new(AI_FALLBACK_TIMEOUT_MS, move || {
+        let log_event = log_event.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let Ok(state) = api::fetch_game_state().await else {
+                return;
+            };
+            if state.status != GameStatus::InProgress || state.current_turn == state.human_player {
+                return;
+            }
+
+            if let Some((row, col)) = ai::get_ai_move(&state.board, state.current_turn, difficulty)
+            {
+                log_event.emit("🤖 MCP agent didn't respond in time, local AI is moving...".to_string());
+                if let Err(e) = api::make_ai_move_in(None, row, col).await {
+                    error!("Local AI fallback move failed: {}", e);
+                    log_event.emit(format!("❌ Local AI move failed: {}", e));
+                }
+            }
+        });
+    });
+    timeout.forget();
+}
+
+/// How many SSE reconnect attempts to retry with exponential backoff before giving up and
+/// falling back to polling.
+#[cfg(target_arch = "wasm32")]
+const SSE_MAX_RECONNECT_ATTEMPTS: u32 = 5;
+
+/// How often to poll `api::fetch_game_state()` once SSE has been given up on.
+#[cfg(target_arch = "wasm32")]
+const SSE_POLL_INTERVAL_MS: u32 = 3000;
+
+/// The live `EventSource`, if one is currently connected, so a reconnect attempt or the effect's
+/// cleanup can close it before replacing or tearing it down.
+#[cfg(target_arch = "wasm32")]
+type SseHandle = Rc<RefCell<Option<EventSource>>>;
+
+/// Whether `new_state` has actually advanced past whatever `game_state` currently holds, by
+/// `GameState::version`. Lets SSE/polling updates skip `.set()` (and the `use_effect_with` churn
+/// it triggers) when the server echoes back a state the client already has.
+#[cfg(target_arch = "wasm32")]
+fn state_is_newer(game_state: &UseStateHandle<Option<GameState>>, new_state: &GameState) -> bool {
+    match game_state.as_ref() {
+        Some(current) => new_state.changed_since(current.version),
+        None => true,
+    }
+}
+
+/// Re-fetch game state if the server has advanced past what `game_state` currently holds, and
+/// apply it. Used after a taunt/emote send whose response doesn't carry the fresh `GameState`,
+/// so the UI doesn't have to wait on a possibly-lagging SSE push to pick up the version bump.
+#[cfg(target_arch = "wasm32")]
+async fn reconcile_game_state(game_state: &UseStateHandle<Option<GameState>>) {
+    let game_id = game_state.as_ref().map(|s| s.id.clone());
+    let since_version = game_state.as_ref().map(|s| s.version).unwrap_or(0);
+
+    if let Ok(Some((state, _))) =
+        api::fetch_game_state_if_changed(game_id.as_deref(), since_version).await
+    {
+        game_state.set(Some(state));
+    }
+}
+
+/// Backoff before the `attempt`'th reconnect: 1s, 2s, 4s, ... capped at 30s.
+#[cfg(target_arch = "wasm32")]
+fn sse_backoff_ms(attempt: u32) -> u32 {
+    1000u32.saturating_mul(1u32 << attempt.min(5)).min(30_000)
+}
+
+/// Open the SSE connection and wire up reconnect-with-backoff: on `onerror`, close the dead
+/// `EventSource` and schedule another `connect_sse` attempt after `sse_backoff_ms(attempt)`.
+/// `attempt` resets to 0 on every successfully parsed message, so a brief blip doesn't carry
+/// a long backoff into the next disconnect. After `SSE_MAX_RECONNECT_ATTEMPTS` in a row fail,
+/// gives up on SSE and switches to polling instead.
+#[cfg(target_arch = "wasm32")]
+fn connect_sse(
+    game_state: UseStateHandle<Option<GameState>>,
+    loading: UseStateHandle<bool>,
+    error_msg: UseStateHandle<Option<String>>,
+    log_event: Callback<String>,
+    connection_status: UseStateHandle<ConnectionStatus>,
+    attempt: Rc<AttemptCell<u32>>,
+    handle_slot: SseHandle,
+) {
+    if attempt.get() > SSE_MAX_RECONNECT_ATTEMPTS {
+        log_event.emit("🔁 Giving up on SSE, falling back to polling for updates".to_string());
+        connection_status.set(ConnectionStatus::Polling);
+        start_polling(game_state, error_msg, log_event, connection_status);
+        return;
+    }
+
+    let Some(event_source) = EventSource::new("/api/events").ok() else {
+        error!("Failed to create EventSource");
+        log_event.emit("❌ Failed to connect to SSE".to_string());
+        schedule_sse_retry(game_state, loading, error_msg, log_event, connection_status, attempt, handle_slot);
+        return;
+    };
+
+    log_event.emit(if attempt.get() == 0 {
+        "✅ SSE connected - listening for updates".to_string()
+    } else {
+        format!("✅ SSE reconnected (attempt {})", attempt.get())
+    });
+    connection_status.set(ConnectionStatus::Live);
+
+    let onmessage = Closure::wrap(Box::new({
+        let game_state = game_state.clone();
+        let connection_status = connection_status.clone();
+        let attempt = attempt.clone();
+        move |event: web_sys::MessageEvent| {
+            if let Some(data) = event.data().as_string() {
+                info!("SSE message received: {}", data);
+                match serde_json::from_str::<GameState>(&data) {
+                    Ok(new_state) => {
+                        if state_is_newer(&game_state, &new_state) {
+                            game_state.set(Some(new_state));
+                        }
+                        connection_status.set(ConnectionStatus::Live);
+                        attempt.set(0);
+                    }
+                    Err(e) => {
+                        error!("Failed to parse SSE data: {}", e);
+                    }
+                }
+            }
+        }
+    }) as Box<dyn FnMut(_)>);
+    event_source.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+    onmessage.forget();
+
+    let onerror = Closure::wrap(Box::new({
+        let game_state = game_state.clone();
+        let loading = loading.clone();
+        let error_msg = error_msg.clone();
+        let log_event = log_event.clone();
+        let connection_status = connection_status.clone();
+        let attempt = attempt.clone();
+        let handle_slot = handle_slot.clone();
+        move |e: web_sys::Event| {
+            error!("SSE error: {:?}", e);
+            log_event.emit("⚠️ SSE connection error".to_string());
+            if let Some(es) = handle_slot.borrow_mut().take() {
+                es.close();
+            }
+            connection_status.set(ConnectionStatus::Reconnecting);
+            schedule_sse_retry(
+                game_state.clone(),
+                loading.clone(),
+                error_msg.clone(),
+                log_event.clone(),
+                connection_status.clone(),
+                attempt.clone(),
+                handle_slot.clone(),
+            );
+        }
+    }) as Box<dyn FnMut(_)>);
+    event_source.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+    onerror.forget();
+
+    *handle_slot.borrow_mut() = Some(event_source);
+}
+
+/// Schedule the next `connect_sse` attempt after this attempt's backoff delay.
+#[cfg(target_arch = "wasm32")]
+fn schedule_sse_retry(
+    game_state: UseStateHandle<Option<GameState>>,
+    loading: UseStateHandle<bool>,
+    error_msg: UseStateHandle<Option<String>>,
+    log_event: Callback<String>,
+    connection_status: UseStateHandle<ConnectionStatus>,
+    attempt: Rc<AttemptCell<u32>>,
+    handle_slot: SseHandle,
+) {
+    let delay = sse_backoff_ms(attempt.get());
+    attempt.set(attempt.get() + 1);
+    log_event.emit(format!("⏳ Reconnecting to SSE in {}ms...", delay));
+
+    let timeout = gloo::timers::callback::Timeout::new(delay, move || {
+        connect_sse(
+            game_state,
+            loading,
+            error_msg,
+            log_event,
+            connection_status,
+            attempt,
+            handle_slot,
+        );
+    });
+    timeout.forget();
+}
+
+/// Periodically re-fetch game state once SSE has been given up on for this session.
+#[cfg(target_arch = "wasm32")]
+fn start_polling(
+    game_state: UseStateHandle<Option<GameState>>,
+    error_msg: UseStateHandle<Option<String>>,
+    log_event: Callback<String>,
+    connection_status: UseStateHandle<ConnectionStatus>,
+) {
+    let interval = gloo::timers::callback::Interval::new(SSE_POLL_INTERVAL_MS, move || {
+        let game_state = game_state.clone();
+        let error_msg = error_msg.clone();
+        let connection_status = connection_status.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            match api::fetch_game_state().await {
+                Ok(state) => {
+                    if state_is_newer(&game_state, &state) {
+                        game_state.set(Some(state));
+                    }
+                    error_msg.set(None);
+                    connection_status.set(ConnectionStatus::Polling);
+                }
+                Err(e) => {
+                    error!("Polling fetch failed: {}", e);
+                }
+            }
+        });
+    });
+    interval.forget();
+}
+
+/// How often to poll `api::pairing_status` while waiting for a human opponent.
+#[cfg(target_arch = "wasm32")]
+const PAIRING_POLL_INTERVAL_MS: u32 = 2000;
+
+/// Poll `api::pairing_status(pairing_id)` until it resolves to a matched game. Once matched,
+/// loads that game's state, switches into `GameMode::VsNetworked`, records which seat this
+/// client owns into `my_seat` (so `my_player` can tell this client's moves from the opponent's),
+/// and stashes the seat token into `opponent_seat` so a later page-close can forfeit the match
+/// via `api::leave_game`.
+#[cfg(target_arch = "wasm32")]
+fn poll_pairing_status(
+    pairing_id: String,
+    game_state: UseStateHandle<Option<GameState>>,
+    game_mode: UseStateHandle<GameMode>,
+    my_seat: UseStateHandle<Option<Player>>,
+    opponent_seat: Rc<RefCell<Option<(String, String)>>>,
+    log_event: Callback<String>,
+) {
+    let handle_slot: Rc<RefCell<Option<gloo::timers::callback::Interval>>> =
+        Rc::new(RefCell::new(None));
+
+    let interval = {
+        let handle_slot = handle_slot.clone();
+        gloo::timers::callback::Interval::new(PAIRING_POLL_INTERVAL_MS, move || {
+            let pairing_id = pairing_id.clone();
+            let game_state = game_state.clone();
+            let game_mode = game_mode.clone();
+            let my_seat = my_seat.clone();
+            let opponent_seat = opponent_seat.clone();
+            let log_event = log_event.clone();
+            let handle_slot = handle_slot.clone();
+
+            wasm_bindgen_futures::spawn_local(async move {
+                match api::pairing_status(&pairing_id).await {
+                    Ok(status) if status.status == "Matched" => {
+                        if let Some(stop) = handle_slot.borrow_mut().take() {
+                            stop.cancel();
+                        }
+
+                        if let (Some(game_id), Some(token), Some(seat)) =
+                            (status.game_id, status.token, status.seat)
+                        {
+                            *opponent_seat.borrow_mut() = Some((game_id.clone(), token));
+                            match api::fetch_game_state_for(Some(&game_id)).await {
+                                Ok(state) => {
+                                    game_state.set(Some(state));
+                                    game_mode.set(GameMode::VsNetworked);
+                                    my_seat.set(Some(seat));
+                                    log_event.emit("🤝 Opponent found, match started!".to_string());
+                                }
+                                Err(e) => {
+                                    error!("Failed to load matched game: {}", e);
+                                    log_event.emit(format!("❌ Failed to load match: {}", e));
+                                }
+                            }
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("Pairing status poll failed: {}", e);
+                    }
+                }
+            });
+        })
+    };
+
+    *handle_slot.borrow_mut() = Some(interval);
+}
+
+/// How many rolling-buffer entries `App` keeps by default for the event log and taunt display,
+/// before the oldest are dropped to keep `log-scroll`/`taunt-display` from growing unbounded
+/// DOM nodes over a long session.
+const DEFAULT_MAX_BUFFER_ITEMS: usize = 50;
+
+#[derive(Properties, PartialEq, Clone)]
+struct AppProps {
+    /// Max entries kept in the event log before the oldest is dropped.
+    #[prop_or(DEFAULT_MAX_BUFFER_ITEMS)]
+    max_log_items: usize,
+    /// Max taunts rendered in `taunt-display` before the oldest drop out of view. The full
+    /// history still lives server-side in `GameState.taunts`; this only trims what's shown.
+    #[prop_or(DEFAULT_MAX_BUFFER_ITEMS)]
+    max_taunt_items: usize,
+}
+
 #[function_component(App)]
-fn app() -> Html {
+fn app(props: &AppProps) -> Html {
     info!("Rendering App component");
 
+    let max_log_items = props.max_log_items;
+    let max_taunt_items = props.max_taunt_items;
+
     let game_state = use_state(|| None::<GameState>);
     let loading = use_state(|| true);
     let error_msg = use_state(|| None::<String>);
     let taunt_input = use_state(String::new);
     let mcp_thinking = use_state(|| false);
+    let ai_difficulty = use_state(|| AiDifficulty::Normal);
+    let connection_status = use_state(|| ConnectionStatus::Live);
+    let game_mode = use_state(|| GameMode::VsMcp);
+    let my_seat = use_state(|| None::<Player>);
+    #[cfg(target_arch = "wasm32")]
+    let opponent_seat = use_state(|| Rc::new(RefCell::new(None::<(String, String)>)));
     let event_log = use_state(|| {
         vec![
             "Welcome to Tic-Tac-Toe!".to_string(),
@@ -56,8 +437,8 @@ fn app() -> Html {
         Callback::from(move |msg: String| {
             let mut logs = (*event_log).clone();
             logs.push(msg);
-            // Keep only last 10 events
-            if logs.len() > 10 {
+            // Rolling buffer: drop the oldest entry once we're past the cap.
+            if logs.len() > max_log_items {
                 logs.remove(0);
             }
             event_log.set(logs);
@@ -73,6 +454,8 @@ fn app() -> Html {
         #[cfg(target_arch = "wasm32")]
         let error_msg = error_msg.clone();
         let log_event = log_event.clone();
+        #[cfg(target_arch = "wasm32")]
+        let connection_status = connection_status.clone();
 
         use_effect_with((), move |_| {
             info!("Setting up SSE connection");
@@ -104,52 +487,25 @@ fn app() -> Html {
                 }
             });
 
-            // Set up SSE connection
+            // Open the SSE connection; `connect_sse` owns reconnect-with-backoff and the
+            // eventual polling fallback from here on.
             #[cfg(target_arch = "wasm32")]
-            let event_source_opt = EventSource::new("/api/events").ok();
-
+            let handle_slot: SseHandle = Rc::new(RefCell::new(None));
             #[cfg(target_arch = "wasm32")]
-            if let Some(ref event_source) = event_source_opt {
-                log_event.emit("✅ SSE connected - listening for updates".to_string());
-
-                // Handle incoming messages
-                let onmessage = Closure::wrap(Box::new(move |event: web_sys::MessageEvent| {
-                    if let Some(data) = event.data().as_string() {
-                        info!("SSE message received: {}", data);
-                        match serde_json::from_str::<GameState>(&data) {
-                            Ok(new_state) => {
-                                game_state.set(Some(new_state));
-                            }
-                            Err(e) => {
-                                error!("Failed to parse SSE data: {}", e);
-                            }
-                        }
-                    }
-                }) as Box<dyn FnMut(_)>);
-
-                event_source.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
-                onmessage.forget(); // Keep closure alive
-
-                // Handle errors
-                let onerror = Closure::wrap(Box::new(move |e: web_sys::Event| {
-                    error!("SSE error: {:?}", e);
-                    log_event.emit("⚠️ SSE connection error".to_string());
-                }) as Box<dyn FnMut(_)>);
-
-                event_source.set_onerror(Some(onerror.as_ref().unchecked_ref()));
-                onerror.forget();
-            } else {
-                #[cfg(target_arch = "wasm32")]
-                {
-                    error!("Failed to create EventSource");
-                    log_event.emit("❌ Failed to connect to SSE".to_string());
-                }
-            }
+            connect_sse(
+                game_state,
+                loading,
+                error_msg,
+                log_event,
+                connection_status,
+                Rc::new(AttemptCell::new(0)),
+                handle_slot.clone(),
+            );
 
             // Cleanup function
             move || {
                 #[cfg(target_arch = "wasm32")]
-                if let Some(es) = event_source_opt {
+                if let Some(es) = handle_slot.borrow_mut().take() {
                     es.close();
                 }
             }
@@ -171,6 +527,7 @@ fn app() -> Html {
                     let source_prefix = match &last_move.source {
                         Some(MoveSource::UI) => "UI:",
                         Some(MoveSource::MCP) => "MCP:",
+                        Some(MoveSource::AI) => "🤖 Local AI:",
                         None => "",
                     };
                     log_event.emit(format!(
@@ -198,12 +555,13 @@ fn app() -> Html {
                     // Log all new taunts
                     for i in *prev_taunt_count..current_count {
                         let taunt = &state.taunts[i];
-                        let prefix = match &taunt.source {
+                        let prefix = match &taunt.sender {
                             Some(MoveSource::UI) => "💬 You:",
                             Some(MoveSource::MCP) => "💬 MCP:",
+                            Some(MoveSource::AI) => "💬 🤖 Local AI:",
                             None => "💬",
                         };
-                        log_event.emit(format!("{} {}", prefix, taunt.message));
+                        log_event.emit(format!("{} {}", prefix, taunt.body));
                     }
                     prev_taunt_count.set(current_count);
 
@@ -231,6 +589,33 @@ fn app() -> Html {
         });
     }
 
+    // Log the win once it happens, describing how it was won ("X won diagonally") from the
+    // winning line's `WinType` so replays and taunts can reference it.
+    let prev_status = use_state(|| None::<GameStatus>);
+    {
+        let prev_status = prev_status.clone();
+        let log_event = log_event.clone();
+        let game_state = game_state.clone();
+
+        use_effect_with(game_state.clone(), move |state| {
+            if let Some(state) = state.as_ref() {
+                if prev_status.as_ref() != Some(&state.status) {
+                    if let GameStatus::Won(player) = state.status {
+                        let how = state
+                            .winning_line
+                            .as_ref()
+                            .and_then(|line| line.win_type)
+                            .map(|win_type| format!(" {}", win_type.adverb()))
+                            .unwrap_or_default();
+                        log_event.emit(format!("🏆 {} won{}!", player, how));
+                    }
+                    prev_status.set(Some(state.status.clone()));
+                }
+            }
+            || ()
+        });
+    }
+
     // Track MCP activity and show "thinking" indicator with debounce
     // Configurable delay in milliseconds (100ms in production, 2000ms for testing)
     const MCP_THINKING_DELAY_MS: u32 = 2000;
@@ -261,6 +646,23 @@ fn app() -> Html {
         });
     }
 
+    let on_ai_difficulty_change = {
+        let ai_difficulty = ai_difficulty.clone();
+        Callback::from(move |_e: web_sys::Event| {
+            #[cfg(target_arch = "wasm32")]
+            {
+                use wasm_bindgen::JsCast;
+                if let Some(target) = _e.target() {
+                    if let Ok(select) = target.dyn_into::<web_sys::HtmlSelectElement>() {
+                        if let Some(parsed) = AiDifficulty::parse(&select.value()) {
+                            ai_difficulty.set(parsed);
+                        }
+                    }
+                }
+            }
+        })
+    };
+
     // Handle taunt input change
     #[cfg(target_arch = "wasm32")]
     let on_taunt_input = {
@@ -278,22 +680,123 @@ fn app() -> Html {
     let on_taunt_input = Callback::from(move |_: web_sys::InputEvent| {});
 
     // Handle taunt submission
+    // `on_send_taunt` doubles as a local command line: a message starting with `/` is parsed as
+    // a command (modeled on the in-game chat commands Hedgewars ships, e.g. `/help room`)
+    // instead of being sent as a taunt.
     let on_send_taunt = {
         #[cfg(target_arch = "wasm32")]
         let taunt_input = taunt_input.clone();
         #[cfg(target_arch = "wasm32")]
         let log_event = log_event.clone();
+        #[cfg(target_arch = "wasm32")]
+        let game_state = game_state.clone();
+        #[cfg(target_arch = "wasm32")]
+        let loading = loading.clone();
+        #[cfg(target_arch = "wasm32")]
+        let ai_difficulty = ai_difficulty.clone();
+        #[cfg(target_arch = "wasm32")]
+        let game_mode = game_mode.clone();
+        #[cfg(target_arch = "wasm32")]
+        let my_seat = my_seat.clone();
 
         Callback::from(move |_| {
             #[cfg(target_arch = "wasm32")]
             {
                 let message = (*taunt_input).clone();
-                if message.trim().is_empty() {
+                let trimmed = message.trim();
+                if trimmed.is_empty() {
+                    return;
+                }
+
+                if let Some(command) = trimmed.strip_prefix('/') {
+                    taunt_input.set(String::new());
+
+                    let mut parts = command.splitn(2, ' ');
+                    let name = parts.next().unwrap_or("");
+                    let arg = parts.next().unwrap_or("").trim();
+
+                    match name {
+                        "new" => {
+                            let game_state = game_state.clone();
+                            let loading = loading.clone();
+                            let log_event = log_event.clone();
+
+                            game_mode.set(GameMode::VsMcp);
+                            my_seat.set(None);
+                            loading.set(true);
+                            log_event.emit("🔄 Creating new game...".to_string());
+
+                            wasm_bindgen_futures::spawn_local(async move {
+                                match api::create_new_game().await {
+                                    Ok(new_state) => {
+                                        info!("New game created");
+                                        game_state.set(Some(new_state));
+                                        loading.set(false);
+                                        log_event.emit("✨ New game started!".to_string());
+                                    }
+                                    Err(e) => {
+                                        error!("Failed to create new game: {}", e);
+                                        loading.set(false);
+                                        log_event.emit(format!("❌ Failed to create game: {}", e));
+                                    }
+                                }
+                            });
+                        }
+                        "help" => {
+                            log_event.emit(
+                                "📖 Commands: /new, /help, /concede, /difficulty <easy|normal|hard>"
+                                    .to_string(),
+                            );
+                        }
+                        "concede" => {
+                            let game_state = game_state.clone();
+                            let log_event = log_event.clone();
+
+                            log_event.emit("🏳️ Conceding...".to_string());
+
+                            wasm_bindgen_futures::spawn_local(async move {
+                                match api::concede_game().await {
+                                    Ok(new_state) => {
+                                        info!("Game conceded");
+                                        game_state.set(Some(new_state));
+                                    }
+                                    Err(e) => {
+                                        error!("Failed to concede: {}", e);
+                                        log_event.emit(format!("❌ Failed to concede: {}", e));
+                                    }
+                                }
+                            });
+                        }
+                        "difficulty" => {
+                            let lower = arg.to_lowercase();
+                            let capitalized = lower
+                                .get(..1)
+                                .map(|c| c.to_uppercase())
+                                .unwrap_or_default()
+                                + lower.get(1..).unwrap_or("");
+                            match AiDifficulty::parse(&capitalized) {
+                                Some(difficulty) => {
+                                    ai_difficulty.set(difficulty);
+                                    log_event.emit(format!(
+                                        "🎚️ Local AI difficulty set to {}",
+                                        difficulty.as_str()
+                                    ));
+                                }
+                                None => {
+                                    log_event.emit(format!("❌ Unknown difficulty: {}", arg));
+                                }
+                            }
+                        }
+                        other => {
+                            log_event.emit(format!("❌ Unknown command: /{}", other));
+                        }
+                    }
                     return;
                 }
 
                 let taunt_input = taunt_input.clone();
                 let log_event = log_event.clone();
+                let game_state = game_state.clone();
 
                 log_event.emit(format!("💬 Sending taunt: {}", message));
 
@@ -302,7 +805,9 @@ fn app() -> Html {
                         Ok(_) => {
                             info!("Taunt sent successfully");
                             taunt_input.set(String::new());
-                            // State will be updated via SSE
+                            // Usually superseded by the SSE push, but in case it's lagging,
+                            // reconcile against the server's version stamp directly.
+                            reconcile_game_state(&game_state).await;
                         }
                         Err(e) => {
                             error!("Failed to send taunt: {}", e);
@@ -314,18 +819,56 @@ fn app() -> Html {
         })
     };
 
-    let on_new_game = {
+    // Handle a quick-emote button click
+    let on_send_emote = {
+        #[cfg(target_arch = "wasm32")]
+        let log_event = log_event.clone();
+
+        Callback::from(move |emote: EmoteEnum| {
+            #[cfg(target_arch = "wasm32")]
+            {
+                let log_event = log_event.clone();
+                log_event.emit(format!("{} Sending emote", emote.as_str()));
+
+                wasm_bindgen_futures::spawn_local(async move {
+                    match api::send_emote(emote).await {
+                        Ok(_) => {
+                            info!("Emote sent successfully");
+                            // State will be updated via SSE
+                        }
+                        Err(e) => {
+                            error!("Failed to send emote: {}", e);
+                            log_event.emit(format!("❌ Failed to send emote: {}", e));
+                        }
+                    }
+                });
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            let _ = emote;
+        })
+    };
+
+    // Start a fresh game in the given mode. Used directly by the pre-game mode menu
+    // (`GameMode::VsMcp`/`VsLocalAi`/`LocalMultiplayer`) and by the "New Game" button, which
+    // always restarts in `GameMode::VsMcp` regardless of what mode the last game was in.
+    let start_game = {
         #[cfg(target_arch = "wasm32")]
         let game_state = game_state.clone();
         let loading = loading.clone();
         let log_event = log_event.clone();
+        let game_mode = game_mode.clone();
+        let my_seat = my_seat.clone();
 
-        Callback::from(move |_| {
+        Callback::from(move |mode: GameMode| {
             #[cfg(target_arch = "wasm32")]
             let game_state = game_state.clone();
             let loading = loading.clone();
             let log_event = log_event.clone();
+            let game_mode = game_mode.clone();
+            let my_seat = my_seat.clone();
 
+            game_mode.set(mode);
+            my_seat.set(None);
             loading.set(true);
             log_event.emit("🔄 Creating new game...".to_string());
 
@@ -348,6 +891,86 @@ fn app() -> Html {
         })
     };
 
+    let on_new_game = {
+        let start_game = start_game.clone();
+        Callback::from(move |_| start_game.emit(GameMode::VsMcp))
+    };
+
+    // Handle "Find Opponent" click: request a pairing and start polling for a match
+    let on_find_opponent = {
+        #[cfg(target_arch = "wasm32")]
+        let game_state = game_state.clone();
+        #[cfg(target_arch = "wasm32")]
+        let game_mode = game_mode.clone();
+        #[cfg(target_arch = "wasm32")]
+        let my_seat = my_seat.clone();
+        #[cfg(target_arch = "wasm32")]
+        let opponent_seat = opponent_seat.clone();
+        let log_event = log_event.clone();
+
+        Callback::from(move |_| {
+            #[cfg(target_arch = "wasm32")]
+            {
+                let game_state = game_state.clone();
+                let game_mode = game_mode.clone();
+                let my_seat = my_seat.clone();
+                let opponent_seat = (*opponent_seat).clone();
+                let log_event = log_event.clone();
+
+                log_event.emit("🔎 Looking for an opponent...".to_string());
+
+                wasm_bindgen_futures::spawn_local(async move {
+                    match api::request_pairing().await {
+                        Ok(response) => {
+                            poll_pairing_status(
+                                response.pairing_id,
+                                game_state,
+                                game_mode,
+                                my_seat,
+                                opponent_seat,
+                                log_event,
+                            );
+                        }
+                        Err(e) => {
+                            error!("Failed to request pairing: {}", e);
+                            log_event.emit(format!("❌ Failed to find opponent: {}", e));
+                        }
+                    }
+                });
+            }
+        })
+    };
+
+    // Forfeit a human-vs-human match on page close, if one was in progress
+    {
+        #[cfg(target_arch = "wasm32")]
+        let opponent_seat = opponent_seat.clone();
+
+        use_effect_with((), move |_| {
+            #[cfg(target_arch = "wasm32")]
+            {
+                let opponent_seat = (*opponent_seat).clone();
+                let closure = Closure::wrap(Box::new(move |_: web_sys::Event| {
+                    if let Some((game_id, token)) = opponent_seat.borrow().clone() {
+                        wasm_bindgen_futures::spawn_local(async move {
+                            let _ = api::leave_game(game_id, token).await;
+                        });
+                    }
+                }) as Box<dyn FnMut(_)>);
+
+                if let Some(window) = web_sys::window() {
+                    let _ = window.add_event_listener_with_callback(
+                        "beforeunload",
+                        closure.as_ref().unchecked_ref(),
+                    );
+                }
+                closure.forget();
+            }
+
+            || ()
+        });
+    }
+
     let game_info = if *loading {
         html! { <p>{"Game is loading..."}</p> }
     } else if let Some(ref err) = *error_msg {
@@ -359,12 +982,15 @@ fn app() -> Html {
             }
             shared::GameStatus::Won(player) => format!("{} wins!", player),
             shared::GameStatus::Draw => "It's a draw!".to_string(),
+            shared::GameStatus::Abandoned => "Game abandoned (idle too long)".to_string(),
         };
 
+        let mine = my_player(*game_mode, state, *my_seat);
+
         // Show turn indicator flash at game start (when move_history is empty or 1 move)
         let turn_indicator =
             if state.status == GameStatus::InProgress && state.move_history.len() <= 1 {
-                if state.current_turn == state.human_player {
+                if state.current_turn == mine {
                     html! { <div class="turn-indicator flash">{"🎯 YOUR TURN!"}</div> }
                 } else {
                     html! { <div class="turn-indicator flash">{"⏳ Opponent's turn..."}</div> }
@@ -375,12 +1001,41 @@ fn app() -> Html {
 
         html! {
             <>
-                <p>{format!("You are {}. {}", state.human_player, status_text)}</p>
+                <p>{format!("You are {}. {}", mine, status_text)}</p>
                 {turn_indicator}
             </>
         }
     } else {
-        html! { <p>{"Click 'New Game' to start"}</p> }
+        html! { <p>{"Choose a mode below to start"}</p> }
+    };
+
+    // Pre-game mode menu: shown only before a game exists, mirroring how the rest of the UI
+    // keys off `game_state`. Each button starts a fresh game in that mode.
+    let mode_menu = if game_state.is_none() && !*loading && error_msg.is_none() {
+        let on_vs_mcp = {
+            let start_game = start_game.clone();
+            Callback::from(move |_| start_game.emit(GameMode::VsMcp))
+        };
+        let on_vs_local_ai = {
+            let start_game = start_game.clone();
+            Callback::from(move |_| start_game.emit(GameMode::VsLocalAi))
+        };
+        let on_local_multiplayer = {
+            let start_game = start_game.clone();
+            Callback::from(move |_| start_game.emit(GameMode::LocalMultiplayer))
+        };
+
+        html! {
+            <div class="game-mode-menu">
+                <h3>{"Choose how to play"}</h3>
+                <button class="btn-primary" onclick={on_vs_mcp}>{"Single Player vs MCP"}</button>
+                <button class="btn-primary" onclick={on_vs_local_ai}>{"Single Player vs Local AI"}</button>
+                <button class="btn-primary" onclick={on_local_multiplayer}>{"Local Multiplayer"}</button>
+                <button class="btn-primary" onclick={on_find_opponent.clone()}>{"Networked Multiplayer"}</button>
+            </div>
+        }
+    } else {
+        html! {}
     };
 
     // Handle drag start
@@ -404,6 +1059,9 @@ fn app() -> Html {
     let on_drop = {
         let game_state = game_state.clone();
         let log_event = log_event.clone();
+        let ai_difficulty = ai_difficulty.clone();
+        let game_mode = game_mode.clone();
+        let my_seat = my_seat.clone();
 
         Callback::from(move |(e, row, col): (DragEvent, u8, u8)| {
             if let Some(drag_event) = e.dyn_ref::<web_sys::DragEvent>() {
@@ -412,6 +1070,8 @@ fn app() -> Html {
 
             let game_state = game_state.clone();
             let log_event = log_event.clone();
+            let ai_difficulty = ai_difficulty.clone();
+            let mode = *game_mode;
 
             // Check if it's a valid move
             if let Some(ref state) = *game_state {
@@ -421,8 +1081,8 @@ fn app() -> Html {
                     return;
                 }
 
-                // Can't move if not human's turn
-                if state.current_turn != state.human_player {
+                // Can't move if it's not a seat this client owns
+                if state.current_turn != my_player(mode, state, *my_seat) {
                     log_event.emit("⚠️ It's not your turn!".to_string());
                     return;
                 }
@@ -438,11 +1098,54 @@ fn app() -> Html {
 
                 wasm_bindgen_futures::spawn_local({
                     let log_event = log_event.clone();
+                    let ai_difficulty = ai_difficulty.clone();
+                    let game_state = game_state.clone();
                     async move {
                         match api::make_move(row, col).await {
-                            Ok(_) => {
+                            Ok(new_state) => {
                                 info!("Move made successfully");
-                                // State will be updated via SSE
+                                // Usually superseded by the SSE push, but reconciling here too
+                                // means the board doesn't sit on stale state if SSE is lagging.
+                                if state_is_newer(&game_state, &new_state) {
+                                    game_state.set(Some(new_state));
+                                }
+
+                                match mode {
+                                    // No MCP agent attached in this mode, so play the AI's reply
+                                    // immediately instead of waiting on a fallback timer for an
+                                    // agent that was never going to respond.
+                                    GameMode::VsLocalAi => {
+                                        if new_state.status == GameStatus::InProgress
+                                            && let Some((row, col)) = ai::get_ai_move(
+                                                &new_state.board,
+                                                new_state.current_turn,
+                                                *ai_difficulty,
+                                            )
+                                        {
+                                            match api::make_ai_move_in(None, row, col).await {
+                                                Ok(ai_state) => {
+                                                    if state_is_newer(&game_state, &ai_state) {
+                                                        game_state.set(Some(ai_state));
+                                                    }
+                                                }
+                                                Err(e) => {
+                                                    error!("Local AI move failed: {}", e);
+                                                    log_event.emit(format!(
+                                                        "❌ Local AI move failed: {}",
+                                                        e
+                                                    ));
+                                                }
+                                            }
+                                        }
+                                    }
+                                    // Both seats belong to this client; no opponent to wait on.
+                                    GameMode::LocalMultiplayer => {}
+                                    // The networked opponent is a real browser on the other end.
+                                    GameMode::VsNetworked => {}
+                                    GameMode::VsMcp => {
+                                        schedule_local_ai_fallback(log_event, *ai_difficulty);
+                                    }
+                                }
                             }
                             Err(e) => {
                                 error!("Failed to make move: {}", e);
@@ -550,16 +1253,47 @@ fn app() -> Html {
         })
         .collect::<Html>();
 
+    // Rolling window over `state.taunts` for display: the full history is still kept
+    // server-side, this just bounds how many `taunt-message` nodes get rendered.
+    let capped_taunts: Vec<_> = game_state
+        .as_ref()
+        .map(|state| {
+            let taunts = &state.taunts;
+            if taunts.len() > max_taunt_items {
+                taunts[taunts.len() - max_taunt_items..].to_vec()
+            } else {
+                taunts.clone()
+            }
+        })
+        .unwrap_or_default();
+
     // Draw overlay
     let draw_overlay = if let Some(ref state) = *game_state {
-        if state.status == GameStatus::Draw {
-            html! {
+        match state.status {
+            GameStatus::Draw => html! {
                 <div class="game-overlay">
                     <div class="draw-text">{"DRAW"}</div>
                 </div>
+            },
+            // Connecting-line overlay over the three winning cells, oriented to match how the
+            // game was won (the `winning-cell` highlight on `board_cells` marks which cells).
+            GameStatus::Won(_) => {
+                let win_type = state.winning_line.as_ref().and_then(|line| line.win_type);
+                match win_line_orientation_class(win_type) {
+                    Some(orientation_class) => html! {
+                        <div class="game-overlay">
+                            <div class={format!("win-line {}", orientation_class)}></div>
+                        </div>
+                    },
+                    None => html! {},
+                }
             }
-        } else {
-            html! {}
+            GameStatus::InProgress => html! {},
+            GameStatus::Abandoned => html! {
+                <div class="game-overlay">
+                    <div class="draw-text">{"ABANDONED"}</div>
+                </div>
+            },
         }
     } else {
         html! {}
@@ -567,11 +1301,12 @@ fn app() -> Html {
 
     // Draggable mark component
     let draggable_mark = if let Some(ref state) = *game_state {
-        let is_human_turn = state.current_turn == state.human_player;
+        let mine = my_player(*game_mode, state, *my_seat);
+        let is_my_turn = state.current_turn == mine;
         let is_game_active = state.status == GameStatus::InProgress;
-        let is_enabled = is_human_turn && is_game_active;
+        let is_enabled = is_my_turn && is_game_active;
 
-        let mark_text = format!("{}", state.human_player);
+        let mark_text = format!("{}", mine);
         let mark_class = if is_enabled {
             "draggable-mark enabled"
         } else {
@@ -602,23 +1337,38 @@ fn app() -> Html {
         html! {}
     };
 
-    // MCP thinking indicator
+    // MCP thinking indicator (relabeled outside VsMcp, where there's no agent to wait on)
     let thinking_indicator = if *mcp_thinking {
+        let label = match *game_mode {
+            GameMode::VsMcp => "MCP Agent Thinking...",
+            GameMode::VsLocalAi => "Local AI Thinking...",
+            GameMode::LocalMultiplayer => "Waiting for the other player...",
+            GameMode::VsNetworked => "Opponent's turn...",
+        };
         html! {
             <div class="mcp-thinking-indicator">
-                <span class="thinking-text">{"MCP Agent Thinking..."}</span>
+                <span class="thinking-text">{label}</span>
             </div>
         }
     } else {
         html! {}
     };
 
+    let (connection_status_class, connection_status_text) = match *connection_status {
+        ConnectionStatus::Live => ("live", "🟢 Live"),
+        ConnectionStatus::Reconnecting => ("reconnecting", "🟡 Reconnecting..."),
+        ConnectionStatus::Polling => ("polling", "🔵 Polling"),
+    };
+
     html! {
         <div class="app-container">
             <header class="app-header">
                 <div class="header-title">
                     <h1>{"TTTTT"}</h1>
                     <span class="subtitle">{"Trash Talkin' Tic-Tac-Toe"}</span>
+                    <span class={format!("connection-status connection-status-{}", connection_status_class)}>
+                        {connection_status_text}
+                    </span>
                 </div>
                 <a href="https://github.com/sw-game-dev/game-mcp-poc" target="_blank" class="github-link" title="Source code">
                     <div class="github-corner">
@@ -633,6 +1383,7 @@ fn app() -> Html {
             <div class="game-info">
                 {game_info}
                 {thinking_indicator}
+                {mode_menu}
             </div>
             <div class="game-layout">
                 <div class="left-panel">
@@ -649,6 +1400,26 @@ fn app() -> Html {
                         <button class="btn-primary" onclick={on_new_game} disabled={*loading}>
                             {"New Game"}
                         </button>
+                        <button class="btn-primary" onclick={on_find_opponent} disabled={*loading}>
+                            {"Find Opponent"}
+                        </button>
+                        <select class="ai-difficulty-select" onchange={on_ai_difficulty_change}>
+                            {
+                                [AiDifficulty::Easy, AiDifficulty::Normal, AiDifficulty::Hard]
+                                    .into_iter()
+                                    .map(|difficulty| {
+                                        html! {
+                                            <option
+                                                value={difficulty.as_str()}
+                                                selected={difficulty == *ai_difficulty}
+                                            >
+                                                {format!("Local AI: {}", difficulty.as_str())}
+                                            </option>
+                                        }
+                                    })
+                                    .collect::<Html>()
+                            }
+                        </select>
                     </div>
                     <div class="log-container">
                         <h3>{"Event Log"}</h3>
@@ -662,23 +1433,24 @@ fn app() -> Html {
                 <h3>{"💬 Trash Talk"}</h3>
                 <div class="taunt-display" id="taunt-display">
                     {
-                        if let Some(ref state) = *game_state {
-                            if state.taunts.is_empty() {
+                        if game_state.is_some() {
+                            if capped_taunts.is_empty() {
                                 html! { <div class="taunt-empty">{"No taunts yet..."}</div> }
                             } else {
-                                // Show all taunts in chronological order (oldest first)
-                                let taunt_count = state.taunts.len();
-                                let taunt_messages: Vec<_> = state.taunts.iter()
+                                // Show the capped window of taunts in chronological order (oldest first)
+                                let taunt_count = capped_taunts.len();
+                                let taunt_messages: Vec<_> = capped_taunts.iter()
                                     .enumerate()
                                     .map(|(idx, taunt)| {
-                                        let (label, label_class) = match &taunt.source {
+                                        let (label, label_class) = match &taunt.sender {
                                             Some(MoveSource::UI) => ("You: ", "taunt-label taunt-label-ui"),
                                             Some(MoveSource::MCP) => ("MCP Agent: ", "taunt-label taunt-label-mcp"),
+                                            Some(MoveSource::AI) => ("Local AI: ", "taunt-label taunt-label-ai"),
                                             None => ("Unknown: ", "taunt-label"),
                                         };
 
                                         // Build class string with user-taunt for UI messages
-                                        let is_user = matches!(&taunt.source, Some(MoveSource::UI));
+                                        let is_user = matches!(&taunt.sender, Some(MoveSource::UI));
                                         let is_latest = idx == taunt_count - 1;
 
                                         let class = match (is_user, is_latest) {
@@ -688,6 +1460,18 @@ fn app() -> Html {
                                             (false, false) => "taunt-message",
                                         };
 
+                                        // A quick emote is stored as an ordinary taunt whose body
+                                        // is one of `EmoteEnum`'s emoji (see `add_emote_in_game`);
+                                        // still carries the usual You:/MCP Agent: label, but its
+                                        // body renders larger, like a reaction rather than a line
+                                        // of chat.
+                                        let is_emote = EmoteEnum::from_body(&taunt.body).is_some();
+                                        let text_class = if is_emote {
+                                            "taunt-text emote-text"
+                                        } else {
+                                            "taunt-text"
+                                        };
+
                                         // Format timestamp for hover text (WASM only)
                                         #[cfg(target_arch = "wasm32")]
                                         {
@@ -695,7 +1479,7 @@ fn app() -> Html {
                                             html! {
                                                 <div class={class}>
                                                     <span class={label_class} title={timestamp_text}>{label}</span>
-                                                    <span class="taunt-text">{&taunt.message}</span>
+                                                    <span class={text_class}>{&taunt.body}</span>
                                                 </div>
                                             }
                                         }
@@ -704,7 +1488,7 @@ fn app() -> Html {
                                         html! {
                                             <div class={class}>
                                                 <span class={label_class}>{label}</span>
-                                                <span class="taunt-text">{&taunt.message}</span>
+                                                <span class={text_class}>{&taunt.body}</span>
                                             </div>
                                         }
                                     })
@@ -716,6 +1500,20 @@ fn app() -> Html {
                         }
                     }
                 </div>
+                <div class="emote-bar">
+                    {
+                        EmoteEnum::ALL.iter().map(|emote| {
+                            let emote = *emote;
+                            let onclick = {
+                                let on_send_emote = on_send_emote.clone();
+                                Callback::from(move |_| on_send_emote.emit(emote))
+                            };
+                            html! {
+                                <button class="btn-emote" onclick={onclick}>{emote.as_str()}</button>
+                            }
+                        }).collect::<Html>()
+                    }
+                </div>
                 <div class="taunt-input-container">
                     <input
                         type="text"
@@ -728,6 +1526,7 @@ fn app() -> Html {
                             {
                                 let taunt_input = taunt_input.clone();
                                 let log_event = log_event.clone();
+                                let game_state = game_state.clone();
                                 Callback::from(move |e: KeyboardEvent| {
                                     if e.key() == "Enter" {
                                         let message = (*taunt_input).clone();
@@ -737,6 +1536,7 @@ fn app() -> Html {
 
                                         let taunt_input = taunt_input.clone();
                                         let log_event = log_event.clone();
+                                        let game_state = game_state.clone();
 
                                         log_event.emit(format!("💬 Sending taunt: {}", message));
 
@@ -745,6 +1545,7 @@ fn app() -> Html {
                                                 Ok(_) => {
                                                     info!("Taunt sent successfully");
                                                     taunt_input.set(String::new());
+                                                    reconcile_game_state(&game_state).await;
                                                 }
                                                 Err(e) => {
                                                     error!("Failed to send taunt: {}", e);
@@ -771,20 +1572,21 @@ fn app() -> Html {
                 </div>
                 {
                     // Show taunt history
-                    if let Some(ref state) = *game_state {
-                        if state.taunts.len() > 1 {
-                            let taunt_history: Vec<_> = state.taunts.iter()
+                    if game_state.is_some() {
+                        if capped_taunts.len() > 1 {
+                            let taunt_history: Vec<_> = capped_taunts.iter()
                                 .rev()
                                 .skip(1) // Skip the latest (already shown above)
                                 .take(3) // Show last 3
                                 .map(|taunt| {
-                                    let prefix = match &taunt.source {
+                                    let prefix = match &taunt.sender {
                                         Some(MoveSource::UI) => "You: ",
                                         Some(MoveSource::MCP) => "MCP: ",
+                                        Some(MoveSource::AI) => "Local AI: ",
                                         None => "",
                                     };
                                     html! {
-                                        <div class="taunt-history-item">{format!("{}{}", prefix, taunt.message)}</div>
+                                        <div class="taunt-history-item">{format!("{}{}", prefix, taunt.body)}</div>
                                     }
                                 })
                                 .collect();
@@ -935,4 +1737,78 @@ mod tests {
         let can_drop = cell == Cell::Empty;
         assert!(!can_drop, "Should not be able to drop on occupied cell");
     }
+
+    fn game_for_my_player(current_turn: Player, human_player: Player) -> GameState {
+        GameState {
+            id: "g".to_string(),
+            board: [[Cell::Empty; 3]; 3],
+            current_turn,
+            human_player,
+            ai_player: human_player.opponent(),
+            status: GameStatus::InProgress,
+            move_history: vec![],
+            taunts: vec![],
+            winning_line: None,
+            turn_started_at: 0,
+            turn_limit_secs: None,
+            ai_difficulty: None,
+            version: 0,
+            previous_game_id: None,
+        }
+    }
+
+    #[test]
+    fn test_my_player_is_human_player_vs_mcp_and_local_ai() {
+        let state = game_for_my_player(Player::O, Player::X);
+        assert_eq!(my_player(GameMode::VsMcp, &state, None), Player::X);
+        assert_eq!(my_player(GameMode::VsLocalAi, &state, None), Player::X);
+    }
+
+    #[test]
+    fn test_my_player_is_current_turn_in_local_multiplayer() {
+        let mut state = game_for_my_player(Player::X, Player::X);
+        assert_eq!(my_player(GameMode::LocalMultiplayer, &state, None), Player::X);
+
+        state.current_turn = Player::O;
+        assert_eq!(my_player(GameMode::LocalMultiplayer, &state, None), Player::O);
+    }
+
+    #[test]
+    fn test_my_player_is_the_paired_seat_when_networked() {
+        // The joining client's `my_seat` can differ from `human_player`, which here just
+        // reflects whichever seat the *creator* of the lobby game happened to be assigned.
+        let state = game_for_my_player(Player::O, Player::X);
+        assert_eq!(my_player(GameMode::VsNetworked, &state, Some(Player::O)), Player::O);
+    }
+
+    #[test]
+    fn test_my_player_falls_back_to_human_player_before_a_seat_is_known() {
+        let state = game_for_my_player(Player::X, Player::X);
+        assert_eq!(my_player(GameMode::VsNetworked, &state, None), Player::X);
+    }
+
+    #[test]
+    fn test_win_line_orientation_class_matches_win_type() {
+        assert_eq!(
+            win_line_orientation_class(Some(WinType::Horizontal)),
+            Some("win-line-horizontal")
+        );
+        assert_eq!(
+            win_line_orientation_class(Some(WinType::Vertical)),
+            Some("win-line-vertical")
+        );
+        assert_eq!(
+            win_line_orientation_class(Some(WinType::DiagonalTopLeft)),
+            Some("win-line-diagonal-tl")
+        );
+        assert_eq!(
+            win_line_orientation_class(Some(WinType::DiagonalTopRight)),
+            Some("win-line-diagonal-tr")
+        );
+    }
+
+    #[test]
+    fn test_win_line_orientation_class_none_for_legacy_games_without_a_win_type() {
+        assert_eq!(win_line_orientation_class(None), None);
+    }
 }
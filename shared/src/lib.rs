@@ -44,15 +44,96 @@ pub enum GameStatus {
     InProgress,
     Won(Player),
     Draw,
+    /// Set by the background sweep (see `db::repository::cleanup_stale_games`) for a game whose
+    /// turn clock ran out with nobody watching, distinct from a normal `Won` forfeit (made by a
+    /// live call into `GameManager`, which can attribute the win to the opponent still present).
+    Abandoned,
 }
 
-/// Source of the move (UI or MCP)
+/// Tunable minimax-backed AI strength, selected per game via `GameState::ai_choose_move`.
+/// Independent of the legacy `Bot`/`Difficulty` auto-play strategy: this ranks every move by
+/// minimax score and picks uniformly among the top-scoring few, so weaker levels can blunder
+/// instead of following a fixed heuristic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AiDifficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl AiDifficulty {
+    /// Parse a difficulty name as used in MCP params and database storage. `None` on anything
+    /// unrecognized, so callers can decide whether that means "leave unset" or "reject".
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "Easy" => Some(AiDifficulty::Easy),
+            "Normal" => Some(AiDifficulty::Normal),
+            "Hard" => Some(AiDifficulty::Hard),
+            _ => None,
+        }
+    }
+
+    /// The name this difficulty round-trips through `parse`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AiDifficulty::Easy => "Easy",
+            AiDifficulty::Normal => "Normal",
+            AiDifficulty::Hard => "Hard",
+        }
+    }
+}
+
+/// Predefined quick-emote vocabulary for `send_emote`, mirroring the `EmoteEnum`/
+/// `SendEmoteRequest` design from the Four Line Dropper project: unlike the free-text
+/// `TauntRequest` message, this is a fixed set of reactions the MCP agent can reliably
+/// interpret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EmoteEnum {
+    Cool,
+    Fire,
+    Steam,
+    Handshake,
+    Cry,
+}
+
+impl EmoteEnum {
+    /// Every emote, in the order the quick-emote bar renders them.
+    pub const ALL: [EmoteEnum; 5] = [
+        EmoteEnum::Cool,
+        EmoteEnum::Fire,
+        EmoteEnum::Steam,
+        EmoteEnum::Handshake,
+        EmoteEnum::Cry,
+    ];
+
+    /// The emoji this variant renders as, and the `ChatMessage::body` it's stored as.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmoteEnum::Cool => "😎",
+            EmoteEnum::Fire => "🔥",
+            EmoteEnum::Steam => "😤",
+            EmoteEnum::Handshake => "🤝",
+            EmoteEnum::Cry => "😭",
+        }
+    }
+
+    /// The reverse of `as_str`: which emote (if any) a `ChatMessage::body` is. Since emotes are
+    /// stored as ordinary taunt text (see `GameManager::add_emote_in_game`), this is how the
+    /// frontend tells an emote apart from a free-text taunt to render it distinctly.
+    pub fn from_body(body: &str) -> Option<EmoteEnum> {
+        Self::ALL.into_iter().find(|emote| emote.as_str() == body)
+    }
+}
+
+/// Source of the move (UI, MCP, or a locally computed AI opponent move)
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MoveSource {
     #[serde(rename = "UI")]
     UI,
     #[serde(rename = "MCP")]
     MCP,
+    #[serde(rename = "AI")]
+    AI,
 }
 
 /// A single move in the game
@@ -70,6 +151,30 @@ pub struct Move {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WinningLine {
     pub positions: Vec<(u8, u8)>,
+    /// The line's orientation, for highlighting a connecting overlay and describing the win in
+    /// words ("X won diagonally"). `None` for lines persisted before this field existed.
+    #[serde(default)]
+    pub win_type: Option<WinType>,
+}
+
+/// The orientation of a `WinningLine`, derived from the direction `check_winner` scanned it in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WinType {
+    Horizontal,
+    Vertical,
+    DiagonalTopLeft,
+    DiagonalTopRight,
+}
+
+impl WinType {
+    /// Short adverb for event-log/taunt text, e.g. "X won diagonally".
+    pub fn adverb(self) -> &'static str {
+        match self {
+            WinType::Horizontal => "horizontally",
+            WinType::Vertical => "vertically",
+            WinType::DiagonalTopLeft | WinType::DiagonalTopRight => "diagonally",
+        }
+    }
 }
 
 /// Complete game state
@@ -82,9 +187,132 @@ pub struct GameState {
     pub ai_player: Player,
     pub status: GameStatus,
     pub move_history: Vec<Move>,
-    pub taunts: Vec<String>,
+    pub taunts: Vec<ChatMessage>,
     #[serde(default)]
     pub winning_line: Option<WinningLine>,
+    /// Unix timestamp the current turn began. Reset whenever `current_turn` changes.
+    #[serde(default)]
+    pub turn_started_at: i64,
+    /// Per-turn time budget in seconds, or `None` for no limit (the default for existing games).
+    #[serde(default)]
+    pub turn_limit_secs: Option<u32>,
+    /// AI strength for `GameState::ai_choose_move`. `None` leaves the AI turn to whatever
+    /// strategy the caller otherwise uses (e.g. the legacy `Bot`/`Difficulty` auto-player).
+    #[serde(default)]
+    pub ai_difficulty: Option<AiDifficulty>,
+    /// Monotonically increasing counter bumped on every move, taunt, or status change, so
+    /// clients that persist or snapshot a `GameState` can cheaply tell whether it's stale
+    /// without comparing the whole board (see `changed_since`).
+    #[serde(default)]
+    pub version: u64,
+    /// The game this one is a rematch of, if any (see `GameManager::request_rematch`). `None`
+    /// for games started via `new_game`/`create_new_game`/`create_game`/`restart_game`.
+    #[serde(default)]
+    pub previous_game_id: Option<String>,
+}
+
+impl GameState {
+    /// If the current turn has overrun `turn_limit_secs`, return the `GameStatus` that should
+    /// result: a win for whoever wasn't on the clock. Returns `None` if the game isn't
+    /// `InProgress`, has no turn limit, or the limit hasn't elapsed yet.
+    pub fn check_timeout(&self, now: i64) -> Option<GameStatus> {
+        if self.status != GameStatus::InProgress {
+            return None;
+        }
+        let limit = self.turn_limit_secs?;
+        if now - self.turn_started_at < limit as i64 {
+            return None;
+        }
+        Some(GameStatus::Won(self.current_turn.opponent()))
+    }
+
+    /// Whether this state has advanced past `version` — true the moment any move, taunt, or
+    /// status change bumps `self.version` above it.
+    pub fn changed_since(&self, version: u64) -> bool {
+        self.version > version
+    }
+
+    /// Encode to a compact MessagePack byte string, for on-disk storage or inter-process
+    /// transfer cheaper than JSON.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, GameError> {
+        rmp_serde::to_vec(self).map_err(GameError::from)
+    }
+
+    /// Decode a byte string produced by `to_bytes` back into a `GameState`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<GameState, GameError> {
+        rmp_serde::from_slice(bytes).map_err(GameError::from)
+    }
+}
+
+// `rmp_serde`'s error types can't be embedded directly in a `GameError` variant via thiserror's
+// `#[from]`: `GameError` derives `Clone`/`Serialize`/`Deserialize`, which `rmp_serde`'s errors
+// don't implement. These manual `From` impls give `to_bytes`/`from_bytes` the same `?`-friendly
+// conversion while keeping `GameError` itself cheaply cloneable and (de)serializable.
+impl From<rmp_serde::encode::Error> for GameError {
+    fn from(err: rmp_serde::encode::Error) -> Self {
+        GameError::EncodeError {
+            message: err.to_string(),
+        }
+    }
+}
+
+impl From<rmp_serde::decode::Error> for GameError {
+    fn from(err: rmp_serde::decode::Error) -> Self {
+        GameError::DecodeError {
+            message: err.to_string(),
+        }
+    }
+}
+
+/// A single chat/taunt message attached to a game. `id` is the row's insertion order and is
+/// the cursor `get_taunts`'s CHATHISTORY-style `before`/`after` pagination pages around.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub id: u64,
+    /// `UI` messages come from the human player in the browser; `MCP` messages come from an
+    /// agent driving the game over the protocol. `None` for messages predating this field.
+    pub sender: Option<MoveSource>,
+    pub body: String,
+    pub timestamp: i64,
+}
+
+/// Cross-game win/loss/draw tally for one side of the human-vs-AI matchup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerStats {
+    pub player_id: String,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub games_played: u32,
+    /// Unix timestamp of this player's most recently recorded game, or `None` if it has none
+    pub last_played: Option<i64>,
+    /// Cumulative points from recorded results (see `GameRepository`'s `WIN_POINTS`/`DRAW_POINTS`/
+    /// `LOSS_POINTS`), independent of the plain win/loss/draw tally above
+    pub score: i64,
+}
+
+/// One row of the `get_leaderboard` view: a player's tally plus its derived win rate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub player_id: String,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub games_played: u32,
+    pub last_played: Option<i64>,
+    /// `wins / (wins + losses + draws)`, or `0.0` if the player has no recorded games
+    pub win_rate: f64,
+    pub score: i64,
+}
+
+/// A human-vs-human pairing lobby entry: a creator waiting for (or already paired with) an
+/// opponent, independent of the single-AI-opponent `GameState` flow
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenGame {
+    pub id: String,
+    pub creator_id: String,
+    pub opponent_id: Option<String>,
+    pub status: String,
 }
 
 /// API request to make a move
@@ -92,12 +320,65 @@ pub struct GameState {
 pub struct MakeMoveRequest {
     pub row: u8,
     pub col: u8,
+    /// Session to move in. Omit to use the implicit current game.
+    #[serde(default)]
+    pub game_id: Option<String>,
+    /// Set by the frontend's local AI opponent (see `ai::get_ai_move`) so the move is recorded
+    /// with `MoveSource::AI` instead of `MoveSource::UI`. Only honored for `Role::Player` tokens.
+    #[serde(default)]
+    pub local_ai: bool,
 }
 
 /// API request to add a taunt
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TauntRequest {
     pub message: String,
+    /// Session to taunt in. Omit to use the implicit current game.
+    #[serde(default)]
+    pub game_id: Option<String>,
+}
+
+/// Response to `request_pairing`: a pairing id to poll via `pairing_status`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairingRequestResponse {
+    pub pairing_id: String,
+}
+
+/// Response to `pairing_status`: `status` is `"Waiting"` until an opponent joins the pairing,
+/// then `"Matched"`, with the lobby session's id and the asking player's own seat and token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairingStatusResponse {
+    pub status: String,
+    #[serde(default)]
+    pub game_id: Option<String>,
+    #[serde(default)]
+    pub seat: Option<Player>,
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+/// API request to leave a human-vs-human match, forfeiting it to the other seat
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaveGameRequest {
+    pub game_id: String,
+    pub token: String,
+}
+
+/// API request to concede the game, forfeiting it to the opponent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConcedeRequest {
+    /// Session to concede. Omit to use the implicit current game.
+    #[serde(default)]
+    pub game_id: Option<String>,
+}
+
+/// API request to send a predefined quick emote (see `EmoteEnum`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendEmoteRequest {
+    pub emote: EmoteEnum,
+    /// Session to emote in. Omit to use the implicit current game.
+    #[serde(default)]
+    pub game_id: Option<String>,
 }
 
 /// Error types for the game
@@ -115,12 +396,24 @@ pub enum GameError {
     #[error("Game is already over: {status:?}")]
     GameOver { status: GameStatus },
 
+    #[error("Game is still in progress")]
+    GameStillInProgress,
+
     #[error("Game not found")]
     GameNotFound,
 
+    #[error("{player:?}'s turn expired")]
+    TurnExpired { player: Player },
+
     #[error("Database error: {message}")]
     DatabaseError { message: String },
 
+    #[error("Failed to encode game state: {message}")]
+    EncodeError { message: String },
+
+    #[error("Failed to decode game state: {message}")]
+    DecodeError { message: String },
+
     #[error("Internal error: {message}")]
     InternalError { message: String },
 }
@@ -139,4 +432,118 @@ mod tests {
     fn test_cell_default() {
         assert_eq!(Cell::default(), Cell::Empty);
     }
+
+    fn game_for_timeout(turn_limit_secs: Option<u32>) -> GameState {
+        GameState {
+            id: "g".to_string(),
+            board: [[Cell::Empty; 3]; 3],
+            current_turn: Player::X,
+            human_player: Player::X,
+            ai_player: Player::O,
+            status: GameStatus::InProgress,
+            move_history: vec![],
+            taunts: vec![],
+            winning_line: None,
+            turn_started_at: 1_000,
+            turn_limit_secs,
+            ai_difficulty: None,
+            version: 0,
+            previous_game_id: None,
+        }
+    }
+
+    #[test]
+    fn test_check_timeout_none_without_limit() {
+        let game = game_for_timeout(None);
+        assert_eq!(game.check_timeout(10_000), None);
+    }
+
+    #[test]
+    fn test_check_timeout_none_before_limit_elapses() {
+        let game = game_for_timeout(Some(30));
+        assert_eq!(game.check_timeout(1_010), None);
+    }
+
+    #[test]
+    fn test_check_timeout_awards_win_to_opponent_after_limit() {
+        let game = game_for_timeout(Some(30));
+        assert_eq!(game.check_timeout(1_030), Some(GameStatus::Won(Player::O)));
+    }
+
+    #[test]
+    fn test_check_timeout_none_when_game_already_over() {
+        let mut game = game_for_timeout(Some(30));
+        game.status = GameStatus::Won(Player::X);
+        assert_eq!(game.check_timeout(10_000), None);
+    }
+
+    #[test]
+    fn test_ai_difficulty_parse_recognizes_names() {
+        assert_eq!(AiDifficulty::parse("Easy"), Some(AiDifficulty::Easy));
+        assert_eq!(AiDifficulty::parse("Normal"), Some(AiDifficulty::Normal));
+        assert_eq!(AiDifficulty::parse("Hard"), Some(AiDifficulty::Hard));
+        assert_eq!(AiDifficulty::parse("nonsense"), None);
+    }
+
+    #[test]
+    fn test_ai_difficulty_as_str_round_trips_through_parse() {
+        for difficulty in [AiDifficulty::Easy, AiDifficulty::Normal, AiDifficulty::Hard] {
+            assert_eq!(AiDifficulty::parse(difficulty.as_str()), Some(difficulty));
+        }
+    }
+
+    #[test]
+    fn test_win_type_adverb() {
+        assert_eq!(WinType::Horizontal.adverb(), "horizontally");
+        assert_eq!(WinType::Vertical.adverb(), "vertically");
+        assert_eq!(WinType::DiagonalTopLeft.adverb(), "diagonally");
+        assert_eq!(WinType::DiagonalTopRight.adverb(), "diagonally");
+    }
+
+    #[test]
+    fn test_to_bytes_from_bytes_round_trip() {
+        let mut game = game_for_timeout(Some(30));
+        game.move_history.push(Move {
+            player: Player::X,
+            row: 0,
+            col: 0,
+            timestamp: 1,
+            source: Some(MoveSource::UI),
+        });
+
+        let bytes = game.to_bytes().unwrap();
+        let decoded = GameState::from_bytes(&bytes).unwrap();
+
+        assert_eq!(decoded, game);
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_garbage() {
+        let err = GameState::from_bytes(&[0xff, 0x00, 0x01]).unwrap_err();
+        assert!(matches!(err, GameError::DecodeError { .. }));
+    }
+
+    #[test]
+    fn test_emote_enum_all_have_distinct_emoji() {
+        let strs: Vec<&str> = EmoteEnum::ALL.iter().map(EmoteEnum::as_str).collect();
+        let unique: std::collections::HashSet<_> = strs.iter().collect();
+        assert_eq!(unique.len(), strs.len());
+    }
+
+    #[test]
+    fn test_emote_enum_from_body_roundtrips_through_as_str() {
+        for emote in EmoteEnum::ALL {
+            assert_eq!(EmoteEnum::from_body(emote.as_str()), Some(emote));
+        }
+        assert_eq!(EmoteEnum::from_body("just a regular taunt"), None);
+    }
+
+    #[test]
+    fn test_changed_since_true_once_version_advances() {
+        let mut game = game_for_timeout(None);
+        assert!(!game.changed_since(0));
+        game.version = 1;
+        assert!(game.changed_since(0));
+        assert!(!game.changed_since(1));
+    }
 }